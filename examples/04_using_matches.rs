@@ -48,6 +48,9 @@ fn main() {
 
     // If we wanted to some custom initialization based off some configuration file provided
     // by the user, we could get the file (A string of the file)
+    //
+    // NOTE: value_of() panics if the value isn't valid UTF-8, for paths which may contain
+    // arbitrary bytes prefer value_of_os() which returns the raw OsStr
     if let Some(ref file) = matches.value_of("config") {
         println!("Using config file: {}", file);
     }
@@ -1,9 +1,11 @@
 mod settings;
+mod unknown_args;
 pub use self::settings::{AppFlags, AppSettings};
+pub use self::unknown_args::{UnknownArgAction, UnknownArgHandler};
 
 // Std
 use std::env;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::fmt;
 use std::io::{self, BufRead, BufWriter, Write};
 use std::iter::Peekable;
@@ -17,9 +19,12 @@ use yaml_rust::Yaml;
 // Internal
 use crate::build::{Arg, ArgGroup, ArgSettings};
 use crate::mkeymap::MKeyMap;
-use crate::output::fmt::ColorWhen;
-use crate::output::{Help, Usage};
+use crate::output::fmt::{is_stdin_a_tty, ColorChoice, ColorSpec, ColorWhen, TermColor};
+use crate::output::{
+    write_templated_version, Help, HelpTree, Usage, UsageRenderer, UsageRendererHandle, UsageSpec,
+};
 use crate::parse::errors::Result as ClapResult;
+use crate::parse::features::response_files::expand_args;
 use crate::parse::{ArgMatcher, ArgMatches, Parser};
 use crate::util::{Key, HELP_HASH, VERSION_HASH};
 use crate::INTERNAL_ERROR_MSG;
@@ -78,6 +83,10 @@ pub struct App<'b> {
     #[doc(hidden)]
     pub long_version: Option<&'b str>,
     #[doc(hidden)]
+    pub version_template: Option<&'b str>,
+    #[doc(hidden)]
+    pub build_info: Option<&'b str>,
+    #[doc(hidden)]
     pub about: Option<&'b str>,
     #[doc(hidden)]
     pub long_about: Option<&'b str>,
@@ -86,6 +95,10 @@ pub struct App<'b> {
     #[doc(hidden)]
     pub pre_help: Option<&'b str>,
     #[doc(hidden)]
+    pub long_more_help: Option<&'b str>,
+    #[doc(hidden)]
+    pub long_pre_help: Option<&'b str>,
+    #[doc(hidden)]
     pub aliases: Option<Vec<(&'b str, bool)>>, // (name, visible)
     #[doc(hidden)]
     pub usage_str: Option<&'b str>,
@@ -100,8 +113,30 @@ pub struct App<'b> {
     #[doc(hidden)]
     pub max_w: Option<usize>,
     #[doc(hidden)]
+    pub min_w: Option<usize>,
+    #[doc(hidden)]
+    pub tab_w: Option<usize>,
+    #[doc(hidden)]
+    pub gutter_w: Option<usize>,
+    #[doc(hidden)]
     pub template: Option<&'b str>,
     #[doc(hidden)]
+    pub help_partials: Vec<(&'b str, &'b str)>,
+    #[doc(hidden)]
+    pub replacers: Vec<(&'b str, &'b [&'b str])>,
+    #[doc(hidden)]
+    pub unknown_arg_handler: Option<UnknownArgHandler<'b>>,
+    #[doc(hidden)]
+    pub usage_renderer: Option<UsageRendererHandle<'b>>,
+    #[doc(hidden)]
+    pub color: Option<ColorChoice>,
+    #[doc(hidden)]
+    pub good_color: Option<TermColor>,
+    #[doc(hidden)]
+    pub warning_color: Option<TermColor>,
+    #[doc(hidden)]
+    pub error_color: Option<TermColor>,
+    #[doc(hidden)]
     pub settings: AppFlags,
     #[doc(hidden)]
     pub g_settings: AppFlags,
@@ -143,6 +178,27 @@ impl<'b> App<'b> {
     /// Get the name of the binary
     pub fn get_bin_name(&self) -> Option<&str> { self.bin_name.as_ref().map(String::as_str) }
 
+    /// Get the short description of the app, as set by [`App::about`]
+    ///
+    /// [`App::about`]: ./struct.App.html#method.about
+    pub fn get_about(&self) -> Option<&str> { self.about }
+
+    /// Iterate over the direct subcommands of the app, in the order they were declared
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let app = App::new("git")
+    ///     .subcommand(App::new("clone").about("Clone a repository"))
+    ///     .subcommand(App::new("init").about("Create an empty repository"));
+    ///
+    /// for sc in app.subcommands_iter() {
+    ///     println!("{:10} {}", sc.get_name(), sc.get_about().unwrap_or(""));
+    /// }
+    /// ```
+    pub fn subcommands_iter(&self) -> impl Iterator<Item = &App<'b>> { subcommands!(self) }
+
     /// Sets a string of author(s) that will be displayed to the user when they
     /// request the help information with `--help` or `-h`.
     ///
@@ -176,6 +232,10 @@ impl<'b> App<'b> {
     ///
     /// **NOTE:** This command **should not** be used for [``]s.
     ///
+    /// **NOTE:** When this isn't set, the file name of the first argument parsed (i.e. `my_prog`
+    /// for `./target/release/my_prog`) is used, so usage strings and the version message show how
+    /// the program was invoked. On Windows a trailing `.exe` is left out.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -300,6 +360,54 @@ impl<'b> App<'b> {
         self
     }
 
+    /// Like [`App::after_help`], but only displayed when the user requests the long help with
+    /// `--help`. The short help from `-h` still displays [`App::after_help`], if set. This is
+    /// useful for lengthy examples which would clutter the short help.
+    ///
+    /// If only [`App::after_help`] is set, it's displayed for both `-h` and `--help`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, ErrorKind};
+    /// let app = App::new("myprog")
+    ///     .after_help("See --help for examples")
+    ///     .after_long_help("EXAMPLES:\n    myprog --fast");
+    ///
+    /// let err = app.clone().try_get_matches_from(vec!["myprog", "-h"]).unwrap_err();
+    /// assert!(err.message.contains("See --help for examples"));
+    /// assert!(!err.message.contains("EXAMPLES:"));
+    ///
+    /// let err = app.try_get_matches_from(vec!["myprog", "--help"]).unwrap_err();
+    /// assert!(err.message.contains("EXAMPLES:"));
+    /// assert!(!err.message.contains("See --help for examples"));
+    /// ```
+    /// [`App::after_help`]: ./struct.App.html#method.after_help
+    pub fn after_long_help<S: Into<&'b str>>(mut self, help: S) -> Self {
+        self.long_more_help = Some(help.into());
+        self
+    }
+
+    /// Like [`App::before_help`], but only displayed when the user requests the long help with
+    /// `--help`. The short help from `-h` still displays [`App::before_help`], if set.
+    ///
+    /// If only [`App::before_help`] is set, it's displayed for both `-h` and `--help`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// App::new("myprog")
+    ///     .before_help("myprog - does things")
+    ///     .before_long_help("myprog - does things\n\nSome longer introduction...")
+    /// # ;
+    /// ```
+    /// [`App::before_help`]: ./struct.App.html#method.before_help
+    pub fn before_long_help<S: Into<&'b str>>(mut self, help: S) -> Self {
+        self.long_pre_help = Some(help.into());
+        self
+    }
+
     /// Sets a string of the version number to be displayed when displaying version or help
     /// information with `-V`.
     ///
@@ -357,6 +465,57 @@ impl<'b> App<'b> {
         self
     }
 
+    /// Sets a template for the message displayed by `-V`/`--version`, instead of the default of
+    /// the binary name followed by the version.
+    ///
+    /// Tags are given inside curly brackets.
+    ///
+    /// Valid tags are:
+    ///
+    ///   * `{bin}`     - Binary name.
+    ///   * `{version}` - Version number, as chosen by [`App::version`] or
+    ///                   [`App::long_version`] depending on which flag was used.
+    ///   * `{build}`   - Build information, as set by [`App::build_info`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let app = App::new("myapp")
+    ///     .version("1.2.3")
+    ///     .build_info("abc123 2024-01-01")
+    ///     .version_template("{bin} {version} ({build})");
+    ///
+    /// let mut out = Vec::new();
+    /// app.write_version(&mut out).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "myapp 1.2.3 (abc123 2024-01-01)");
+    /// ```
+    /// [`App::version`]: ./struct.App.html#method.version
+    /// [`App::long_version`]: ./struct.App.html#method.long_version
+    /// [`App::build_info`]: ./struct.App.html#method.build_info
+    pub fn version_template<S: Into<&'b str>>(mut self, s: S) -> Self {
+        self.version_template = Some(s.into());
+        self
+    }
+
+    /// Sets build information, such as a commit hash or build date, to be used by the `{build}`
+    /// tag of [`App::version_template`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// App::new("myprog")
+    ///     .build_info("abc123 2024-01-01")
+    ///     .version_template("{bin} {version} ({build})")
+    /// # ;
+    /// ```
+    /// [`App::version_template`]: ./struct.App.html#method.version_template
+    pub fn build_info<S: Into<&'b str>>(mut self, info: S) -> Self {
+        self.build_info = Some(info.into());
+        self
+    }
+
     /// Overrides the `clap` generated usage string.
     ///
     /// This will be displayed to the user when errors are found in argument parsing.
@@ -381,6 +540,47 @@ impl<'b> App<'b> {
         self
     }
 
+    /// Builds the usage string with `renderer` instead of `clap`'s own layout, in both the help
+    /// message and errors. Unlike with [`App::override_usage`], errors still get a
+    /// "context-aware" usage string, since the renderer is told which arguments were used.
+    ///
+    /// The renderer only applies to this [`App`], not to its subcommands, and
+    /// [`App::override_usage`] takes precedence over it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, UsageParts, UsageRenderer};
+    /// struct Legacy;
+    ///
+    /// impl UsageRenderer for Legacy {
+    ///     fn render(&self, parts: &UsageParts) -> String {
+    ///         let mut usage = parts.name.to_owned();
+    ///         for r in &parts.required {
+    ///             usage.push_str(&format!(" {}", r));
+    ///         }
+    ///         for o in parts.options.iter().chain(parts.flags.iter()) {
+    ///             usage.push_str(&format!(" [{}]", o));
+    ///         }
+    ///         usage
+    ///     }
+    /// }
+    ///
+    /// let err = App::new("myprog")
+    ///     .arg(Arg::from("-c, --config <FILE> 'the config'").required(false))
+    ///     .arg(Arg::from("<input> 'the input'"))
+    ///     .usage_renderer(Box::new(Legacy))
+    ///     .try_get_matches_from(vec!["myprog", "--help"])
+    ///     .unwrap_err();
+    /// assert!(err.message.contains("myprog <input> [--config <FILE>]"));
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`App::override_usage`]: ./struct.App.html#method.override_usage
+    pub fn usage_renderer(mut self, renderer: Box<dyn UsageRenderer + 'b>) -> Self {
+        self.usage_renderer = Some(UsageRendererHandle::new(renderer));
+        self
+    }
+
     /// Overrides the `clap` generated help message. This should only be used
     /// when the auto-generated message does not suffice.
     ///
@@ -430,7 +630,8 @@ impl<'b> App<'b> {
     ///   * `{version}`     - Version number.
     ///   * `{author}`      - Author information.
     ///   * `{about}`       - General description (from [`App::about`])
-    ///   * `{usage}`       - Automatically generated or given usage string.
+    ///   * `{usage}`       - Automatically generated or given usage string, wrapped at the term
+    ///     width with continuation lines lined up under the first argument.
     ///   * `{all-args}`    - Help for all arguments (options, flags, positionals arguments,
     ///                       and subcommands) including titles.
     ///   * `{unified}`     - Unified help for options and flags. Note, you must *also* set
@@ -440,8 +641,20 @@ impl<'b> App<'b> {
     ///   * `{options}`     - Help for options.
     ///   * `{positionals}` - Help for positionals arguments.
     ///   * `{subcommands}` - Help for subcommands.
+    ///   * `{subcommands-all}` - Help for subcommands, including those marked
+    ///     [`AppSettings::Hidden`].
     ///   * `{after-help}`  - Help from [`App::after_help`]
     ///   * `{before-help}`  - Help from [`App::before_help`]
+    ///   * `{after-long-help}`  - Help from [`App::after_long_help`], or [`App::after_help`] if
+    ///     it isn't set
+    ///   * `{before-long-help}`  - Help from [`App::before_long_help`], or
+    ///     [`App::before_help`] if it isn't set
+    ///   * `{tab}`         - One level of indentation (see [`App::tab_width`])
+    ///   * `{columns:N}`   - Wraps everything after the tag at `N` columns instead of the
+    ///     terminal width (see [`App::set_term_width`]), `{columns:0}` doesn't wrap at all. Like
+    ///     any width `N` is raised to [`App::min_term_width`]
+    ///   * `{@name}`       - The partial template registered as `name` (see
+    ///     [`App::help_partial`])
     ///
     /// # Examples
     ///
@@ -457,12 +670,126 @@ impl<'b> App<'b> {
     /// [`App::about`]: ./struct.App.html#method.about
     /// [`App::after_help`]: ./struct.App.html#method.after_help
     /// [`App::before_help`]: ./struct.App.html#method.before_help
+    /// [`App::after_long_help`]: ./struct.App.html#method.after_long_help
+    /// [`App::before_long_help`]: ./struct.App.html#method.before_long_help
+    /// [`App::tab_width`]: ./struct.App.html#method.tab_width
+    /// [`App::set_term_width`]: ./struct.App.html#method.set_term_width
+    /// [`App::min_term_width`]: ./struct.App.html#method.min_term_width
+    /// [`App::help_partial`]: ./struct.App.html#method.help_partial
+    /// [`AppSettings::Hidden`]: ./enum.AppSettings.html#variant.Hidden
     /// [`AppSettings::UnifiedHelpMessage`]: ./enum.AppSettings.html#variant.UnifiedHelpMessage
     pub fn help_template<S: Into<&'b str>>(mut self, s: S) -> Self {
         self.template = Some(s.into());
         self
     }
 
+    /// Registers a named partial template, which help templates can then include with the
+    /// `{@name}` tag. Partials may use any of the tags of [`App::help_template`], including other
+    /// partials. Partials are inherited by subcommands, unless they register one of the same name.
+    ///
+    /// **NOTE:** Partials may only be nested a few levels deep, deeper nesting (such as a partial
+    /// including itself) is reported as an error when writing the help message. Unknown partial
+    /// names are written back as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog")
+    ///     .version("1.0")
+    ///     .about("Does things")
+    ///     .help_partial("header", "{bin} {version}\n{about}")
+    ///     .help_template("{@header}\n\n{usage}");
+    ///
+    /// let help = app.write_help_to_string().unwrap();
+    /// assert!(help.starts_with("myprog 1.0\nDoes things"));
+    /// ```
+    /// [`App::help_template`]: ./struct.App.html#method.help_template
+    pub fn help_partial<S: Into<&'b str>>(mut self, name: &'b str, s: S) -> Self {
+        self.help_partials.retain(|&(n, _)| n != name);
+        self.help_partials.push((name, s.into()));
+        self
+    }
+
+    /// Replaces the argument `name` with the arguments of `target` whenever it is given on the
+    /// command line, i.e. `myprog --sync` is parsed exactly as if the user typed
+    /// `myprog remote update --all`. This is useful to keep old or shortcut flags working after
+    /// the interface has changed.
+    ///
+    /// **NOTE:** Replacement happens at the raw token level, before anything is interpreted as a
+    /// flag, option or value. Only tokens matching `name` exactly are replaced, so `--sync=yes`
+    /// or `-s` combined with other short flags are left alone. The arguments of `target` are
+    /// never replaced themselves, so replacements can't loop.
+    ///
+    /// **NOTE:** Only the arguments of the [`App`] being parsed are replaced, replacements
+    /// registered on subcommands are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .replace("--sync", &["remote", "update", "--all"])
+    ///     .subcommand(App::new("remote")
+    ///         .subcommand(App::new("update")
+    ///             .arg(Arg::with_name("all").long("all"))))
+    ///     .get_matches_from(vec!["myprog", "--sync"]);
+    ///
+    /// let (_, remote) = m.subcommand();
+    /// let (name, update) = remote.unwrap().subcommand();
+    /// assert_eq!(name, "update");
+    /// assert!(update.unwrap().is_present("all"));
+    /// ```
+    /// [`App`]: ./struct.App.html
+    pub fn replace(mut self, name: &'b str, target: &'b [&'b str]) -> Self {
+        self.replacers.retain(|&(n, _)| n != name);
+        self.replacers.push((name, target));
+        self
+    }
+
+    /// Hands every argument starting with a hyphen which doesn't match any [`Arg`] (i.e.
+    /// `--unknown`, `--unknown=val` or `-u`) to `f`, which decides if it's an error, should be
+    /// collected for [`ArgMatches::unknown_args`], or ignored. This is handy for proxy programs
+    /// which forward the flags they don't know about to another program.
+    ///
+    /// Without a callback unknown arguments are always an error. The callback only applies to
+    /// this [`App`], not to its subcommands, and isn't asked about arguments accepted as values
+    /// because of [`AppSettings::AllowLeadingHyphen`] or [`AppSettings::AllowNegativeNumbers`].
+    ///
+    /// **NOTE:** A cluster of short flags is only passed to `f` (as a whole) when its first flag
+    /// is unknown, i.e. `-xv` but not `-vx` if only `-v` is known.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, UnknownArgAction};
+    /// let m = App::new("proxy")
+    ///     .arg(Arg::from("-v, --verbose 'be verbose'"))
+    ///     .on_unknown_arg(|arg| {
+    ///         if arg == "--quiet" {
+    ///             UnknownArgAction::Ignore
+    ///         } else {
+    ///             UnknownArgAction::Collect
+    ///         }
+    ///     })
+    ///     .get_matches_from(vec!["proxy", "--jobs=4", "-v", "--quiet", "-x"]);
+    ///
+    /// assert!(m.is_present("verbose"));
+    /// assert_eq!(m.unknown_args().collect::<Vec<_>>(), ["--jobs=4", "-x"]);
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`App`]: ./struct.App.html
+    /// [`ArgMatches::unknown_args`]: ./struct.ArgMatches.html#method.unknown_args
+    /// [`AppSettings::AllowLeadingHyphen`]: ./enum.AppSettings.html#variant.AllowLeadingHyphen
+    /// [`AppSettings::AllowNegativeNumbers`]: ./enum.AppSettings.html#variant.AllowNegativeNumbers
+    pub fn on_unknown_arg<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&OsStr) -> UnknownArgAction + 'b,
+    {
+        self.unknown_arg_handler = Some(UnknownArgHandler::new(f));
+        self
+    }
+
     /// Enables a single command, or [``], level settings.
     ///
     /// See [`AppSettings`] for a full list of possibilities and examples.
@@ -551,8 +878,13 @@ impl<'b> App<'b> {
     /// ignore terminal widths and use source formatting.
     ///
     /// `clap` automatically tries to determine the terminal width on Unix, Linux, OSX and Windows
-    /// if the `wrap_help` cargo "feature" has been used while compiling. If the terminal width
-    /// cannot be determined, `clap` defaults to `120`.
+    /// if the `wrap_help` cargo "feature" has been used while compiling. The `COLUMNS` environment
+    /// variable takes precedence over the detected width when it's set to a positive number. If
+    /// the terminal width cannot be determined, `clap` defaults to `120`. Either way the width is
+    /// limited by [`App::max_term_width`], while a width set here always wins.
+    ///
+    /// **NOTE:** Widths below the minimum of [`App::min_term_width`] (`20` by default) are raised
+    /// to it.
     ///
     /// **NOTE:** This setting applies globally and *not* on a per-command basis.
     ///
@@ -572,11 +904,97 @@ impl<'b> App<'b> {
     ///     .set_term_width(80)
     /// # ;
     /// ```
+    /// [`App::min_term_width`]: ./struct.App.html#method.min_term_width
+    /// [`App::max_term_width`]: ./struct.App.html#method.max_term_width
     pub fn set_term_width(mut self, width: usize) -> Self {
         self.term_w = Some(width);
         self
     }
 
+    /// Sets when to color the help and error messages in a single call, taking precedence over
+    /// [`AppSettings::ColoredHelp`], [`AppSettings::ColorAuto`], [`AppSettings::ColorAlways`] and
+    /// [`AppSettings::ColorNever`]. Any [`ColorChoice`] other than `Never` also turns on colored
+    /// help, so `ColoredHelp` on its own behaves like [`ColorChoice::Auto`].
+    ///
+    /// **NOTE:** [`AppSettings::DisableColoredHelp`] still disables colors in the help message.
+    ///
+    /// **NOTE:** Subcommands which don't set a choice of their own inherit this one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, ColorChoice};
+    /// App::new("myprog")
+    ///     .color(ColorChoice::Never)
+    /// # ;
+    /// ```
+    /// [`ColorChoice`]: ./enum.ColorChoice.html
+    /// [`ColorChoice::Auto`]: ./enum.ColorChoice.html#variant.Auto
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::ColorAuto`]: ./enum.AppSettings.html#variant.ColorAuto
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    /// [`AppSettings::ColorNever`]: ./enum.AppSettings.html#variant.ColorNever
+    /// [`AppSettings::DisableColoredHelp`]: ./enum.AppSettings.html#variant.DisableColoredHelp
+    pub fn color(mut self, choice: ColorChoice) -> Self {
+        self.color = Some(choice);
+        self
+    }
+
+    /// Sets the color of "good" things in help and error messages, such as the arguments in the
+    /// help message, in place of the default green. Like any color it's only used when the
+    /// messages are colored at all.
+    ///
+    /// **NOTE:** Subcommands which don't set a color of their own inherit this one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, TermColor};
+    /// App::new("myprog")
+    ///     .good_color(TermColor::Rgb(0, 135, 95))
+    /// # ;
+    /// ```
+    pub fn good_color(mut self, color: TermColor) -> Self {
+        self.good_color = Some(color);
+        self
+    }
+
+    /// Sets the color of warnings in help and error messages, such as the section titles of the
+    /// help message and the offending values of errors, in place of the default yellow.
+    ///
+    /// **NOTE:** Subcommands which don't set a color of their own inherit this one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, TermColor};
+    /// App::new("myprog")
+    ///     .warning_color(TermColor::Ansi256(208))
+    /// # ;
+    /// ```
+    pub fn warning_color(mut self, color: TermColor) -> Self {
+        self.warning_color = Some(color);
+        self
+    }
+
+    /// Sets the color of the `error:` prefix of error messages in place of the default red. It's
+    /// always written in bold.
+    ///
+    /// **NOTE:** Subcommands which don't set a color of their own inherit this one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, TermColor};
+    /// App::new("myprog")
+    ///     .error_color(TermColor::Ansi16(13))
+    /// # ;
+    /// ```
+    pub fn error_color(mut self, color: TermColor) -> Self {
+        self.error_color = Some(color);
+        self
+    }
+
     /// Sets the max terminal width at which to wrap help messages. Using `0` will ignore terminal
     /// widths and use source formatting.
     ///
@@ -605,6 +1023,74 @@ impl<'b> App<'b> {
         self
     }
 
+    /// Sets the narrowest terminal width help messages are wrapped at, defaults to `20`. Narrower
+    /// widths, whether detected, set with [`App::set_term_width`] or with the `{columns:N}` tag of
+    /// [`App::help_template`], are raised to this, because wrapping at only a few columns leaves a
+    /// word or less per line. Using `0` allows any width.
+    ///
+    /// **NOTE:** Disabling wrapping (i.e. `set_term_width(0)`) isn't affected.
+    ///
+    /// **NOTE:** This setting applies globally and *not* on a per-command basis.
+    ///
+    /// **NOTE:** This setting must be set **before** any subcommands are added!
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// App::new("myprog")
+    ///     .min_term_width(40)
+    /// # ;
+    /// ```
+    /// [`App::set_term_width`]: ./struct.App.html#method.set_term_width
+    /// [`App::help_template`]: ./struct.App.html#method.help_template
+    pub fn min_term_width(mut self, w: usize) -> Self {
+        self.min_w = Some(w);
+        self
+    }
+
+    /// Sets the number of spaces used for each level of indentation in the help message.
+    /// Defaults to `4`. Using `0` disables indentation entirely. This is also the width written
+    /// by the `{tab}` tag in a [`App::help_template`].
+    ///
+    /// **NOTE:** Subcommands always indent like the app they're part of, the width set here
+    /// replaces theirs once the app is built.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// App::new("myprog")
+    ///     .tab_width(2)
+    /// # ;
+    /// ```
+    /// [`App::help_template`]: ./struct.App.html#method.help_template
+    pub fn tab_width(mut self, w: usize) -> Self {
+        self.tab_w = Some(w);
+        self
+    }
+
+    /// Sets a fixed number of spaces between each argument (or subcommand) and its help in the
+    /// help message. By default the help is aligned into a column based on the longest argument,
+    /// with a fixed gutter each help starts right after its own argument instead.
+    ///
+    /// **NOTE:** If the gutter would push the help past the terminal width, the help is moved to
+    /// the next line just like with [`AppSettings::NextLineHelp`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// App::new("myprog")
+    ///     .help_gutter(2)
+    /// # ;
+    /// ```
+    /// [`AppSettings::NextLineHelp`]: ./enum.AppSettings.html#variant.NextLineHelp
+    pub fn help_gutter(mut self, w: usize) -> Self {
+        self.gutter_w = Some(w);
+        self
+    }
+
     /// Adds an [argument] to the list of valid possibilities.
     ///
     /// # Examples
@@ -632,7 +1118,10 @@ impl<'b> App<'b> {
         } else {
             None
         };
-        let arg = a.into().help_heading(help_heading);
+        let mut arg = a.into();
+        if arg.help_heading.is_none() {
+            arg.help_heading = help_heading;
+        }
         self.args.push(arg);
         self
     }
@@ -901,6 +1390,46 @@ impl<'b> App<'b> {
         self
     }
 
+    /// Adds a hidden `completions <shell>` subcommand which writes the completion script for
+    /// `shell` (one of `bash`, `elvish`, `fish`, `powershell` or `zsh`) to `stdout` and exits, so
+    /// users can install the completions without a separate binary, i.e.
+    /// `myprog completions bash > /etc/bash_completion.d/myprog`. The script completes the binary name the program
+    /// was run as.
+    ///
+    /// Using the subcommand makes the `try_get_matches` family of methods return an [`Error`] of
+    /// kind [`ErrorKind::CompletionsDisplayed`] holding the script, which [`Error::exit`] prints.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .arg(Arg::from("-v, --verbose 'be verbose'"))
+    ///     .gen_completions_subcommand()
+    ///     .try_get_matches_from(vec!["myprog", "completions", "bash"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    /// assert!(err.message.contains("complete -F _myprog"));
+    /// ```
+    /// [`Error`]: ./struct.Error.html
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`ErrorKind::CompletionsDisplayed`]: ./enum.ErrorKind.html#variant.CompletionsDisplayed
+    pub fn gen_completions_subcommand(self) -> Self {
+        self.subcommand(
+            App::new("completions")
+                .about("Writes the completion script for a shell")
+                .setting(AppSettings::Hidden)
+                .setting(AppSettings::CompletionsSubcommand)
+                .arg(
+                    Arg::with_name("shell")
+                        .help("The shell to complete")
+                        .required(true)
+                        .possible_values(&["bash", "elvish", "fish", "powershell", "zsh"]),
+                ),
+        )
+    }
+
     /// Allows custom ordering of [``]s within the help message. Subcommands with a lower
     /// value will be displayed first in the help message. This is helpful when one would like to
     /// emphasise frequently used subcommands, or prioritize those towards the top of the list.
@@ -998,6 +1527,9 @@ impl<'b> App<'b> {
     /// **NOTE:** clap has the ability to distinguish between "short" and "long" help messages
     /// depending on if the user ran [`-h` (short)] or [`--help` (long)]
     ///
+    /// **NOTE:** The app is finalized on the first call, so this may be called any number of
+    /// times, i.e. from a custom `help` subcommand.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -1010,13 +1542,10 @@ impl<'b> App<'b> {
     /// [`-h` (short)]: ./struct.Arg.html#method.help
     /// [`--help` (long)]: ./struct.Arg.html#method.long_help
     pub fn print_help(&mut self) -> ClapResult<()> {
-        // If there are global arguments, or settings we need to propagate them down to subcommands
-        // before parsing incase we run into a subcommand
-        self._build();
-
         let out = io::stdout();
         let mut buf_w = BufWriter::new(out.lock());
-        self.write_help(&mut buf_w)
+        self.write_help(&mut buf_w)?;
+        buf_w.flush().map_err(From::from)
     }
 
     /// Prints the full help message to [`io::stdout()`] using a [`BufWriter`] using the same
@@ -1037,13 +1566,10 @@ impl<'b> App<'b> {
     /// [`-h` (short)]: ./struct.Arg.html#method.help
     /// [`--help` (long)]: ./struct.Arg.html#method.long_help
     pub fn print_long_help(&mut self) -> ClapResult<()> {
-        // If there are global arguments, or settings we need to propagate them down to subcommands
-        // before parsing incase we run into a subcommand
-        self._build();
-
         let out = io::stdout();
         let mut buf_w = BufWriter::new(out.lock());
-        self.write_long_help(&mut buf_w)
+        self.write_long_help(&mut buf_w)?;
+        buf_w.flush().map_err(From::from)
     }
 
     /// Writes the full help message to the user to a [`io::Write`] object in the same method as if
@@ -1065,10 +1591,7 @@ impl<'b> App<'b> {
     /// [`-h` (short)]: ./struct.Arg.html#method.help
     /// [`--help` (long)]: ./struct.Arg.html#method.long_help
     pub fn write_help<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
-        self._build();
-
-        let p = Parser::new(self);
-        Help::new(w, &p, false, false).write_help()
+        self._write_help(w, false)
     }
 
     /// Writes the full help message to the user to a [`io::Write`] object in the same method as if
@@ -1090,10 +1613,75 @@ impl<'b> App<'b> {
     /// [`-h` (short)]: ./struct.Arg.html#method.help
     /// [`--help` (long)]: ./struct.Arg.html#method.long_help
     pub fn write_long_help<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
-        self._build();
+        self._write_help(w, true)
+    }
+
+    // The app is finalized the same way as before parsing, but only once, so the help messages
+    // can be written any number of times and still match the one shown for `--help`
+    fn _write_help<W: Write>(&mut self, w: &mut W, use_long: bool) -> ClapResult<()> {
+        if !self.settings.is_set(AppSettings::Built) {
+            self._build();
+        }
 
-        let p = Parser::new(self);
-        Help::new(w, &p, true, false).write_help()
+        let mut p = Parser::new(self);
+        p._build();
+        Help::new(w, &p, use_long, false).write_help()
+    }
+
+    /// Renders the full help message into a [`String`] in the same method as if the user ran `-h`
+    ///
+    /// A [`String`] isn't a terminal, so the returned string only contains the ANSI escape codes
+    /// of colors when [`AppSettings::ColoredHelp`] is in effect along with
+    /// [`AppSettings::ColorAlways`], whatever stdout is connected to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog");
+    /// let help = app.write_help_to_string().expect("failed to render help");
+    /// assert!(help.contains("myprog"));
+    /// ```
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    pub fn write_help_to_string(&mut self) -> ClapResult<String> {
+        self._write_help_to_string(false)
+    }
+
+    /// Renders the full help message into a [`String`] in the same method as if the user ran
+    /// `--help`
+    ///
+    /// A [`String`] isn't a terminal, so the returned string only contains the ANSI escape codes
+    /// of colors when [`AppSettings::ColoredHelp`] is in effect along with
+    /// [`AppSettings::ColorAlways`], whatever stdout is connected to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog");
+    /// let help = app.write_long_help_to_string().expect("failed to render help");
+    /// assert!(help.contains("myprog"));
+    /// ```
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    pub fn write_long_help_to_string(&mut self) -> ClapResult<String> {
+        self._write_help_to_string(true)
+    }
+
+    // Colors which depend on stdout being a terminal are left out, whatever stdout is
+    fn _write_help_to_string(&mut self, use_long: bool) -> ClapResult<String> {
+        let color = self.color;
+        if self.color_when() == ColorWhen::Auto {
+            self.color = Some(ColorChoice::Never);
+        }
+        let mut buf = Vec::new();
+        let res = self._write_help(&mut buf, use_long);
+        self.color = color;
+        res?;
+        String::from_utf8(buf).map_err(From::from)
     }
 
     /// Writes the version message to the user to a [`io::Write`] object as if the user ran `-V`.
@@ -1138,7 +1726,22 @@ impl<'b> App<'b> {
         self._write_version(w, true).map_err(From::from)
     }
 
-    /// @TODO-v3-alpha @docs @p2: write docs
+    /// Returns the usage string, including the `USAGE:` title, exactly as it's shown in the help
+    /// message and in errors. The [`App`] is built first (positional indices computed, help and
+    /// version args added, etc.) so the usage is complete. Nothing is written to stdout and the
+    /// process isn't exited, which makes this useful for reporting errors from your own
+    /// validation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog")
+    ///     .arg("-v 'be verbose'")
+    ///     .arg("<input> 'the input'");
+    /// assert_eq!(app.generate_usage(), "USAGE:\n    myprog [FLAGS] <input>");
+    /// ```
+    /// [`App`]: ./struct.App.html
     pub fn generate_usage(&mut self) -> String {
         // If there are global arguments, or settings we need to propgate them down to subcommands
         // before parsing incase we run into a subcommand
@@ -1151,6 +1754,64 @@ impl<'b> App<'b> {
         Usage::new(&parser).create_usage_with_title(&[])
     }
 
+    /// Returns a structured description of the usage, listing the required arguments, the
+    /// positional arguments in order, the optional flags and options, and whether there are any
+    /// subcommands. This is meant for tooling which would otherwise have to parse the usage string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog")
+    ///     .arg("-v 'be verbose'")
+    ///     .arg("<input> 'the input'")
+    ///     .arg("[output] 'the output'");
+    /// let spec = app.generate_usage_spec();
+    /// assert_eq!(spec.required, ["input"]);
+    /// assert_eq!(spec.positionals, ["input", "output"]);
+    /// assert!(spec.flags.contains(&"v"));
+    /// assert!(!spec.has_subcommands);
+    /// ```
+    pub fn generate_usage_spec(&mut self) -> UsageSpec<'b> {
+        if !self.settings.is_set(AppSettings::Built) {
+            self._build();
+        }
+
+        let mut parser = Parser::new(self);
+        parser._build();
+        Usage::new(&parser).create_usage_spec()
+    }
+
+    /// Returns a structured model of the help message instead of text: the app's metadata, each
+    /// args section with its entries (name as written, help text and the special values such as
+    /// `[default: auto]`) and the subcommands, all in the order they'd be written. This is meant
+    /// for programs which display the help in their own widgets. No colors are included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myprog")
+    ///     .about("does things")
+    ///     .arg(Arg::from("-c, --config <FILE> 'the config'").default_value("my.cfg"))
+    ///     .subcommand(App::new("test").about("tests things"));
+    /// let tree = app.generate_help_tree();
+    /// assert_eq!(tree.about, Some("does things"));
+    /// assert_eq!(tree.sections[1].title, "OPTIONS");
+    /// let config = &tree.sections[1].entries[0];
+    /// assert_eq!(config.name, "-c, --config <FILE>");
+    /// assert_eq!(config.help, Some("the config"));
+    /// assert_eq!(config.spec_vals, "[default: my.cfg]");
+    /// assert_eq!(tree.subcommands[1].name, "test");
+    /// ```
+    pub fn generate_help_tree(&mut self) -> HelpTree<'b> { self._help_tree(false) }
+
+    /// Returns a structured model of the long help message (i.e. `--help`), like
+    /// [`App::generate_help_tree`] does for the short one.
+    ///
+    /// [`App::generate_help_tree`]: ./struct.App.html#method.generate_help_tree
+    pub fn generate_long_help_tree(&mut self) -> HelpTree<'b> { self._help_tree(true) }
+
     /// Starts the parsing process, upon a failed parse an error will be displayed to the user and
     /// the process will exit with the appropriate error code. By default this method gets all user
     /// provided arguments from [`env::args_os`] in order to allow for invalid UTF-8 code points,
@@ -1186,12 +1847,7 @@ impl<'b> App<'b> {
                 // Otherwise, write to stderr and exit
                 if e.use_stderr() {
                     wlnerr!("{}", e.message);
-                    if self.settings.is_set(AppSettings::WaitOnError) {
-                        wlnerr!("\nPress [ENTER] / [RETURN] to continue...");
-                        let mut s = String::new();
-                        let i = io::stdin();
-                        i.lock().read_line(&mut s).unwrap();
-                    }
+                    self.wait_on_error();
                     drop(e);
                     process::exit(1);
                 }
@@ -1260,12 +1916,7 @@ impl<'b> App<'b> {
             // Otherwise, write to stderr and exit
             if e.use_stderr() {
                 wlnerr!("{}", e.message);
-                if self.settings.is_set(AppSettings::WaitOnError) {
-                    wlnerr!("\nPress [ENTER] / [RETURN] to continue...");
-                    let mut s = String::new();
-                    let i = io::stdin();
-                    i.lock().read_line(&mut s).unwrap();
-                }
+                self.wait_on_error();
                 drop(self);
                 drop(e);
                 process::exit(1);
@@ -1323,6 +1974,15 @@ impl<'b> App<'b> {
     /// **NOTE:** The first argument will be parsed as the binary name unless
     /// [`AppSettings::NoBinaryName`] is used
     ///
+    /// **NOTE:** The same [`App`] may be parsed any number of times; state gathered while parsing
+    /// one argument list (such as having seen `--`) does not carry over into the next call.
+    ///
+    /// **NOTE:** Arguments are pulled from `itr` one at a time as they're parsed, nothing is
+    /// collected up front. Parsing stops pulling as soon as the result is known, i.e. once `--help`
+    /// is found, so a lazy iterator (such as tokens from a REPL) is never read further than needed.
+    /// The exception is [`AppSettings::ExpandArgsFromFile`], which reads all of `itr` and the files
+    /// it names before parsing starts.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -1337,6 +1997,7 @@ impl<'b> App<'b> {
     /// [`App`]: ./struct.App.html
     /// [`App::try_get_matches_from`]: ./struct.App.html#method.try_get_matches_from
     /// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+    /// [`AppSettings::ExpandArgsFromFile`]: ./enum.AppSettings.html#variant.ExpandArgsFromFile
     pub fn try_get_matches_from_mut<I, T>(&mut self, itr: I) -> ClapResult<ArgMatches>
     where
         I: IntoIterator<Item = T>,
@@ -1346,31 +2007,87 @@ impl<'b> App<'b> {
         // Get the name of the program (argument 1 of env::args()) and determine the
         // actual file
         // that was used to execute the program. This is because a program called
-        // ./target/release/my_prog -a
-        // will have two arguments, './target/release/my_prog', '-a' but we don't want
+        // ./target/release/my_prog.exe -a
+        // will have two arguments, './target/release/my_prog.exe', '-a' but we don't want
         // to display
-        // the full path when displaying help messages and such
+        // the full path (or the .exe on Windows) when displaying help messages and such. Other
+        // extensions stay, as in python3.8. An empty argv[0] has no file name and leaves
+        // bin_name unset
         if !self.settings.is_set(AppSettings::NoBinaryName) {
             if let Some(name) = it.next() {
                 let bn_os = name.into();
                 let p = Path::new(&*bn_os);
-                if let Some(f) = p.file_name() {
-                    if let Some(s) = f.to_os_string().to_str() {
-                        if self.bin_name.is_none() {
-                            self.bin_name = Some(s.to_owned());
-                        }
+                if let Some(s) = p.file_name().and_then(OsStr::to_str) {
+                    let s = if cfg!(windows) && s.to_ascii_lowercase().ends_with(".exe") {
+                        &s[..s.len() - 4]
+                    } else {
+                        s
+                    };
+                    if self.bin_name.is_none() && !s.is_empty() {
+                        self.bin_name = Some(s.to_owned());
                     }
                 }
             }
         }
 
-        self._do_parse(&mut it.peekable())
+        if self.is_set(AppSettings::ExpandArgsFromFile) {
+            let args = expand_args(it)?;
+            return self._do_parse_replaced(args.into_iter());
+        }
+        self._do_parse_replaced(it)
     }
 }
 
 // Internally used only
 #[doc(hidden)]
 impl<'b> App<'b> {
+    // Pauses before an error exit so the message can be read, but only when a user could
+    // actually answer, otherwise pipelines would hang
+    fn wait_on_error(&self) {
+        if self.settings.is_set(AppSettings::WaitOnError) && is_stdin_a_tty() {
+            wlnerr!("\nPress [ENTER] / [RETURN] to continue...");
+            let mut s = String::new();
+            let i = io::stdin();
+            let _ = i.lock().read_line(&mut s);
+        }
+    }
+
+    // Clears the settings the parser flips while walking argv, so a reused App (i.e. via
+    // try_get_matches_from_mut) starts every parse from the same state
+    pub(crate) fn _reset_parse_state(&mut self) {
+        self.unset(AppSettings::TrailingValues);
+        self.unset(AppSettings::ValidArgFound);
+        self.unset(AppSettings::ValidNegNumFound);
+    }
+
+    // Applies the replacements of App::replace in a single pass (lazily, like the rest of argv)
+    // before handing the arguments to the parser
+    #[doc(hidden)]
+    fn _do_parse_replaced<I, T>(&mut self, it: I) -> ClapResult<ArgMatches>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        if self.replacers.is_empty() {
+            return self._do_parse(&mut it.peekable());
+        }
+        let replacers = self.replacers.clone();
+        let mut it = it
+            .flat_map(move |a| {
+                let a: OsString = a.into();
+                let target = a
+                    .to_str()
+                    .and_then(|s| replacers.iter().find(|&&(n, _)| n == s))
+                    .map(|&(_, t)| t);
+                match target {
+                    Some(t) => t.iter().map(OsString::from).collect::<Vec<_>>(),
+                    None => vec![a],
+                }
+            })
+            .peekable();
+        self._do_parse(&mut it)
+    }
+
     #[doc(hidden)]
     fn _do_parse<I, T>(&mut self, it: &mut Peekable<I>) -> ClapResult<ArgMatches>
     where
@@ -1378,13 +2095,14 @@ impl<'b> App<'b> {
         T: Into<OsString> + Clone,
     {
         debugln!("App::_do_parse;");
-        let mut matcher = ArgMatcher::new();
 
         // If there are global arguments, or settings we need to propgate them down to subcommands
         // before parsing incase we run into a subcommand
         if !self.settings.is_set(AppSettings::Built) {
             self._build();
         }
+        self._reset_parse_state();
+        let mut matcher = ArgMatcher::for_app(self);
 
         {
             let mut parser = Parser::new(self);
@@ -1423,6 +2141,9 @@ impl<'b> App<'b> {
             true
         });
 
+        // Positionals without an index are numbered in the order they were declared, skipping the
+        // indices which were given explicitly
+        let taken: Vec<u64> = positionals!(self).filter_map(|p| p.index).collect();
         let mut pos_counter = 1;
         for a in self.args.args.iter_mut() {
             // Fill in the groups
@@ -1450,16 +2171,62 @@ impl<'b> App<'b> {
             }
             a._build();
             if a.short.is_none() && a.long.is_none() && a.index.is_none() {
+                while taken.contains(&pos_counter) {
+                    pos_counter += 1;
+                }
                 a.index = Some(pos_counter);
                 pos_counter += 1;
             }
         }
+        self._build_conflicts();
+
+        if self.is_set(AppSettings::HelpExpected) {
+            for a in self.args.args.iter().filter(|a| !a.is_set(ArgSettings::Hidden)) {
+                assert!(
+                    a.help.is_some() || a.long_help.is_some(),
+                    "AppSettings::HelpExpected is set for `{}`, but argument `{}` has no help text",
+                    self.name,
+                    a.name
+                );
+            }
+        }
 
         debug_assert!(self._app_debug_asserts());
         self.args._build();
         self.settings.set(AppSettings::Built);
     }
 
+    // Conflicts go both ways, so every arg also gets the args conflicting with it added to its own
+    // conflicts. Conflicts with groups are left as they are.
+    fn _build_conflicts(&mut self) {
+        debugln!("App::_build_conflicts;");
+        let mut reverse = vec![];
+        for a in self.args.args.iter() {
+            if let Some(ref bl) = a.blacklist {
+                for &c in bl.iter().filter(|&&c| c != a.id) {
+                    if self.args.args.iter().any(|x| x.id == c) {
+                        reverse.push((c, a.id));
+                    }
+                }
+            }
+        }
+        for (c, id) in reverse {
+            let a = self
+                .args
+                .args
+                .iter_mut()
+                .find(|x| x.id == c)
+                .expect(INTERNAL_ERROR_MSG);
+            if let Some(ref mut bl) = a.blacklist {
+                if !bl.contains(&id) {
+                    bl.push(id);
+                }
+                continue;
+            }
+            a.blacklist = Some(vec![id]);
+        }
+    }
+
     // Perform some expensive assertions on the Parser itself
     fn _app_debug_asserts(&mut self) -> bool {
         debugln!("App::_app_debug_asserts;");
@@ -1471,6 +2238,28 @@ impl<'b> App<'b> {
                 ));
             }
         }
+        // Positional indices must be unique and contiguous starting at 1, which is only known once
+        // the missing ones were filled in. Like every check here it only runs with debug
+        // assertions, so the tests of it are `#[cfg(debug_assertions)]`
+        let mut pos: Vec<_> = positionals!(self)
+            .filter_map(|p| p.index.map(|i| (i, p.name)))
+            .collect();
+        pos.sort_by_key(|&(i, _)| i);
+        for (n, &(idx, name)) in pos.iter().enumerate() {
+            if n > 0 && pos[n - 1].0 == idx {
+                panic!(
+                    "positional index {} is used by both `{}` and `{}`",
+                    idx,
+                    pos[n - 1].1,
+                    name
+                );
+            }
+            assert!(
+                idx == n as u64 + 1,
+                "index {} missing; indices must be contiguous starting at 1",
+                n + 1
+            );
+        }
         // * Args listed inside groups should exist
         // * Groups should not have naming conflicts with Args
 
@@ -1500,14 +2289,31 @@ impl<'b> App<'b> {
                     if vsc {
                         $sc.set(AppSettings::DisableVersion);
                     }
-                    if gv && $sc.version.is_none() && $_self.version.is_some() {
+                    // A subcommand with a version of its own keeps it, long version included
+                    let has_ver = $sc.version.is_some() || $sc.long_version.is_some();
+                    if gv && !has_ver {
                         $sc.set(AppSettings::GlobalVersion);
-                        $sc.version = Some($_self.version.unwrap());
+                        $sc.version = $_self.version;
+                        $sc.long_version = $_self.long_version;
                     }
                     $sc.settings = $sc.settings | $_self.g_settings;
                     $sc.g_settings = $sc.g_settings | $_self.g_settings;
                     $sc.term_w = $_self.term_w;
                     $sc.max_w = $_self.max_w;
+                    $sc.min_w = $_self.min_w;
+                    $sc.tab_w = $_self.tab_w;
+                    $sc.gutter_w = $_self.gutter_w;
+                    if $sc.color.is_none() {
+                        $sc.color = $_self.color;
+                    }
+                    $sc.good_color = $sc.good_color.or($_self.good_color);
+                    $sc.warning_color = $sc.warning_color.or($_self.warning_color);
+                    $sc.error_color = $sc.error_color.or($_self.error_color);
+                    for &(name, partial) in &$_self.help_partials {
+                        if !$sc.help_partials.iter().any(|&(n, _)| n == name) {
+                            $sc.help_partials.push((name, partial));
+                        }
+                    }
                 }
                 {
                     for a in $_self.args.args.iter().filter(|a| a.global) {
@@ -1546,13 +2352,14 @@ impl<'b> App<'b> {
             .args
             .args
             .iter()
-            .any(|x| x.long == Some("help") || x.id == HELP_HASH))
+            .any(|x| x.long == Some("help") || x.id == HELP_HASH)
+            || self.is_set(AppSettings::DisableHelpFlags))
         {
             debugln!("App::_create_help_and_version: Building --help");
             let mut help = Arg::with_name("help")
                 .long("help")
                 .help("Prints help information");
-            if !self.args.args.iter().any(|x| x.short == Some('h')) {
+            if !self.args.args.iter().any(|x| x.has_short('h')) {
                 help = help.short('h');
             }
 
@@ -1569,7 +2376,7 @@ impl<'b> App<'b> {
             let mut version = Arg::with_name("version")
                 .long("version")
                 .help("Prints version information");
-            if !self.args.args.iter().any(|x| x.short == Some('V')) {
+            if !self.args.args.iter().any(|x| x.has_short('V')) {
                 version = version.short('V');
             }
 
@@ -1625,29 +2432,19 @@ impl<'b> App<'b> {
             );
         }
 
-        // Short conflicts
-        if let Some(s) = a.short {
+        // Short conflicts, aliases included
+        for s in a
+            .short
+            .iter()
+            .chain(a.short_aliases.iter().flat_map(|als| als.iter().map(|(c, _)| c)))
+        {
             assert!(
-                self.args.args.iter().filter(|x| x.short == Some(s)).count() < 2,
+                self.args.args.iter().filter(|x| x.has_short(*s)).count() < 2,
                 "Argument short must be unique\n\n\t-{} is already in use",
                 s
             );
         }
 
-        if let Some(idx) = a.index {
-            // No index conflicts
-            assert!(
-                positionals!(self).fold(0, |acc, p| if p.index == Some(idx) {
-                    acc + 1
-                } else {
-                    acc
-                }) < 2,
-                "Argument '{}' has the same index as another positional \
-                 argument\n\n\tUse Arg::setting(ArgSettings::MultipleValues) to allow one \
-                 positional argument to take multiple values",
-                a.name
-            );
-        }
         if a.is_set(ArgSettings::Last) {
             assert!(
                 a.long.is_none(),
@@ -1681,9 +2478,8 @@ impl<'b> App<'b> {
             if sc.bin_name.is_none() {
                 sdebugln!("No");
                 let bin_name = format!(
-                    "{}{}{}",
+                    "{} {}",
                     self.bin_name.as_ref().unwrap_or(&self.name.clone()),
-                    if self.bin_name.is_some() { " " } else { "" },
                     &*sc.name
                 );
                 debugln!(
@@ -1703,6 +2499,17 @@ impl<'b> App<'b> {
         }
     }
 
+    fn _help_tree(&mut self, use_long: bool) -> HelpTree<'b> {
+        if !self.settings.is_set(AppSettings::Built) {
+            self._build();
+        }
+
+        let mut parser = Parser::new(self);
+        parser._build();
+        let mut sink = io::sink();
+        Help::new(&mut sink, &parser, use_long, false).help_tree()
+    }
+
     pub(crate) fn _write_version<W: Write>(&self, w: &mut W, use_long: bool) -> io::Result<()> {
         debugln!("App::_write_version;");
         let ver = if use_long {
@@ -1712,15 +2519,15 @@ impl<'b> App<'b> {
             self.version
                 .unwrap_or_else(|| self.long_version.unwrap_or(""))
         };
-        if let Some(bn) = self.bin_name.as_ref() {
-            if bn.contains(' ') {
-                // Incase we're dealing with subcommands i.e. git mv is translated to git-mv
-                write!(w, "{} {}", bn.replace(" ", "-"), ver)
-            } else {
-                write!(w, "{} {}", &self.name[..], ver)
-            }
+        let bin = match self.bin_name.as_ref() {
+            // Incase we're dealing with subcommands i.e. git mv is translated to git-mv
+            Some(bn) => bn.replace(" ", "-"),
+            None => self.name.clone(),
+        };
+        if let Some(t) = self.version_template {
+            write_templated_version(w, t, &bin, ver, self.build_info)
         } else {
-            write!(w, "{} {}", &self.name[..], ver)
+            write!(w, "{} {}", bin, ver)
         }
     }
 
@@ -1749,11 +2556,33 @@ impl<'b> App<'b> {
         self.args.args.iter().find(|a| a.id == arg_id)
     }
 
-    // Should we color the output? None=determined by output location, true=yes, false=no
+    // When to color error messages, and in which colors
+    pub(crate) fn color_spec(&self) -> ColorSpec {
+        ColorSpec {
+            when: self.color_when(),
+            good: self.good_color,
+            warning: self.warning_color,
+            error: self.error_color,
+        }
+    }
+
+    // Should the help message be colored at all? App::color overrides ColoredHelp
+    pub(crate) fn colored_help(&self) -> bool {
+        match self.color {
+            Some(c) => c != ColorChoice::Never,
+            None => self.is_set(AppSettings::ColoredHelp),
+        }
+    }
+
+    // Should we color the output? Auto=determined by output location, Always=yes, Never=no
     #[doc(hidden)]
-    pub fn color(&self) -> ColorWhen {
-        debugln!("App::color;");
-        debug!("App::color: Color setting...");
+    pub fn color_when(&self) -> ColorWhen {
+        debugln!("App::color_when;");
+        if let Some(c) = self.color {
+            debugln!("App::color_when: App::color={:?}", c);
+            return c.into();
+        }
+        debug!("App::color_when: Color setting...");
         if self.is_set(AppSettings::ColorNever) {
             sdebugln!("Never");
             ColorWhen::Never
@@ -1909,6 +2738,8 @@ impl<'a> From<&'a Yaml> for App<'a> {
         yaml_str!(a, yaml, about);
         yaml_str!(a, yaml, before_help);
         yaml_str!(a, yaml, after_help);
+        yaml_str!(a, yaml, before_long_help);
+        yaml_str!(a, yaml, after_long_help);
         yaml_str!(a, yaml, alias);
         yaml_str!(a, yaml, visible_alias);
 
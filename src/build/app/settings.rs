@@ -45,6 +45,23 @@ bitflags! {
         const INFER_SUBCOMMANDS    = 1 << 37;
         const CONTAINS_LAST        = 1 << 38;
         const ARGS_OVERRIDE_SELF   = 1 << 39;
+        const SORT_POSITIONALS     = 1 << 40;
+        const HIDE_USAGE           = 1 << 41;
+        const BREAK_LONG_WORDS     = 1 << 42;
+        const PASSTHROUGH_AFTER_POS = 1 << 43;
+        const ALLOW_MULTIPLE_SCS   = 1 << 44;
+        const CASE_INSENSITIVE_SCS = 1 << 45;
+        const SUGGEST_SCS          = 1 << 46;
+        const DISABLE_HELP_FLAGS   = 1 << 47;
+        const DISABLE_COLORED_HELP = 1 << 48;
+        const EXPAND_ARGS_FROM_FILE = 1 << 49;
+        const HELP_HYPERLINKS = 1 << 50;
+        const COMPLETIONS_SC = 1 << 51;
+        const SUGGEST_FLAGS = 1 << 52;
+        const SC_IN_USAGE = 1 << 53;
+        const HELP_EXPECTED = 1 << 54;
+        const NO_AUTO_ERROR = 1 << 55;
+        const COLLECT_ERRORS = 1 << 56;
     }
 }
 
@@ -58,7 +75,9 @@ impl BitOr for AppFlags {
 }
 
 impl Default for AppFlags {
-    fn default() -> Self { AppFlags(Flags::UTF8_NONE | Flags::COLOR_AUTO) }
+    fn default() -> Self {
+        AppFlags(Flags::UTF8_NONE | Flags::COLOR_AUTO | Flags::SUGGEST_SCS | Flags::SUGGEST_FLAGS)
+    }
 }
 
 impl AppFlags {
@@ -69,6 +88,7 @@ impl AppFlags {
         ArgRequiredElseHelp => Flags::A_REQUIRED_ELSE_HELP,
         ArgsNegateSubcommands => Flags::ARGS_NEGATE_SCS,
         AllowExternalSubcommands => Flags::ALLOW_UNK_SC,
+        AllowMultipleSubcommands => Flags::ALLOW_MULTIPLE_SCS,
         AllowInvalidUtf8 => Flags::UTF8_NONE,
         AllowLeadingHyphen => Flags::LEADING_HYPHEN,
         AllowNegativeNumbers => Flags::ALLOW_NEG_NUMS,
@@ -80,12 +100,18 @@ impl AppFlags {
         DontDelimitTrailingValues => Flags::DONT_DELIM_TRAIL,
         DontCollapseArgsInUsage => Flags::DONT_COLLAPSE_ARGS,
         DeriveDisplayOrder => Flags::DERIVE_DISP_ORDER,
+        DisableColoredHelp => Flags::DISABLE_COLORED_HELP,
+        DisableHelpFlags => Flags::DISABLE_HELP_FLAGS,
         DisableHelpSubcommand => Flags::DISABLE_HELP_SC,
         DisableVersion => Flags::DISABLE_VERSION,
+        ExpandArgsFromFile => Flags::EXPAND_ARGS_FROM_FILE,
         GlobalVersion => Flags::GLOBAL_VERSION,
+        HelpExpected => Flags::HELP_EXPECTED,
+        HelpHyperlinks => Flags::HELP_HYPERLINKS,
         HidePossibleValuesInHelp => Flags::NO_POS_VALUES,
         Hidden => Flags::HIDDEN,
         LowIndexMultiplePositional => Flags::LOW_INDEX_MUL_POS,
+        NoAutoError => Flags::NO_AUTO_ERROR,
         NoAutoHelp => Flags::NO_AUTO_HELP,
         NoAutoVersion => Flags::NO_AUTO_VERSION,
         NoBinaryName => Flags::NO_BIN_NAME,
@@ -93,6 +119,9 @@ impl AppFlags {
         SubcommandsNegateReqs => Flags::SC_NEGATE_REQS,
         SubcommandRequired => Flags::SC_REQUIRED,
         SubcommandRequiredElseHelp => Flags::SC_REQUIRED_ELSE_HELP,
+        ShowSubcommandsInUsage => Flags::SC_IN_USAGE,
+        SuggestFlags => Flags::SUGGEST_FLAGS,
+        SuggestSubcommands => Flags::SUGGEST_SCS,
         TrailingVarArg => Flags::TRAILING_VARARG,
         UnifiedHelpMessage => Flags::UNIFIED_HELP,
         NextLineHelp => Flags::NEXT_LINE_HELP,
@@ -104,7 +133,14 @@ impl AppFlags {
         ValidArgFound => Flags::VALID_ARG_FOUND,
         InferSubcommands => Flags::INFER_SUBCOMMANDS,
         AllArgsOverrideSelf => Flags::ARGS_OVERRIDE_SELF,
-        ContainsLast => Flags::CONTAINS_LAST
+        SortPositionals => Flags::SORT_POSITIONALS,
+        HideUsage => Flags::HIDE_USAGE,
+        BreakLongWords => Flags::BREAK_LONG_WORDS,
+        CaseInsensitiveSubcommands => Flags::CASE_INSENSITIVE_SCS,
+        CollectErrors => Flags::COLLECT_ERRORS,
+        PassthroughAfterPositional => Flags::PASSTHROUGH_AFTER_POS,
+        ContainsLast => Flags::CONTAINS_LAST,
+        CompletionsSubcommand => Flags::COMPLETIONS_SC
     }
 }
 
@@ -353,6 +389,43 @@ pub enum AppSettings {
     /// [`ArgMatches`]: ./struct.ArgMatches.html
     AllowExternalSubcommands,
 
+    /// Specifies that [``]s may be used more than once, one after another, such as
+    /// `myprog log -n 5 log -n 3`. Parsing of a subcommand stops as soon as the name of another
+    /// subcommand of the parent is found (unless that name is expected as a value), and the
+    /// remaining arguments are parsed as the next invocation.
+    ///
+    /// All invocations, in order, can be retrieved with [`ArgMatches::subcommand_matches_all`],
+    /// while [`ArgMatches::subcommand`] and friends continue to return only the first one.
+    ///
+    /// **NOTE:** Only subcommands of the [`App`] this setting is applied to may be repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::AllowMultipleSubcommands)
+    ///     .subcommand(App::new("log")
+    ///         .arg(Arg::with_name("num").short('n').takes_value(true)))
+    ///     .subcommand(App::new("grep")
+    ///         .arg(Arg::with_name("pattern")))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "log", "-n", "5", "grep", "fix", "log", "-n", "3"
+    ///     ]);
+    ///
+    /// let scs: Vec<_> = m.subcommand_matches_all()
+    ///     .into_iter()
+    ///     .map(|(name, sub_m)| (name, sub_m.value_of("num").or(sub_m.value_of("pattern"))))
+    ///     .collect();
+    /// assert_eq!(scs, [("log", Some("5")), ("grep", Some("fix")), ("log", Some("3"))]);
+    /// assert_eq!(m.subcommand_name(), Some("log"));
+    /// ```
+    /// [``]: ./struct.App.html
+    /// [`App`]: ./struct.App.html
+    /// [`ArgMatches::subcommand_matches_all`]: ./struct.ArgMatches.html#method.subcommand_matches_all
+    /// [`ArgMatches::subcommand`]: ./struct.ArgMatches.html#method.subcommand
+    AllowMultipleSubcommands,
+
     /// Specifies that use of a valid [argument] negates [subcomands] being used after. By default
     /// `clap` allows arguments between subcommands such as
     /// `<cmd> [cmd_args] <cmd2> [cmd2_args] <cmd3> [cmd3_args]`. This setting disables that
@@ -395,10 +468,80 @@ pub enum AppSettings {
     /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
     ArgRequiredElseHelp,
 
+    /// Allows words which are longer than the available width to be broken across lines when
+    /// wrapping help text. By default such words (long URLs, for instance) are left intact and
+    /// overflow the terminal width.
+    ///
+    /// **NOTE:** This setting is cosmetic only and does not affect any functionality.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::BreakLongWords)
+    ///     .get_matches();
+    /// ```
+    BreakLongWords,
+
+    /// Allows [``]s and their aliases to be matched regardless of case, so `myprog BUILD` and
+    /// `myprog Build` both run the `build` subcommand. Exact matches are always tried first.
+    ///
+    /// The name returned by [`ArgMatches::subcommand`] and friends is still the declared name,
+    /// not the casing used at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings};
+    /// let m = App::new("prog")
+    ///     .setting(AppSettings::CaseInsensitiveSubcommands)
+    ///     .subcommand(App::new("build")
+    ///         .alias("compile"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "COMPILE"
+    ///     ]);
+    ///
+    /// assert_eq!(m.subcommand_name(), Some("build"));
+    /// ```
+    /// [``]: ./struct.App.html
+    /// [`ArgMatches::subcommand`]: ./struct.ArgMatches.html#method.subcommand
+    CaseInsensitiveSubcommands,
+
+    /// Keeps parsing past the problems it can recover from, i.e. unknown arguments, missing or
+    /// invalid values, conflicts and missing required arguments, and reports all of them together
+    /// as one [`ErrorKind::MultipleErrors`] error. The individual errors are in [`Error::errors`].
+    /// This is meant for tools which validate a command line as it's typed.
+    ///
+    /// **NOTE:** Help and version messages, and errors about subcommands, still end parsing right
+    /// away.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let err = App::new("prog")
+    ///     .setting(AppSettings::CollectErrors)
+    ///     .arg(Arg::from("--mode <MODE> 'the mode'").possible_values(&["fast", "slow"]))
+    ///     .try_get_matches_from(vec!["prog", "--unknown", "--mode", "medium"])
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    /// let kinds = err.errors.iter().map(|e| e.kind).collect::<Vec<_>>();
+    /// assert_eq!(kinds, [ErrorKind::UnknownArgument, ErrorKind::InvalidValue]);
+    /// ```
+    /// [`ErrorKind::MultipleErrors`]: ./enum.ErrorKind.html#variant.MultipleErrors
+    /// [`Error::errors`]: ./struct.Error.html#structfield.errors
+    CollectErrors,
+
     /// Uses colorized help messages.
     ///
     /// **NOTE:** Must be compiled with the `color` cargo feature
     ///
+    /// **NOTE:** Error messages, including the usage printed with them, are colored regardless
+    /// of this setting, following [`AppSettings::ColorAuto`], [`AppSettings::ColorAlways`] and
+    /// [`AppSettings::ColorNever`].
+    ///
     /// # Platform Specific
     ///
     /// This setting only applies to Unix, Linux, and OSX (i.e. non-Windows platforms)
@@ -411,6 +554,9 @@ pub enum AppSettings {
     ///     .setting(AppSettings::ColoredHelp)
     ///     .get_matches();
     /// ```
+    /// [`AppSettings::ColorAuto`]: ./enum.AppSettings.html#variant.ColorAuto
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    /// [`AppSettings::ColorNever`]: ./enum.AppSettings.html#variant.ColorNever
     ColoredHelp,
 
     /// Enables colored output only when the output is going to a terminal or TTY.
@@ -500,6 +646,64 @@ pub enum AppSettings {
     /// [`Arg::use_delimiter(false)`]: ./struct.Arg.html#method.use_delimiter
     DontDelimitTrailingValues,
 
+    /// Never colors help messages, regardless of [`AppSettings::ColoredHelp`],
+    /// [`AppSettings::ColorAlways`] or whether the terminal looks like it supports color. Useful
+    /// when output goes through something that reports itself as a TTY but mangles ANSI codes.
+    ///
+    /// **NOTE:** This only affects help messages; use [`AppSettings::ColorNever`] to also strip
+    /// color from error messages.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::ColoredHelp)
+    ///     .setting(AppSettings::DisableColoredHelp)
+    ///     .get_matches();
+    /// ```
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    /// [`AppSettings::ColorNever`]: ./enum.AppSettings.html#variant.ColorNever
+    DisableColoredHelp,
+
+    /// Disables `-h` and `--help` [`App`] without affecting any of the [``]s, so that `-h` or
+    /// `--help` can be used by your own arguments. When disabled, `--help` is treated like any
+    /// other unknown argument unless you declare it yourself.
+    ///
+    /// **NOTE:** To only change the short or long of the built-in flag, use [`App::mut_arg`]
+    /// instead, e.g. `.mut_arg("help", |a| a.short('?').long("help"))`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::DisableHelpFlags)
+    ///     .try_get_matches_from(vec![
+    ///         "myprog", "-h"
+    ///     ]);
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+    /// ```
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::DisableHelpFlags)
+    ///     .arg(Arg::with_name("host")
+    ///         .short('h')
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-h", "localhost"
+    ///     ]);
+    /// assert_eq!(m.value_of("host"), Some("localhost"));
+    /// ```
+    /// [``]: ./struct..html
+    /// [`App`]: ./struct.App.html
+    /// [`App::mut_arg`]: ./struct.App.html#method.mut_arg
+    DisableHelpFlags,
+
     /// Disables the `help` subcommand
     ///
     /// # Examples
@@ -568,11 +772,40 @@ pub enum AppSettings {
     /// [``]: ./struct..html
     DeriveDisplayOrder,
 
+    /// Expands any argument starting with `@` into the arguments read from the file it names,
+    /// i.e. `myprog @args.txt`. This is useful to get around command line length limits.
+    ///
+    /// The file is split on whitespace and newlines; single or double quotes keep whitespace
+    /// inside an argument (i.e. `"two words"`). Files may name other `@` files, which are
+    /// expanded in turn. Arguments after `--` are never expanded.
+    ///
+    /// **NOTE:** A file that can't be read is an [`ErrorKind::Io`] error, and a file that
+    /// (indirectly) names itself is an [`ErrorKind::Format`] error.
+    ///
+    /// **NOTE:** Without this setting, an argument like `@args.txt` is parsed as any other value.
+    ///
+    /// **NOTE:** All arguments are read and expanded before parsing starts, so with this setting
+    /// [`App::try_get_matches_from_mut`] no longer pulls them from the iterator one at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::ExpandArgsFromFile)
+    ///     .arg(Arg::from("[files]... 'files to process'"))
+    ///     .get_matches_from(vec!["myprog", "@files.txt"]);
+    /// ```
+    /// [`ErrorKind::Io`]: ./enum.ErrorKind.html#variant.Io
+    /// [`ErrorKind::Format`]: ./enum.ErrorKind.html#variant.Format
+    /// [`App::try_get_matches_from_mut`]: ./struct.App.html#method.try_get_matches_from_mut
+    ExpandArgsFromFile,
+
     /// Specifies to use the version of the current command for all child [``]s.
     /// (Defaults to `false`; subcommands have independent version strings from their parents.)
     ///
-    /// **NOTE:** The version for the current command **and** this setting must be set **prior** to
-    /// adding any child subcommands
+    /// Both the [`App::version`] and the [`App::long_version`] are inherited, by subcommands of
+    /// subcommands too. A subcommand which sets either of them keeps its own version.
     ///
     /// # Examples
     ///
@@ -587,8 +820,59 @@ pub enum AppSettings {
     /// // "myprog-test v1.1"
     /// ```
     /// [``]: ./struct..html
+    /// [`App::version`]: ./struct.App.html#method.version
+    /// [`App::long_version`]: ./struct.App.html#method.long_version
     GlobalVersion,
 
+    /// Panics when the [`App`] is built if any argument which isn't [hidden] has neither
+    /// [`Arg::help`] nor [`Arg::long_help`] set, naming the offending argument. This is meant as a
+    /// lint to catch undocumented arguments before a release. The check applies to this [`App`]
+    /// only, use [`App::global_setting`] to include subcommands.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// # use clap::{App, Arg, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::HelpExpected)
+    ///     .arg(Arg::with_name("documented").long("documented").help("Has help"))
+    ///     .arg(Arg::with_name("undocumented").long("undocumented"))
+    ///     .get_matches_from(vec!["myprog"]);
+    /// // panics: argument `undocumented` has no help text
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [hidden]: ./enum.ArgSettings.html#variant.Hidden
+    /// [`Arg::help`]: ./struct.Arg.html#method.help
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    HelpExpected,
+
+    /// Makes URLs (starting with `http://`, `https://` or `file://`) in the help of arguments and
+    /// subcommands, and in [`App::before_help`] and [`App::after_help`], clickable in terminals
+    /// supporting [OSC 8 hyperlinks]. The links take no space, so wrapping and alignment of the
+    /// help message are unaffected.
+    ///
+    /// **NOTE:** Links are only written when the help message is colored, i.e. with
+    /// [`AppSettings::ColoredHelp`] and output going to a terminal. They are never written with
+    /// [`AppSettings::DisableColoredHelp`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::ColoredHelp)
+    ///     .setting(AppSettings::HelpHyperlinks)
+    ///     .after_help("Report bugs at https://example.com/issues")
+    ///     .get_matches();
+    /// ```
+    /// [OSC 8 hyperlinks]: https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
+    /// [`App::before_help`]: ./struct.App.html#method.before_help
+    /// [`App::after_help`]: ./struct.App.html#method.after_help
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::DisableColoredHelp`]: ./enum.AppSettings.html#variant.DisableColoredHelp
+    HelpHyperlinks,
+
     /// Specifies that this [``] should be hidden from help messages
     ///
     /// # Examples
@@ -607,6 +891,25 @@ pub enum AppSettings {
     /// This can be useful if there are many values, or they are explained elsewhere.
     HidePossibleValuesInHelp,
 
+    /// Tells `clap` *not* to print the `USAGE:` section when displaying help information. This
+    /// can be useful for apps that only dispatch to subcommands. The `{usage}` tag of a
+    /// [`App::help_template`] is also left empty.
+    ///
+    /// **NOTE:** This setting is cosmetic only and does not affect the usage string displayed in
+    /// error messages.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::HideUsage)
+    ///     .subcommand(App::new("test"))
+    ///     .get_matches();
+    /// ```
+    /// [`App::help_template`]: ./struct.App.html#method.help_template
+    HideUsage,
+
     /// Tries to match unknown args to partial [`subcommands`] or their [aliases]. For example to
     /// match a subcommand named `test`, one could use `t`, `te`, `tes`, and `test`.
     ///
@@ -637,6 +940,46 @@ pub enum AppSettings {
     /// [`AppSeettings::ArgsNegateSubcommands`]: ./enum.AppSettings.html#variant.ArgsNegateSubcommands
     InferSubcommands,
 
+    /// Stops [`App::try_get_matches`] and friends from printing anything, so every [`Error`]
+    /// (including the ones for `--help` and `--version`) is handed back unprinted, with its text in
+    /// the `message`. A library can then decide what to print based on its [`ErrorKind`].
+    ///
+    /// Without this setting the `try_` methods print the version message to `stdout` right away
+    /// and return an empty `message` for [`ErrorKind::VersionDisplayed`].
+    ///
+    /// **NOTE:** With this setting the exit code becomes your responsibility, nothing is printed
+    /// and the process keeps running even for `--help` and `--version`. [`Error::exit`] gives the
+    /// default behavior back for the errors you don't handle yourself.
+    ///
+    /// **NOTE:** [`App::get_matches`] and the other methods which don't return a
+    /// [`clap::Result`] have no way to hand an error back, so they still print it and exit.
+    ///
+    /// **NOTE:** To cover the `--version` of subcommands as well, set this with
+    /// [`App::global_setting`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .version("1.0")
+    ///     .setting(AppSettings::NoAutoError)
+    ///     .try_get_matches_from(vec!["myprog", "--version"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    /// assert_eq!(err.message, "myprog 1.0");
+    /// ```
+    /// [`App::try_get_matches`]: ./struct.App.html#method.try_get_matches
+    /// [`App::get_matches`]: ./struct.App.html#method.get_matches
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    /// [`Error`]: ./struct.Error.html
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    /// [`ErrorKind::VersionDisplayed`]: ./enum.ErrorKind.html#variant.VersionDisplayed
+    /// [`clap::Result`]: ./type.Result.html
+    NoAutoError,
+
     /// Specifies that the parser should not assume the first argument passed is the binary name.
     /// This is normally the case when using a "daemon" style mode, or an interactive CLI where one
     /// one would not normally type the binary or program name for each command.
@@ -667,6 +1010,31 @@ pub enum AppSettings {
     /// ```
     NextLineHelp,
 
+    /// Specifies that `clap` should stop parsing flags and options once the first positional
+    /// argument has been found, and treat everything after it as positional values, exactly as if
+    /// `--` had been used right after it. This is useful for wrapper CLIs such as
+    /// `time <cmd> <args>...` where the arguments for the wrapped command may start with a hyphen.
+    ///
+    /// **NOTE:** Using `--` before the first positional still stops parsing immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::PassthroughAfterPositional)
+    ///     .arg(Arg::from("-v 'verbose'"))
+    ///     .arg(Arg::from("<cmd> 'command to run'"))
+    ///     .arg(Arg::from("[args]... 'arguments for the command'"))
+    ///     .get_matches_from(vec!["myprog", "-v", "ls", "-v", "-l"]);
+    ///
+    /// assert_eq!(m.occurrences_of("v"), 1);
+    /// assert_eq!(m.value_of("cmd"), Some("ls"));
+    /// let args: Vec<&str> = m.values_of("args").unwrap().collect();
+    /// assert_eq!(args, ["-v", "-l"]);
+    /// ```
+    PassthroughAfterPositional,
+
     /// Allows [``]s to override all requirements of the parent command.
     /// For example if you had a subcommand or top level application with a required argument
     /// that is only required as long as there is no subcommand present,
@@ -713,9 +1081,11 @@ pub enum AppSettings {
     SubcommandsNegateReqs,
 
     /// Specifies that the help text should be displayed (before exiting gracefully) if no
-    /// [``]s are present at runtime (i.e. an empty run such as `$ myprog`).
+    /// [``]s are present at runtime (i.e. an empty run such as `$ myprog`). Like `--help`, the
+    /// full help is written to `stdout` and the process exits with a status of `0`; the `try_`
+    /// methods return an [`ErrorKind::HelpDisplayed`] error.
     ///
-    /// **NOTE:** This should *not* be used with [`AppSettings::SubcommandRequired`] as they do
+    /// **NOTE:** This takes precedence over [`AppSettings::SubcommandRequired`] as they do
     /// nearly same thing; this prints the help text, and the other prints an error.
     ///
     /// **NOTE:** If the user specifies arguments at runtime, but no subcommand the help text will
@@ -725,11 +1095,16 @@ pub enum AppSettings {
     /// # Examples
     ///
     /// ```rust
-    /// # use clap::{App, Arg, AppSettings};
-    /// App::new("myprog")
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let err = App::new("myprog")
     ///     .setting(AppSettings::SubcommandRequiredElseHelp)
-    /// # ;
+    ///     .subcommand(App::new("test"))
+    ///     .try_get_matches_from(vec!["myprog"])
+    ///     .unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    /// assert_eq!(err.exit_code(), 0);
     /// ```
+    /// [`ErrorKind::HelpDisplayed`]: ./enum.ErrorKind.html#variant.HelpDisplayed
     /// [``]: ./struct..html
     /// [`AppSettings::SubcommandRequired`]: ./enum.AppSettings.html#variant.SubcommandRequired
     /// [`AppSettings::ArgRequiredElseHelp`]: ./enum.AppSettings.html#variant.ArgRequiredElseHelp
@@ -790,6 +1165,99 @@ pub enum AppSettings {
     /// [``]: ./struct..html
     SubcommandRequired,
 
+    /// Lists the names of the visible [``]s in the usage string instead of the generic
+    /// `SUBCOMMAND` placeholder, i.e. `myprog [build|test]`. They're listed in the same order as
+    /// in the help message, by [`App::display_order`] and then alphabetically. Hidden subcommands
+    /// are left out.
+    ///
+    /// To keep the usage readable, only the first five names are listed, followed by `...`, the
+    /// help message still lists all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings};
+    /// let err = App::new("myprog")
+    ///     .setting(AppSettings::ShowSubcommandsInUsage)
+    ///     .subcommand(App::new("test").display_order(1))
+    ///     .subcommand(App::new("build"))
+    ///     .subcommand(App::new("secret").setting(AppSettings::Hidden))
+    ///     .try_get_matches_from(vec!["myprog", "--bogus"])
+    ///     .unwrap_err();
+    /// assert!(err.message.contains("myprog [build|help|test]"));
+    /// ```
+    /// [``]: ./struct.App.html
+    /// [`App::display_order`]: ./struct.App.html#method.display_order
+    ShowSubcommandsInUsage,
+
+    /// Sorts positional arguments in the help message by their [`Arg::display_order`] (and then
+    /// alphabetically) just like flags and options, instead of listing them by index which is
+    /// the default.
+    ///
+    /// **NOTE:** This setting is cosmetic only and does not affect any functionality.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::SortPositionals)
+    ///     .arg(Arg::with_name("second").index(1).display_order(2))
+    ///     .arg(Arg::with_name("first").index(2).display_order(1))
+    ///     .get_matches();
+    /// // running `myprog --help` will list <first> before <second>
+    /// ```
+    /// [`Arg::display_order`]: ./struct.Arg.html#method.display_order
+    SortPositionals,
+
+    /// Suggests the closest matching long flag or option (including its aliases) when an unknown
+    /// one is used, i.e. ``Did you mean `--color`?``. Hidden arguments are never suggested.
+    ///
+    /// **NOTE:** This setting is on by default, use [`App::unset_setting`] to disable it. It has
+    /// no effect unless `clap` is compiled with the `suggestions` cargo feature (also on by
+    /// default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let res = App::new("myprog")
+    ///     .unset_setting(AppSettings::SuggestFlags)
+    ///     .arg(Arg::with_name("color").long("color"))
+    ///     .try_get_matches_from(vec![
+    ///         "myprog", "--colr"
+    ///     ]);
+    ///
+    /// assert!(!res.unwrap_err().message.contains("Did you mean"));
+    /// ```
+    /// [`App::unset_setting`]: ./struct.App.html#method.unset_setting
+    SuggestFlags,
+
+    /// Suggests the closest matching [``] name or visible alias when an unknown subcommand is
+    /// used, i.e. `Did you mean 'install'?`. Hidden subcommands and aliases are never suggested.
+    ///
+    /// **NOTE:** This setting is on by default, use [`App::unset_setting`] to disable it. It has
+    /// no effect unless `clap` is compiled with the `suggestions` cargo feature (also on by
+    /// default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .unset_setting(AppSettings::SuggestSubcommands)
+    ///     .subcommand(App::new("install"))
+    ///     .try_get_matches_from(vec![
+    ///         "myprog", "instll"
+    ///     ]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_ne!(res.unwrap_err().kind, ErrorKind::InvalidSubcommand);
+    /// ```
+    /// [``]: ./struct.App.html
+    /// [`App::unset_setting`]: ./struct.App.html#method.unset_setting
+    SuggestSubcommands,
+
     /// Specifies that the final positional argument is a "VarArg" and that `clap` should not
     /// attempt to parse any further args.
     ///
@@ -861,6 +1329,10 @@ pub enum AppSettings {
     /// Windows where a user tries to open the binary by double-clicking instead of using the
     /// command line.
     ///
+    /// **NOTE:** This only applies to actual errors, not to `--help` or `--version`. It is also
+    /// skipped when `stdin` is not a TTY so that pipelines don't hang (detecting this requires
+    /// the `color` feature, without it clap always waits).
+    ///
     /// **NOTE:** This setting is **not** recursive with [``]s, meaning if you wish this
     /// behavior for all subcommands, you must set this on each command (needing this is extremely
     /// rare)
@@ -899,6 +1371,9 @@ pub enum AppSettings {
 
     #[doc(hidden)]
     ContainsLast,
+
+    #[doc(hidden)]
+    CompletionsSubcommand,
 }
 
 impl FromStr for AppSettings {
@@ -910,7 +1385,10 @@ impl FromStr for AppSettings {
             "allowinvalidutf8" => Ok(AppSettings::AllowInvalidUtf8),
             "allowleadinghyphen" => Ok(AppSettings::AllowLeadingHyphen),
             "allowexternalsubcommands" => Ok(AppSettings::AllowExternalSubcommands),
+            "allowmultiplesubcommands" => Ok(AppSettings::AllowMultipleSubcommands),
             "allownegativenumbers" => Ok(AppSettings::AllowNegativeNumbers),
+            "caseinsensitivesubcommands" => Ok(AppSettings::CaseInsensitiveSubcommands),
+            "collecterrors" => Ok(AppSettings::CollectErrors),
             "colorauto" => Ok(AppSettings::ColorAuto),
             "coloralways" => Ok(AppSettings::ColorAlways),
             "colornever" => Ok(AppSettings::ColorNever),
@@ -918,13 +1396,19 @@ impl FromStr for AppSettings {
             "derivedisplayorder" => Ok(AppSettings::DeriveDisplayOrder),
             "dontcollapseargsinusage" => Ok(AppSettings::DontCollapseArgsInUsage),
             "dontdelimittrailingvalues" => Ok(AppSettings::DontDelimitTrailingValues),
+            "expandargsfromfile" => Ok(AppSettings::ExpandArgsFromFile),
+            "disablecoloredhelp" => Ok(AppSettings::DisableColoredHelp),
+            "disablehelpflags" => Ok(AppSettings::DisableHelpFlags),
             "disablehelpsubcommand" => Ok(AppSettings::DisableHelpSubcommand),
             "disableversion" => Ok(AppSettings::DisableVersion),
             "globalversion" => Ok(AppSettings::GlobalVersion),
+            "helpexpected" => Ok(AppSettings::HelpExpected),
+            "helphyperlinks" => Ok(AppSettings::HelpHyperlinks),
             "hidden" => Ok(AppSettings::Hidden),
             "hidepossiblevaluesinhelp" => Ok(AppSettings::HidePossibleValuesInHelp),
             "infersubcommands" => Ok(AppSettings::InferSubcommands),
             "lowindexmultiplepositional" => Ok(AppSettings::LowIndexMultiplePositional),
+            "noautoerror" => Ok(AppSettings::NoAutoError),
             "nobinaryname" => Ok(AppSettings::NoBinaryName),
             "nextlinehelp" => Ok(AppSettings::NextLineHelp),
             "strictutf8" => Ok(AppSettings::StrictUtf8),
@@ -939,6 +1423,13 @@ impl FromStr for AppSettings {
             "validargfound" => Ok(AppSettings::ValidArgFound),
             "built" => Ok(AppSettings::Built),
             "trailingvalues" => Ok(AppSettings::TrailingValues),
+            "showsubcommandsinusage" => Ok(AppSettings::ShowSubcommandsInUsage),
+            "sortpositionals" => Ok(AppSettings::SortPositionals),
+            "suggestflags" => Ok(AppSettings::SuggestFlags),
+            "suggestsubcommands" => Ok(AppSettings::SuggestSubcommands),
+            "hideusage" => Ok(AppSettings::HideUsage),
+            "breaklongwords" => Ok(AppSettings::BreakLongWords),
+            "passthroughafterpositional" => Ok(AppSettings::PassthroughAfterPositional),
             _ => Err("unknown AppSetting, cannot convert from str".to_owned()),
         }
     }
@@ -962,6 +1453,10 @@ mod test {
             "allowexternalsubcommands".parse::<AppSettings>().unwrap(),
             AppSettings::AllowExternalSubcommands
         );
+        assert_eq!(
+            "allowmultiplesubcommands".parse::<AppSettings>().unwrap(),
+            AppSettings::AllowMultipleSubcommands
+        );
         assert_eq!(
             "allowinvalidutf8".parse::<AppSettings>().unwrap(),
             AppSettings::AllowInvalidUtf8
@@ -974,6 +1469,14 @@ mod test {
             "allownegativenumbers".parse::<AppSettings>().unwrap(),
             AppSettings::AllowNegativeNumbers
         );
+        assert_eq!(
+            "caseinsensitivesubcommands".parse::<AppSettings>().unwrap(),
+            AppSettings::CaseInsensitiveSubcommands
+        );
+        assert_eq!(
+            "collecterrors".parse::<AppSettings>().unwrap(),
+            AppSettings::CollectErrors
+        );
         assert_eq!(
             "coloredhelp".parse::<AppSettings>().unwrap(),
             AppSettings::ColoredHelp
@@ -990,6 +1493,14 @@ mod test {
             "colornever".parse::<AppSettings>().unwrap(),
             AppSettings::ColorNever
         );
+        assert_eq!(
+            "disablecoloredhelp".parse::<AppSettings>().unwrap(),
+            AppSettings::DisableColoredHelp
+        );
+        assert_eq!(
+            "disablehelpflags".parse::<AppSettings>().unwrap(),
+            AppSettings::DisableHelpFlags
+        );
         assert_eq!(
             "disablehelpsubcommand".parse::<AppSettings>().unwrap(),
             AppSettings::DisableHelpSubcommand
@@ -1010,10 +1521,22 @@ mod test {
             "derivedisplayorder".parse::<AppSettings>().unwrap(),
             AppSettings::DeriveDisplayOrder
         );
+        assert_eq!(
+            "expandargsfromfile".parse::<AppSettings>().unwrap(),
+            AppSettings::ExpandArgsFromFile
+        );
         assert_eq!(
             "globalversion".parse::<AppSettings>().unwrap(),
             AppSettings::GlobalVersion
         );
+        assert_eq!(
+            "helpexpected".parse::<AppSettings>().unwrap(),
+            AppSettings::HelpExpected
+        );
+        assert_eq!(
+            "helphyperlinks".parse::<AppSettings>().unwrap(),
+            AppSettings::HelpHyperlinks
+        );
         assert_eq!(
             "hidden".parse::<AppSettings>().unwrap(),
             AppSettings::Hidden
@@ -1026,6 +1549,10 @@ mod test {
             "lowindexmultiplePositional".parse::<AppSettings>().unwrap(),
             AppSettings::LowIndexMultiplePositional
         );
+        assert_eq!(
+            "noautoerror".parse::<AppSettings>().unwrap(),
+            AppSettings::NoAutoError
+        );
         assert_eq!(
             "nobinaryname".parse::<AppSettings>().unwrap(),
             AppSettings::NoBinaryName
@@ -1086,6 +1613,34 @@ mod test {
             "infersubcommands".parse::<AppSettings>().unwrap(),
             AppSettings::InferSubcommands
         );
+        assert_eq!(
+            "showsubcommandsinusage".parse::<AppSettings>().unwrap(),
+            AppSettings::ShowSubcommandsInUsage
+        );
+        assert_eq!(
+            "sortpositionals".parse::<AppSettings>().unwrap(),
+            AppSettings::SortPositionals
+        );
+        assert_eq!(
+            "suggestflags".parse::<AppSettings>().unwrap(),
+            AppSettings::SuggestFlags
+        );
+        assert_eq!(
+            "suggestsubcommands".parse::<AppSettings>().unwrap(),
+            AppSettings::SuggestSubcommands
+        );
+        assert_eq!(
+            "hideusage".parse::<AppSettings>().unwrap(),
+            AppSettings::HideUsage
+        );
+        assert_eq!(
+            "breaklongwords".parse::<AppSettings>().unwrap(),
+            AppSettings::BreakLongWords
+        );
+        assert_eq!(
+            "passthroughafterpositional".parse::<AppSettings>().unwrap(),
+            AppSettings::PassthroughAfterPositional
+        );
         assert!("hahahaha".parse::<AppSettings>().is_err());
     }
 }
@@ -0,0 +1,45 @@
+// Std
+use std::cell::RefCell;
+use std::ffi::OsStr;
+use std::fmt;
+use std::rc::Rc;
+
+type Callback<'b> = dyn FnMut(&OsStr) -> UnknownArgAction + 'b;
+
+/// What the parser does with an argument no [`Arg`] matches, as decided by the callback given to
+/// [`App::on_unknown_arg`].
+///
+/// [`Arg`]: ./struct.Arg.html
+/// [`App::on_unknown_arg`]: ./struct.App.html#method.on_unknown_arg
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UnknownArgAction {
+    /// Fail with an [`ErrorKind::UnknownArgument`] error, as if there were no callback
+    ///
+    /// [`ErrorKind::UnknownArgument`]: ./enum.ErrorKind.html#variant.UnknownArgument
+    Error,
+    /// Keep the argument so it can be retrieved with [`ArgMatches::unknown_args`]
+    ///
+    /// [`ArgMatches::unknown_args`]: ./struct.ArgMatches.html#method.unknown_args
+    Collect,
+    /// Silently drop the argument
+    Ignore,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct UnknownArgHandler<'b>(Rc<RefCell<Callback<'b>>>);
+
+impl<'b> UnknownArgHandler<'b> {
+    pub(crate) fn new<F>(f: F) -> Self
+    where
+        F: FnMut(&OsStr) -> UnknownArgAction + 'b,
+    {
+        UnknownArgHandler(Rc::new(RefCell::new(f)))
+    }
+
+    pub(crate) fn call(&self, arg: &OsStr) -> UnknownArgAction { (&mut *self.0.borrow_mut())(arg) }
+}
+
+impl<'b> fmt::Debug for UnknownArgHandler<'b> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "UnknownArgHandler") }
+}
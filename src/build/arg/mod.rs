@@ -1,5 +1,7 @@
 mod settings;
+mod value_hint;
 pub use self::settings::{ArgFlags, ArgSettings};
+pub use self::value_hint::ValueHint;
 
 // Std
 use std::borrow::Cow;
@@ -13,12 +15,13 @@ use std::rc::Rc;
 use std::str;
 
 // Third Party
-use crate::util::VecMap;
+use crate::util::{collate, VecMap};
 #[cfg(feature = "yaml")]
 use yaml_rust;
 
 // Internal
 use crate::build::UsageParser;
+use crate::parse::errors::Error;
 use crate::util::Key;
 #[cfg(any(target_os = "windows", target_arch = "wasm32"))]
 use crate::util::OsStrExt3;
@@ -26,6 +29,7 @@ use crate::INTERNAL_ERROR_MSG;
 
 type Validator = Rc<dyn Fn(String) -> Result<(), String>>;
 type ValidatorOs = Rc<dyn Fn(&OsStr) -> Result<(), String>>;
+type ValidatorError = Rc<dyn Fn(&OsStr) -> Result<(), Error>>;
 
 type Id = u64;
 
@@ -81,12 +85,20 @@ pub struct Arg<'help> {
     #[doc(hidden)]
     pub aliases: Option<Vec<(&'help str, bool)>>, // (name, visible)
     #[doc(hidden)]
+    pub short_aliases: Option<Vec<(char, bool)>>, // (name, visible)
+    #[doc(hidden)]
     pub disp_ord: usize,
     #[doc(hidden)]
     pub unified_ord: usize,
     #[doc(hidden)]
     pub possible_vals: Option<Vec<&'help str>>,
     #[doc(hidden)]
+    pub possible_vals_os: Option<Vec<&'help OsStr>>,
+    #[doc(hidden)]
+    pub possible_vals_cap: Option<usize>,
+    #[doc(hidden)]
+    pub value_hint: ValueHint,
+    #[doc(hidden)]
     pub val_names: Option<VecMap<&'help str>>,
     #[doc(hidden)]
     pub num_vals: Option<u64>,
@@ -95,14 +107,22 @@ pub struct Arg<'help> {
     #[doc(hidden)]
     pub min_vals: Option<u64>,
     #[doc(hidden)]
+    pub max_occurs: Option<u64>,
+    #[doc(hidden)]
+    pub min_occurs: Option<u64>,
+    #[doc(hidden)]
     pub validator: Option<Validator>,
     #[doc(hidden)]
     pub validator_os: Option<ValidatorOs>,
     #[doc(hidden)]
+    pub validator_error: Option<ValidatorError>,
+    #[doc(hidden)]
     pub val_delim: Option<char>,
     #[doc(hidden)]
     pub default_vals: Option<Vec<&'help OsStr>>,
     #[doc(hidden)]
+    pub default_missing_vals: Option<Vec<&'help OsStr>>,
+    #[doc(hidden)]
     pub default_vals_ifs: Option<VecMap<(Id, Option<&'help OsStr>, &'help OsStr)>>,
     #[doc(hidden)]
     pub env: Option<(&'help OsStr, Option<OsString>)>,
@@ -189,14 +209,21 @@ impl<'help> Arg<'help> {
                 "required_ifs" => yaml_tuple2!(a, v, required_if),
                 "takes_value" => yaml_to_bool!(a, v, takes_value),
                 "index" => yaml_to_u64!(a, v, index),
+                "last" => yaml_to_bool!(a, v, last),
+                "raw" => yaml_to_bool!(a, v, raw),
+                "allow_stdin_dash" => yaml_to_bool!(a, v, allow_stdin_dash),
                 "global" => yaml_to_bool!(a, v, global),
                 "multiple" => yaml_to_bool!(a, v, multiple),
+                "multiple_values" => yaml_to_bool!(a, v, multiple_values),
+                "multiple_occurrences" => yaml_to_bool!(a, v, multiple_occurrences),
                 "hidden" => yaml_to_bool!(a, v, hidden),
                 "next_line_help" => yaml_to_bool!(a, v, next_line_help),
                 "group" => yaml_to_str!(a, v, group),
                 "number_of_values" => yaml_to_u64!(a, v, number_of_values),
                 "max_values" => yaml_to_u64!(a, v, max_values),
                 "min_values" => yaml_to_u64!(a, v, min_values),
+                "max_occurrences" => yaml_to_u64!(a, v, max_occurrences),
+                "min_occurrences" => yaml_to_u64!(a, v, min_occurrences),
                 "value_name" => yaml_to_str!(a, v, value_name),
                 "use_delimiter" => yaml_to_bool!(a, v, use_delimiter),
                 "allow_hyphen_values" => yaml_to_bool!(a, v, allow_hyphen_values),
@@ -206,6 +233,7 @@ impl<'help> Arg<'help> {
                 "required_unless" => yaml_to_str!(a, v, required_unless),
                 "display_order" => yaml_to_usize!(a, v, display_order),
                 "default_value" => yaml_to_str!(a, v, default_value),
+                "default_value_hidden" => yaml_to_str!(a, v, default_value_hidden),
                 "default_value_if" => yaml_tuple3!(a, v, default_value_if),
                 "default_value_ifs" => yaml_tuple3!(a, v, default_value_if),
                 "env" => yaml_to_str!(a, v, env),
@@ -217,6 +245,11 @@ impl<'help> Arg<'help> {
                 "conflicts_with" => yaml_vec_or_str!(v, a, conflicts_with),
                 "overrides_with" => yaml_vec_or_str!(v, a, overrides_with),
                 "possible_values" => yaml_vec_or_str!(v, a, possible_value),
+                "value_hint" => a.value_hint(
+                    yaml_str!(v)
+                        .parse()
+                        .expect("unknown ValueHint found in YAML file"),
+                ),
                 "required_unless_one" => yaml_vec_or_str!(v, a, required_unless),
                 "required_unless_all" => {
                     a = yaml_vec_or_str!(v, a, required_unless);
@@ -233,6 +266,37 @@ impl<'help> Arg<'help> {
         a
     }
 
+    /// Creates a new instance of [`Arg`] from a usage string, such as
+    /// `-c, --config <FILE> 'sets the config file'`. This is the same parser used when passing a
+    /// `&str` directly to [`App::arg`].
+    ///
+    /// The usage string may contain, in any order before the help text:
+    ///
+    /// * `-s` and/or `--long` switches
+    /// * an explicit name or value name, either `<required>` or `[optional]`
+    /// * `...` to allow multiple occurrences/values
+    /// * a trailing `'help text'` wrapped in single quotes
+    ///
+    /// # Panics
+    ///
+    /// Since an invalid usage string is a programmer error, this panics if no name can be
+    /// determined, a `-` or `--` is not followed by a name, a `<` or `[` is never closed, or the
+    /// help text is missing its closing `'`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::from_usage("-c, --config <FILE> 'sets the config file'"))
+    ///     .get_matches_from(vec!["prog", "--config", "app.toml"]);
+    ///
+    /// assert_eq!(m.value_of("config"), Some("app.toml"));
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`App::arg`]: ./struct.App.html#method.arg
+    pub fn from_usage(u: &'help str) -> Self { UsageParser::from_usage(u).parse() }
+
     /// Sets the short version of the argument without the preceding `-`.
     ///
     /// By default `clap` automatically assigns `V` and `h` to the auto-generated `version` and
@@ -265,7 +329,27 @@ impl<'help> Arg<'help> {
     ///
     /// assert!(m.is_present("config"));
     /// ```
+    ///
+    /// Shorts can be stacked, as in `-abc`. Once a short which [takes a value] is reached, the
+    /// rest of the stack is used as its value, so `-vfout.txt` is the same as `-v -f out.txt`
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short('v'))
+    ///     .arg(Arg::with_name("file")
+    ///         .short('f')
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-vfout.txt"
+    ///     ]);
+    ///
+    /// assert!(m.is_present("verbose"));
+    /// assert_eq!(m.value_of("file"), Some("out.txt"));
+    /// ```
     /// [`short`]: ./struct.Arg.html#method.short
+    /// [takes a value]: ./struct.Arg.html#method.takes_value
     pub fn short(mut self, s: char) -> Self {
         self.short = Some(s);
         self
@@ -431,6 +515,123 @@ impl<'help> Arg<'help> {
         self
     }
 
+    /// Allows adding an [`Arg`] short alias, a hidden single character which works exactly like
+    /// the [`Arg::short`], including when stacked with other shorts (i.e. `-vx`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///             .arg(Arg::with_name("test")
+    ///             .short('t')
+    ///             .short_alias('e')
+    ///             .takes_value(true))
+    ///        .get_matches_from(vec![
+    ///             "prog", "-e", "cool"
+    ///         ]);
+    /// assert!(m.is_present("test"));
+    /// assert_eq!(m.value_of("test"), Some("cool"));
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`Arg::short`]: ./struct.Arg.html#method.short
+    pub fn short_alias(mut self, name: char) -> Self {
+        if let Some(ref mut als) = self.short_aliases {
+            als.push((name, false));
+        } else {
+            self.short_aliases = Some(vec![(name, false)]);
+        }
+        self
+    }
+
+    /// Allows adding multiple [`Arg`] short aliases, see [`Arg::short_alias`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///             .arg(Arg::with_name("test")
+    ///                     .short('t')
+    ///                     .short_aliases(&['e', 's']))
+    ///             .arg(Arg::with_name("verbose")
+    ///                     .short('v'))
+    ///             .get_matches_from(vec![
+    ///                 "prog", "-vs"
+    ///             ]);
+    /// assert!(m.is_present("test"));
+    /// assert!(m.is_present("verbose"));
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`Arg::short_alias`]: ./struct.Arg.html#method.short_alias
+    pub fn short_aliases(mut self, names: &[char]) -> Self {
+        if let Some(ref mut als) = self.short_aliases {
+            for n in names {
+                als.push((*n, false));
+            }
+        } else {
+            self.short_aliases = Some(names.iter().map(|n| (*n, false)).collect());
+        }
+        self
+    }
+
+    /// Allows adding an [`Arg`] short alias that functions exactly like those defined with
+    /// [`Arg::short_alias`], except that it's listed in the help message as
+    /// `[short aliases: -e]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///             .arg(Arg::with_name("test")
+    ///                 .long("test")
+    ///                 .visible_short_alias('t'))
+    ///        .get_matches_from(vec![
+    ///             "prog", "-t"
+    ///         ]);
+    /// assert!(m.is_present("test"));
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`Arg::short_alias`]: ./struct.Arg.html#method.short_alias
+    pub fn visible_short_alias(mut self, name: char) -> Self {
+        if let Some(ref mut als) = self.short_aliases {
+            als.push((name, true));
+        } else {
+            self.short_aliases = Some(vec![(name, true)]);
+        }
+        self
+    }
+
+    /// Allows adding multiple [`Arg`] short aliases that function exactly like those defined
+    /// with [`Arg::short_aliases`], except that they're listed in the help message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///             .arg(Arg::with_name("test")
+    ///                 .long("test")
+    ///                 .visible_short_aliases(&['t', 'e']))
+    ///        .get_matches_from(vec![
+    ///             "prog", "-e"
+    ///         ]);
+    /// assert!(m.is_present("test"));
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`Arg::short_aliases`]: ./struct.Arg.html#method.short_aliases
+    pub fn visible_short_aliases(mut self, names: &[char]) -> Self {
+        if let Some(ref mut als) = self.short_aliases {
+            for n in names {
+                als.push((*n, true));
+            }
+        } else {
+            self.short_aliases = Some(names.iter().map(|n| (*n, true)).collect());
+        }
+        self
+    }
+
     /// Sets the short help text of the argument that will be displayed to the user when they print
     /// the help information with `-h`. Typically, this is a short (one line) description of the
     /// arg.
@@ -878,6 +1079,10 @@ impl<'help> Arg<'help> {
     /// **NOTE:** When an argument is overridden it is essentially as if it never was used, any
     /// conflicts, requirements, etc. are evaluated **after** all "overrides" have been removed
     ///
+    /// **NOTE:** Positional arguments, options and flags can all override one another, the order
+    /// they were used in is what counts, not the kind of argument. To have an argument win no
+    /// matter the order, see [`Arg::overrides_regardless_of_order`].
+    ///
     /// **WARNING:** Positional arguments and options which accept [`Multiple*`] cannot override
     /// themselves (or we would never be able to advance to the next positional). If a positional
     /// argument or option with one of the [`Multiple*`] settings lists itself as an override, it is
@@ -902,6 +1107,26 @@ impl<'help> Arg<'help> {
     ///                                 // was never used because it was overridden with color
     /// assert!(!m.is_present("flag"));
     /// ```
+    /// An option can override a positional argument, and the other way around:
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::with_name("config"))
+    ///     .arg(Arg::with_name("set")
+    ///         .long("set")
+    ///         .takes_value(true)
+    ///         .overrides_with("config"));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog", "app.conf", "--set", "k=v"]);
+    /// assert!(!m.is_present("config"));
+    /// assert_eq!(m.value_of("set"), Some("k=v"));
+    ///
+    /// let m = app.get_matches_from(vec!["prog", "--set", "k=v", "app.conf"]);
+    /// assert!(!m.is_present("set"));
+    /// assert_eq!(m.value_of("config"), Some("app.conf"));
+    /// ```
+    ///
     /// Care must be taken when using this setting, and having an arg override with itself. This
     /// is common practice when supporting things like shell aliases, config files, etc.
     /// However, when combined with multiple values, it can get dicy.
@@ -972,6 +1197,7 @@ impl<'help> Arg<'help> {
     /// ```
     /// [`Multiple*`]: ./enum.ArgSettings.html#variant.MultipleValues
     /// [`UseValueDelimiter`]: ./enum.ArgSettings.html#variant.UseValueDelimiter
+    /// [`Arg::overrides_regardless_of_order`]: ./struct.Arg.html#method.overrides_regardless_of_order
     pub fn overrides_with<T: Key>(mut self, arg_id: T) -> Self {
         let name = arg_id.key();
         if let Some(ref mut vec) = self.overrides {
@@ -1020,6 +1246,41 @@ impl<'help> Arg<'help> {
         self
     }
 
+    /// Makes this argument win its [overrides] no matter the order they were used in, instead of
+    /// whichever argument was used **last**. Whenever this argument is used, the arguments it
+    /// overrides (or which override it) are removed, even when they come after it.
+    ///
+    /// **NOTE:** If both arguments of an override set this, the one used last wins as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::with_name("config"))
+    ///     .arg(Arg::with_name("set")
+    ///         .long("set")
+    ///         .takes_value(true)
+    ///         .overrides_with("config")
+    ///         .overrides_regardless_of_order(true));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog", "app.conf", "--set", "k=v"]);
+    /// assert!(!m.is_present("config"));
+    /// assert_eq!(m.value_of("set"), Some("k=v"));
+    ///
+    /// let m = app.get_matches_from(vec!["prog", "--set", "k=v", "app.conf"]);
+    /// assert!(!m.is_present("config"));
+    /// assert_eq!(m.value_of("set"), Some("k=v"));
+    /// ```
+    /// [overrides]: ./struct.Arg.html#method.overrides_with
+    pub fn overrides_regardless_of_order(self, always: bool) -> Self {
+        if always {
+            self.setting(ArgSettings::OverridesRegardlessOfOrder)
+        } else {
+            self.unset_setting(ArgSettings::OverridesRegardlessOfOrder)
+        }
+    }
+
     /// Sets an argument by name that is required when this one is present I.e. when
     /// using this argument, the following argument *must* be present.
     ///
@@ -1473,17 +1734,20 @@ impl<'help> Arg<'help> {
     /// not define position in the argument list as a whole.
     ///
     /// **NOTE:** If no [`Arg::short`], or [`Arg::long`] have been defined, you can optionally
-    /// leave off the `index` method, and the index will be assigned in order of evaluation.
-    /// Utilizing the `index` method allows for setting indexes out of order
+    /// leave off the `index` method, and the index will be assigned in order of evaluation,
+    /// skipping any index set explicitly on another positional argument. Utilizing the `index`
+    /// method allows for setting indexes out of order
     ///
     /// **NOTE:** When utilized with [`Arg::multiple(true)`], only the **last** positional argument
     /// may be defined as multiple (i.e. with the highest index)
     ///
     /// # Panics
     ///
-    /// Although not in this method directly, [`App`] will [`panic!`] if indexes are skipped (such
-    /// as defining `index(1)` and `index(3)` but not `index(2)`, or a positional argument is
-    /// defined as multiple and is not the highest index
+    /// Although not in this method directly, [`App`] will [`panic!`] when it's built with debug
+    /// assertions enabled if indexes are skipped (such as defining `index(1)` and `index(3)` but
+    /// not `index(2)`), if two positional arguments share an index, or if a positional argument is
+    /// defined as multiple and is not the highest index. Like the other checks of programmer
+    /// errors these are skipped in release builds.
     ///
     /// # Examples
     ///
@@ -1528,7 +1792,14 @@ impl<'help> Arg<'help> {
     /// **NOTE:** This setting only applies to [options] and [positional arguments]
     ///
     /// **NOTE:** When the terminator is passed in on the command line, it is **not** stored as one
-    /// of the values
+    /// of the values, and parsing continues normally after it.
+    ///
+    /// **NOTE:** For [options] the terminator may be `--`, in which case it ends the option's
+    /// values instead of marking all following arguments as positional values.
+    ///
+    /// **NOTE:** When combined with [`max_values`], the terminator still ends the values early,
+    /// and is still consumed when exactly the maximum number of values precede it. Passing more
+    /// values than the maximum before the terminator is an error as usual.
     ///
     /// # Examples
     ///
@@ -1558,6 +1829,24 @@ impl<'help> Arg<'help> {
     /// assert_eq!(&cmds, &["find", "-type", "f", "-name", "special"]);
     /// assert_eq!(m.value_of("location"), Some("/home/clap"));
     /// ```
+    ///
+    /// Using `--` as the terminator of an option
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("files")
+    ///         .long("files")
+    ///         .multiple(true)
+    ///         .value_terminator("--"))
+    ///     .arg(Arg::with_name("rest"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--files", "a", "b", "c", "--", "rest"
+    ///     ]);
+    /// let files: Vec<_> = m.values_of("files").unwrap().collect();
+    /// assert_eq!(&files, &["a", "b", "c"]);
+    /// assert_eq!(m.value_of("rest"), Some("rest"));
+    /// ```
     /// [options]: ./struct.Arg.html#method.takes_value
     /// [positional arguments]: ./struct.Arg.html#method.index
     /// [`multiple(true)`]: ./struct.Arg.html#method.multiple
@@ -1692,6 +1981,105 @@ impl<'help> Arg<'help> {
         self
     }
 
+    /// Like [`Arg::possible_values`], but for values which may not be valid UTF-8, such as file
+    /// paths. Values are compared byte for byte, so [`ArgSettings::IgnoreCase`] doesn't apply to
+    /// them. They can be combined with [`Arg::possible_values`], a value is then valid if it
+    /// matches either.
+    ///
+    /// The help message and errors show these values lossily converted to UTF-8.
+    ///
+    /// **NOTE:** This setting only applies to [options] and [positional arguments]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// # use std::ffi::OsStr;
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("dir")
+    ///         .possible_values_os(&[OsStr::new("/tmp"), OsStr::new("/var/tmp")]))
+    ///     .get_matches_from(vec![
+    ///         "prog", "/var/tmp"
+    ///     ]);
+    /// assert_eq!(m.value_of_os("dir"), Some(OsStr::new("/var/tmp")));
+    /// ```
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [`ArgSettings::IgnoreCase`]: ./enum.ArgSettings.html#variant.IgnoreCase
+    /// [options]: ./struct.Arg.html#method.takes_value
+    /// [positional arguments]: ./struct.Arg.html#method.index
+    pub fn possible_values_os(mut self, names: &[&'help OsStr]) -> Self {
+        self.setb(ArgSettings::TakesValue);
+        if let Some(ref mut vec) = self.possible_vals_os {
+            for s in names {
+                vec.push(s);
+            }
+        } else {
+            self.possible_vals_os = Some(names.to_vec());
+        }
+        self
+    }
+
+    /// Tells the shell completion generators in [`complete`] what kind of value this argument
+    /// takes, i.e. a directory or a hostname, so the shell only offers matching candidates. Without
+    /// a hint the shell completes values as it does by default.
+    ///
+    /// **NOTE:** Setting a hint implies [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ValueHint, complete};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("out")
+    ///         .long("out-dir")
+    ///         .value_hint(ValueHint::DirPath));
+    ///
+    /// let mut buf = Vec::new();
+    /// complete::fish(&app, "myprog", &mut buf).unwrap();
+    /// let script = String::from_utf8(buf).unwrap();
+    ///
+    /// assert!(script.contains("-l out-dir -r -f -a \"(__fish_complete_directories)\""));
+    /// ```
+    /// [`complete`]: ./complete/index.html
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn value_hint(mut self, hint: ValueHint) -> Self {
+        self.setb(ArgSettings::TakesValue);
+        self.value_hint = hint;
+        self
+    }
+
+    /// Limits how many [possible values] are listed in the short help message (i.e. `-h`). Only
+    /// the first `cap` values are shown, followed by `...` and a pointer to `--help`, which
+    /// always lists all of them. A `cap` of `0` leaves only the pointer.
+    ///
+    /// This is useful for arguments with a large number of possible values which would
+    /// otherwise flood the help message.
+    ///
+    /// **NOTE:** The pointer names the long of the help flag, so it follows
+    /// `mut_arg("help", |a| a.long("usage"))`. Without a long help flag, such as with
+    /// [`AppSettings::DisableHelpFlags`], there's nothing to point to and every value is listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("prog")
+    ///     .arg(Arg::with_name("mode")
+    ///         .long("mode")
+    ///         .takes_value(true)
+    ///         .possible_values(&["fast", "slow", "medium", "turbo"])
+    ///         .possible_values_cap(2));
+    ///
+    /// let help = app.write_help_to_string().unwrap();
+    /// assert!(help.contains("[possible values: fast, slow, ... (see --help for all)]"));
+    /// ```
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    /// [`AppSettings::DisableHelpFlags`]: ./enum.AppSettings.html#variant.DisableHelpFlags
+    pub fn possible_values_cap(mut self, cap: usize) -> Self {
+        self.possible_vals_cap = Some(cap);
+        self
+    }
+
     /// Specifies the name of the [`ArgGroup`] the argument belongs to.
     ///
     /// # Examples
@@ -1783,6 +2171,9 @@ impl<'help> Arg<'help> {
     /// [`Arg::multiple(true)`] would allow `-f <file> <file> <file> -f <file> <file> <file>` where
     /// as *not* setting [`Arg::multiple(true)`] would only allow one occurrence of this argument.
     ///
+    /// **NOTE:** For options the count applies to *each* occurrence, so `-f <file> -f <file>
+    /// <file>` is an error even though three values were given in total.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -1901,6 +2292,44 @@ impl<'help> Arg<'help> {
         self
     }
 
+    /// Works like [`Arg::validator_os`] but the closure returns a full [`Error`] instead of a
+    /// message. The error is returned from parsing as is, so its [`ErrorKind`], formatting and
+    /// exit code are the same as those of clap's own errors.
+    ///
+    /// The validator runs as each value is parsed, before it is stored, and also applies to
+    /// default values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Error, ErrorKind};
+    /// # use std::ffi::OsStr;
+    /// fn not_zero(v: &OsStr) -> Result<(), Error> {
+    ///     if v != "0" { return Ok(()); }
+    ///     Err(Error::with_description("The count can't be zero", ErrorKind::InvalidValue))
+    /// }
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("count")
+    ///         .long("count")
+    ///         .takes_value(true)
+    ///         .validator_error(not_zero))
+    ///     .try_get_matches_from(vec![
+    ///         "prog", "--count", "0"
+    ///     ]);
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+    /// ```
+    /// [`Arg::validator_os`]: ./struct.Arg.html#method.validator_os
+    /// [`Error`]: ./struct.Error.html
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    pub fn validator_error<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&OsStr) -> Result<(), Error> + 'static,
+    {
+        self.validator_error = Some(Rc::new(f));
+        self
+    }
+
     /// Specifies the *maximum* number of values are for this argument. For example, if you had a
     /// `-f <file>` argument where you wanted up to 3 'files' you would set `.max_values(3)`, and
     /// this argument would be satisfied if the user provided, 1, 2, or 3 values.
@@ -2026,6 +2455,84 @@ impl<'help> Arg<'help> {
         self.setting(ArgSettings::TakesValue)
     }
 
+    /// Specifies the *maximum* number of times this argument may be used. For example, a `-v`
+    /// verbosity flag where anything beyond `-vvv` is meaningless could set
+    /// `.max_occurrences(3)`, and using it a fourth time would be an error.
+    ///
+    /// Unlike [`max_values`], this limits occurrences rather than values, so it also applies to
+    /// flags which take no values.
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::multiple_occurrences(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short('v')
+    ///         .max_occurrences(3))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-vvv"
+    ///     ]);
+    ///
+    /// assert_eq!(m.occurrences_of("verbose"), 3);
+    /// ```
+    ///
+    /// Using the argument more often than allowed is an error
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short('v')
+    ///         .max_occurrences(3))
+    ///     .try_get_matches_from(vec![
+    ///         "prog", "-vvvv"
+    ///     ]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::TooManyOccurrences);
+    /// ```
+    /// [`max_values`]: ./struct.Arg.html#method.max_values
+    /// [`Arg::multiple_occurrences(true)`]: ./struct.Arg.html#method.multiple_occurrences
+    pub fn max_occurrences(mut self, qty: u64) -> Self {
+        self.setb(ArgSettings::MultipleOccurrences);
+        self.max_occurs = Some(qty);
+        self
+    }
+
+    /// Specifies the *minimum* number of times this argument must be used when it is present.
+    /// For example, `.min_occurrences(2)` requires at least `-vv` whenever `-v` is used at all.
+    ///
+    /// **NOTE:** This is only checked when the argument is used. To also require the argument
+    /// to be present, combine it with [`Arg::required(true)`].
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::multiple_occurrences(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short('v')
+    ///         .min_occurrences(2))
+    ///     .try_get_matches_from(vec![
+    ///         "prog", "-v"
+    ///     ]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::TooFewOccurrences);
+    /// ```
+    /// [`Arg::required(true)`]: ./struct.Arg.html#method.required
+    /// [`Arg::multiple_occurrences(true)`]: ./struct.Arg.html#method.multiple_occurrences
+    pub fn min_occurrences(mut self, qty: u64) -> Self {
+        self.setb(ArgSettings::MultipleOccurrences);
+        self.min_occurs = Some(qty);
+        self
+    }
+
     /// Specifies the separator to use when values are clumped together, defaults to `,` (comma).
     ///
     /// **NOTE:** implicitly sets [`Arg::use_delimiter(true)`]
@@ -2276,6 +2783,32 @@ impl<'help> Arg<'help> {
         self.default_values_os(&[val])
     }
 
+    /// Provides a default value in the exact same manner as [`Arg::default_value`], but keeps it
+    /// out of the help message as [`ArgSettings::HideDefaultValue`] does. Setting both in one call
+    /// makes it hard to forget hiding a sensitive default, such as the path of a token.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ValueSource};
+    /// let mut app = App::new("prog")
+    ///     .arg(Arg::with_name("token")
+    ///         .long("token-file")
+    ///         .default_value_hidden("/etc/prog/token"));
+    ///
+    /// let help = app.write_help_to_string().unwrap();
+    /// assert!(!help.contains("/etc/prog/token"));
+    ///
+    /// let m = app.get_matches_from(vec!["prog"]);
+    /// assert_eq!(m.value_of("token"), Some("/etc/prog/token"));
+    /// assert_eq!(m.value_source("token"), Some(ValueSource::DefaultValue));
+    /// ```
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`ArgSettings::HideDefaultValue`]: ./enum.ArgSettings.html#variant.HideDefaultValue
+    pub fn default_value_hidden(self, val: &'help str) -> Self {
+        self.default_value(val).setting(ArgSettings::HideDefaultValue)
+    }
+
     /// Like [`Arg::default_value'] but for args taking multiple values
     /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
     pub fn default_values(self, vals: &[&'help str]) -> Self {
@@ -2296,6 +2829,77 @@ impl<'help> Arg<'help> {
         self
     }
 
+    /// Specifies the value of an option which was used at runtime without a value, i.e. `--color`
+    /// meaning `--color=auto` while `--color=never` still works. The help message displays such an
+    /// option as `--color[=<WHEN>]`.
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::takes_value(true)`] and [`Arg::require_equals(true)`],
+    /// so a following argument is never taken as the value, and [`Arg::min_values(0)`] unless the
+    /// minimum was already set.
+    ///
+    /// **NOTE:** [`Arg::default_value`] still applies when the option isn't used at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .value_name("WHEN")
+    ///         .possible_values(&["always", "auto", "never"])
+    ///         .default_value("never")
+    ///         .default_missing_value("auto"));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog"]);
+    /// assert_eq!(m.value_of("color"), Some("never"));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog", "--color"]);
+    /// assert_eq!(m.value_of("color"), Some("auto"));
+    /// assert_eq!(m.occurrences_of("color"), 1);
+    ///
+    /// let m = app.get_matches_from(vec!["prog", "--color=always"]);
+    /// assert_eq!(m.value_of("color"), Some("always"));
+    /// ```
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    /// [`Arg::require_equals(true)`]: ./struct.Arg.html#method.require_equals
+    /// [`Arg::min_values(0)`]: ./struct.Arg.html#method.min_values
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    pub fn default_missing_value(self, val: &'help str) -> Self {
+        self.default_missing_values_os(&[OsStr::from_bytes(val.as_bytes())])
+    }
+
+    /// Provides a default missing value in the exact same manner as
+    /// [`Arg::default_missing_value`] only using [`OsStr`]s instead.
+    /// [`Arg::default_missing_value`]: ./struct.Arg.html#method.default_missing_value
+    /// [`OsStr`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html
+    pub fn default_missing_value_os(self, val: &'help OsStr) -> Self {
+        self.default_missing_values_os(&[val])
+    }
+
+    /// Like [`Arg::default_missing_value`] but for args taking multiple values
+    /// [`Arg::default_missing_value`]: ./struct.Arg.html#method.default_missing_value
+    pub fn default_missing_values(self, vals: &[&'help str]) -> Self {
+        let vals_vec: Vec<_> = vals
+            .iter()
+            .map(|val| OsStr::from_bytes(val.as_bytes()))
+            .collect();
+        self.default_missing_values_os(&vals_vec[..])
+    }
+
+    /// Provides default missing values in the exact same manner as
+    /// [`Arg::default_missing_values`] only using [`OsStr`]s instead.
+    /// [`Arg::default_missing_values`]: ./struct.Arg.html#method.default_missing_values
+    /// [`OsStr`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html
+    pub fn default_missing_values_os(mut self, vals: &[&'help OsStr]) -> Self {
+        self.setb(ArgSettings::RequireEquals);
+        if self.min_vals.is_none() {
+            self.min_vals = Some(0);
+        }
+        self.default_missing_vals = Some(vals.to_vec());
+        self
+    }
+
     /// Specifies the value of the argument if `arg` has been used at runtime. If `val` is set to
     /// `None`, `arg` only needs to be present. If `val` is set to `"some-val"` then `arg` must be
     /// present at runtime **and** have the value `val`.
@@ -2310,6 +2914,9 @@ impl<'help> Arg<'help> {
     ///
     /// **NOTE:** This implicitly sets [`Arg::takes_value(true)`].
     ///
+    /// **NOTE:** Only the [`Arg::default_value`] is shown in the help message, conditional
+    /// defaults are not. If users need to know about them, describe them in [`Arg::long_help`].
+    ///
     /// **NOTE:** If using YAML the values should be laid out as follows (`None` can be represented
     /// as `null` in YAML)
     ///
@@ -2392,6 +2999,7 @@ impl<'help> Arg<'help> {
     /// ```
     /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
     /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
     pub fn default_value_if<T: Key>(
         self,
         arg_id: T,
@@ -2795,6 +3403,102 @@ impl<'help> Arg<'help> {
         }
     }
 
+    /// Specifies that once this positional argument is reached, it takes every argument left as
+    /// one of its values, taken literally. Flags, options, subcommand names and `--` aren't
+    /// recognized anymore from that point on. This is meant for commands which run another
+    /// command, such as `docker run IMAGE COMMAND...`. The help message shows it like any other
+    /// positional argument with multiple values, i.e. `<COMMAND>...`.
+    ///
+    /// This is like [`AppSettings::TrailingVarArg`], but only applies to this argument. It
+    /// differs from [`Last`], which is only accessible after a `--`, in that no `--` is needed.
+    /// And unlike a [`value_terminator`], nothing ends the values.
+    ///
+    /// **NOTE:** Setting this implies [`ArgSettings::TakesValue`], [`ArgSettings::MultipleValues`]
+    /// and [`ArgSettings::AllowHyphenValues`], so even the first value may start with a hyphen as
+    /// long as it isn't a known flag or option.
+    ///
+    /// **NOTE:** This used to imply [`Last`], i.e. only values after a `--` were captured. To get
+    /// that behavior back, use [`Last`] together with [`ArgSettings::MultipleValues`] and
+    /// [`ArgSettings::AllowHyphenValues`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("docker-run")
+    ///     .arg(Arg::from("-d, --detach 'run in the background'"))
+    ///     .arg(Arg::with_name("image").required(true))
+    ///     .arg(Arg::with_name("command")
+    ///         .value_name("COMMAND")
+    ///         .raw(true))
+    ///     .get_matches_from(vec![
+    ///         "docker-run", "-d", "alpine", "ls", "-d", "--", "/tmp"
+    ///     ]);
+    ///
+    /// assert_eq!(m.occurrences_of("detach"), 1);
+    /// assert_eq!(m.value_of("image"), Some("alpine"));
+    /// let cmd: Vec<_> = m.values_of("command").unwrap().collect();
+    /// assert_eq!(cmd, ["ls", "-d", "--", "/tmp"]);
+    /// ```
+    /// [`AppSettings::TrailingVarArg`]: ./enum.AppSettings.html#variant.TrailingVarArg
+    /// [`Last`]: ./struct.Arg.html#method.last
+    /// [`value_terminator`]: ./struct.Arg.html#method.value_terminator
+    /// [`ArgSettings::TakesValue`]: ./enum.ArgSettings.html#variant.TakesValue
+    /// [`ArgSettings::MultipleValues`]: ./enum.ArgSettings.html#variant.MultipleValues
+    /// [`ArgSettings::AllowHyphenValues`]: ./enum.ArgSettings.html#variant.AllowHyphenValues
+    pub fn raw(self, raw: bool) -> Self {
+        if raw {
+            self.setting(ArgSettings::Raw)
+                .setting(ArgSettings::TakesValue)
+                .setting(ArgSettings::MultipleValues)
+                .setting(ArgSettings::AllowHyphenValues)
+        } else {
+            self.unset_setting(ArgSettings::Raw)
+        }
+    }
+
+    /// Specifies that a value of exactly `-` means "read from stdin", a common Unix idiom such as
+    /// `tool -f -`. Such a value is accepted even when it isn't one of the [possible values] or
+    /// would be rejected by a [validator], and [`ArgMatches::value_reader`] then reads from stdin
+    /// instead of opening a file named `-`.
+    ///
+    /// **NOTE:** Setting this implies [`ArgSettings::TakesValue`]
+    ///
+    /// **NOTE:** Stdin can only be read once, so no more than one argument per invocation should
+    /// consume it. `clap` doesn't check this for you.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("file")
+    ///         .short('f')
+    ///         .allow_stdin_dash(true)
+    ///         .validator(|f| if f.ends_with(".txt") {
+    ///             Ok(())
+    ///         } else {
+    ///             Err(String::from("not a text file"))
+    ///         }))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-f", "-"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_of("file"), Some("-"));
+    /// ```
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    /// [validator]: ./struct.Arg.html#method.validator
+    /// [`ArgMatches::value_reader`]: ./struct.ArgMatches.html#method.value_reader
+    /// [`ArgSettings::TakesValue`]: ./enum.ArgSettings.html#variant.TakesValue
+    pub fn allow_stdin_dash(self, dash: bool) -> Self {
+        if dash {
+            self.setting(ArgSettings::AllowStdinDash)
+                .setting(ArgSettings::TakesValue)
+        } else {
+            self.unset_setting(ArgSettings::AllowStdinDash)
+        }
+    }
+
     /// Specifies that the argument is required by default. Required by default means it is
     /// required, when no other conflicting rules or overrides have been evaluated. Conflicting
     /// rules take precedence over being required.
@@ -3027,7 +3731,8 @@ impl<'help> Arg<'help> {
         }
     }
 
-    /// Specifies that an argument can be matched to all child [``]s.
+    /// Specifies that an argument can be matched to all child [``]s, at any depth, i.e. children
+    /// of children and so on.
     ///
     /// **NOTE:** Global arguments *only* propagate down, **not** up (to parent commands), however
     /// their values once a user uses them will be propagated back up to parents. In effect, this
@@ -3192,6 +3897,47 @@ impl<'help> Arg<'help> {
         }
     }
 
+    /// Specifies that the possible values of an argument should be sorted in the
+    /// `[possible values: ...]` of the help text and in the shell completions. Defaults to `false`
+    /// (i.e. the values are listed in the order they were given)
+    ///
+    /// The values are sorted alphabetically rather than by their bytes: case and the accents of
+    /// Latin letters are ignored, so `Éclair` comes before `fast`. This is no full collation, only
+    /// the accented letters of Latin-1 and Latin Extended-A are folded, anything else (such as
+    /// Greek or Cyrillic letters) is compared by its lowercase code point.
+    ///
+    /// This is useful when the possible values come from an unordered collection such as a
+    /// `HashSet`, which would otherwise make the help text change from one run to the next.
+    ///
+    /// **NOTE:** Setting this implies [`ArgSettings::TakesValue`]
+    ///
+    /// **NOTE:** The message of an [`ErrorKind::InvalidValue`] error always lists the possible
+    /// values sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("prog")
+    ///     .arg(Arg::with_name("mode")
+    ///         .long("mode")
+    ///         .possible_values(&["slow", "fast", "medium"])
+    ///         .sort_possible_values(true));
+    ///
+    /// let help = app.write_help_to_string().unwrap();
+    /// assert!(help.contains("[possible values: fast, medium, slow]"));
+    /// ```
+    /// [`ErrorKind::InvalidValue`]: ./enum.ErrorKind.html#variant.InvalidValue
+    /// [`ArgSettings::TakesValue`]: ./enum.ArgSettings.html#variant.TakesValue
+    pub fn sort_possible_values(self, sort: bool) -> Self {
+        if sort {
+            self.setting(ArgSettings::SortPossibleValues)
+                .setting(ArgSettings::TakesValue)
+        } else {
+            self.unset_setting(ArgSettings::SortPossibleValues)
+        }
+    }
+
     /// Specifies that the default value of an argument should not be displayed in the help text.
     ///
     /// This is useful when default behavior of an arg is explained elsewhere in the help text.
@@ -3385,6 +4131,38 @@ impl<'help> Arg<'help> {
         }
     }
 
+    /// Allows the [value delimiter] to be escaped with a backslash (`\`), in which case it's kept as
+    /// part of the value instead of splitting it. The backslash itself is removed, while
+    /// backslashes which don't precede the delimiter are left as is.
+    ///
+    /// **NOTE:** Setting this implies [`ArgSettings::TakesValue`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("path")
+    ///         .long("path")
+    ///         .value_delimiter(":")
+    ///         .allow_delimiter_escape(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", r"--path=/bin:/opt/c\:d:/usr/bin",
+    ///     ]);
+    ///
+    /// assert_eq!(m.values_of("path").unwrap().collect::<Vec<_>>(), ["/bin", "/opt/c:d", "/usr/bin"]);
+    /// ```
+    /// [value delimiter]: ./struct.Arg.html#method.value_delimiter
+    /// [`ArgSettings::TakesValue`]: ./enum.ArgSettings.html#variant.TakesValue
+    pub fn allow_delimiter_escape(self, esc: bool) -> Self {
+        if esc {
+            self.setting(ArgSettings::AllowDelimiterEscape)
+                .setting(ArgSettings::TakesValue)
+        } else {
+            self.unset_setting(ArgSettings::AllowDelimiterEscape)
+        }
+    }
+
     /// Specifies that any values inside the associated ENV variables of an argument should not be
     /// displayed in the help text.
     ///
@@ -3421,6 +4199,33 @@ impl<'help> Arg<'help> {
         }
     }
 
+    /// Specifies that the ENV variable of an argument should not be displayed in the help text at
+    /// all. Unlike [`Arg::hide_env_values`] this omits the whole `[env: ...]` portion, while the
+    /// ENV variable is still read when the argument isn't used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("connect")
+    ///     .arg(Arg::with_name("host")
+    ///         .long("host")
+    ///         .env("CONNECT")
+    ///         .hide_env(true));
+    ///
+    /// ```
+    ///
+    /// If we were to run the above program with `$ CONNECT=super_secret connect --help` the
+    /// `[env: CONNECT=super_secret]` portion of the help text would be omitted.
+    /// [`Arg::hide_env_values`]: ./struct.Arg.html#method.hide_env_values
+    pub fn hide_env(self, hide: bool) -> Self {
+        if hide {
+            self.setting(ArgSettings::HideEnv)
+        } else {
+            self.unset_setting(ArgSettings::HideEnv)
+        }
+    }
+
     /// When set to `true` the help string will be displayed on the line after the argument and
     /// indented once. This can be helpful for arguments with very long or complex help messages.
     /// This can also be helpful for arguments with very long flag names, or many/long value names.
@@ -3789,27 +4594,6 @@ impl<'help> Arg<'help> {
         }
     }
 
-    /// Indicates that all parameters passed after this should not be parsed
-    /// individually, but rather passed in their entirety. It is worth noting
-    /// that setting this requires all values to come after a `--` to indicate they
-    /// should all be captured. For example:
-    ///
-    /// ```notrust
-    /// --foo something -- -v -v -v -b -b -b --baz -q -u -x
-    /// ```
-    /// Will result in everything after `--` to be considered one raw argument. This behavior
-    /// may not be exactly what you are expecting and using [`AppSettings::TrailingVarArg`]
-    /// may be more appropriate.
-    ///
-    /// **NOTE:** Implicitly sets [`Arg::multiple(true)`], [`Arg::allow_hyphen_values(true)`], and
-    /// [`Arg::last(true)`] when set to `true`
-    ///
-    /// [`Arg::multiple(true)`]: ./struct.Arg.html#method.multiple
-    /// [`Arg::allow_hyphen_values(true)`]: ./struct.Arg.html#method.allow_hyphen_values
-    /// [`Arg::last(true)`]: ./struct.Arg.html#method.last
-    /// [`AppSettings::TrailingVarArg`]: ./enum.AppSettings.html#variant.TrailingVarArg
-    pub fn raw(self, raw: bool) -> Self { self.multiple(raw).allow_hyphen_values(raw).last(raw) }
-
     /// Hides an argument from short help message output.
     ///
     /// **NOTE:** This does **not** hide the argument from usage strings on error
@@ -3993,9 +4777,31 @@ impl<'help> Arg<'help> {
         self
     }
 
-    /// Set a custom heading for this arg to be printed under
-    pub fn help_heading(mut self, s: Option<&'help str>) -> Self {
-        self.help_heading = s;
+    /// Sets a custom heading for this argument to be printed under in the help message, instead
+    /// of the default `FLAGS:`, `OPTIONS:`, etc. Sections are displayed in the order their
+    /// headings are first used, after the default sections.
+    ///
+    /// **NOTE:** An argument's own heading takes precedence over one set with
+    /// [`App::help_heading`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// App::new("prog")
+    ///     .arg(Arg::with_name("proxy")
+    ///         .long("proxy")
+    ///         .takes_value(true)
+    ///         .help_heading("NETWORKING"))
+    ///     .arg(Arg::with_name("log-file")
+    ///         .long("log-file")
+    ///         .takes_value(true)
+    ///         .help_heading("LOGGING"))
+    /// # ;
+    /// ```
+    /// [`App::help_heading`]: ./struct.App.html#method.help_heading
+    pub fn help_heading<H: Into<Option<&'help str>>>(mut self, s: H) -> Self {
+        self.help_heading = s.into();
         self
     }
 
@@ -4035,11 +4841,58 @@ impl<'help> Arg<'help> {
     #[doc(hidden)]
     pub fn has_switch(&self) -> bool { self.short.is_some() || self.long.is_some() }
 
+    // Is `s` the short or one of the short aliases?
+    pub(crate) fn has_short(&self, s: char) -> bool {
+        self.short == Some(s)
+            || self
+                .short_aliases
+                .as_ref()
+                .map_or(false, |als| als.iter().any(|&(c, _)| c == s))
+    }
+
     #[doc(hidden)]
     pub fn longest_filter(&self) -> bool {
         self.is_set(ArgSettings::TakesValue) || self.long.is_some() || self.short.is_none()
     }
 
+    // The value placeholders written for the arg (i.e. `<FILE>`), and whether they're followed by
+    // `...`. Both the Display impl and the help message write the values this way.
+    pub(crate) fn val_placeholders(&self) -> (Vec<String>, bool) {
+        if self.has_switch() && !self.is_set(ArgSettings::TakesValue) {
+            return (vec![], false);
+        }
+        let mult = self.is_set(ArgSettings::MultipleValues)
+            || self.is_set(ArgSettings::MultipleOccurrences);
+        if let Some(ref vec) = self.val_names {
+            let vals = vec.values().map(|val| format!("<{}>", val)).collect();
+            (vals, mult && vec.len() == 1)
+        } else if let Some(num) = self.num_vals {
+            let vals = (0..num).map(|_| format!("<{}>", self.name)).collect();
+            (vals, mult && num == 1)
+        } else {
+            (vec![format!("<{}>", self.name)], mult)
+        }
+    }
+
+    // All possible values for display and shell completions, including the lossily converted
+    // OsStr ones
+    pub(crate) fn possible_vals_lossy(&self) -> Option<Vec<Cow<'help, str>>> {
+        if self.possible_vals.is_none() && self.possible_vals_os.is_none() {
+            return None;
+        }
+        let strs = self.possible_vals.iter().flatten().map(|&v| Cow::Borrowed(v));
+        let oss = self
+            .possible_vals_os
+            .iter()
+            .flatten()
+            .map(|&v| v.to_string_lossy());
+        let mut pvs: Vec<_> = strs.chain(oss).collect();
+        if self.is_set(ArgSettings::SortPossibleValues) {
+            pvs.sort_by(|a, b| collate(a, b));
+        }
+        Some(pvs)
+    }
+
     // Used for positionals when printing
     #[doc(hidden)]
     pub fn multiple_str(&self) -> &str {
@@ -4101,96 +4954,42 @@ impl<'help> PartialEq for Arg<'help> {
 
 impl<'help> Display for Arg<'help> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        if self.index.is_some() || (self.long.is_none() && self.short.is_none()) {
-            // Positional
-            let mut delim = String::new();
-            delim.push(if self.is_set(ArgSettings::RequireDelimiter) {
-                self.val_delim.expect(INTERNAL_ERROR_MSG)
-            } else {
-                ' '
-            });
-            if let Some(ref names) = self.val_names {
-                write!(
-                    f,
-                    "{}",
-                    names
-                        .values()
-                        .map(|n| format!("<{}>", n))
-                        .collect::<Vec<_>>()
-                        .join(&*delim)
-                )?;
-            } else {
-                write!(f, "<{}>", self.name)?;
+        if self.has_switch() {
+            if !self.is_set(ArgSettings::TakesValue) {
+                // Flag
+                return match self.long {
+                    Some(l) => write!(f, "--{}", l),
+                    None => write!(f, "-{}", self.short.expect(INTERNAL_ERROR_MSG)),
+                };
             }
-            if self.settings.is_set(ArgSettings::MultipleValues)
-                && (self.val_names.is_none()
-                    || (self.val_names.is_some() && self.val_names.as_ref().unwrap().len() == 1))
-            {
-                write!(f, "...")?;
-            }
-            return Ok(());
-        } else if !self.is_set(ArgSettings::TakesValue) {
-            // Flag
-            if let Some(l) = self.long {
-                write!(f, "--{}", l)?;
-            } else if let Some(s) = self.short {
-                write!(f, "-{}", s)?;
+            // Write the name such --long or -l, the values of an arg with a default missing value
+            // are optional, so they're written as --long[=<val>]
+            let sep = if self.default_missing_vals.is_some() {
+                "[="
+            } else if self.is_set(ArgSettings::RequireEquals) {
+                "="
+            } else {
+                " "
+            };
+            match self.long {
+                Some(l) => write!(f, "--{}{}", l, sep)?,
+                None => write!(f, "-{}{}", self.short.expect(INTERNAL_ERROR_MSG), sep)?,
             }
-
-            return Ok(());
-        }
-        let sep = if self.is_set(ArgSettings::RequireEquals) {
-            "="
-        } else {
-            " "
-        };
-        // Write the name such --long or -l
-        if let Some(l) = self.long {
-            write!(f, "--{}{}", l, sep)?;
-        } else {
-            write!(f, "-{}{}", self.short.unwrap(), sep)?;
         }
+
+        // Write the values such as <name1> <name2>
+        let (vals, ellipsis) = self.val_placeholders();
         let delim = if self.is_set(ArgSettings::RequireDelimiter) {
             self.val_delim.expect(INTERNAL_ERROR_MSG)
         } else {
             ' '
         };
-
-        // Write the values such as <name1> <name2>
-        if let Some(ref vec) = self.val_names {
-            let mut it = vec.iter().peekable();
-            while let Some((_, val)) = it.next() {
-                write!(f, "<{}>", val)?;
-                if it.peek().is_some() {
-                    write!(f, "{}", delim)?;
-                }
-            }
-            let num = vec.len();
-            if self.is_set(ArgSettings::MultipleValues) && num == 1 {
-                write!(f, "...")?;
-            }
-        } else if let Some(num) = self.num_vals {
-            let mut it = (0..num).peekable();
-            while let Some(_) = it.next() {
-                write!(f, "<{}>", self.name)?;
-                if it.peek().is_some() {
-                    write!(f, "{}", delim)?;
-                }
-            }
-            if self.is_set(ArgSettings::MultipleValues) && num == 1 {
-                write!(f, "...")?;
-            }
-        } else {
-            write!(
-                f,
-                "<{}>{}",
-                self.name,
-                if self.is_set(ArgSettings::MultipleOccurrences) {
-                    "..."
-                } else {
-                    ""
-                }
-            )?;
+        write!(f, "{}", vals.join(&*delim.to_string()))?;
+        if ellipsis {
+            write!(f, "...")?;
+        }
+        if self.has_switch() && self.default_missing_vals.is_some() {
+            write!(f, "]")?;
         }
 
         Ok(())
@@ -4214,11 +5013,13 @@ impl<'help> fmt::Debug for Arg<'help> {
             "Arg {{ id: {:X?}, name: {:?}, help: {:?}, long_help: {:?}, conflicts_with: {:?}, \
              settings: {:?}, required_unless: {:?}, overrides_with: {:?}, groups: {:?}, \
              requires: {:?}, requires_ifs: {:?}, short: {:?}, index: {:?}, long: {:?}, \
-             aliases: {:?}, possible_values: {:?}, value_names: {:?}, number_of_values: {:?}, \
-             max_values: {:?}, min_values: {:?}, value_delimiter: {:?}, default_value_ifs: {:?}, \
+             aliases: {:?}, short_aliases: {:?}, possible_values: {:?}, possible_values_os: {:?}, value_hint: {:?}, \
+             value_names: {:?}, number_of_values: {:?}, \
+             max_values: {:?}, min_values: {:?}, max_occurrences: {:?}, min_occurrences: {:?}, \
+             value_delimiter: {:?}, default_value_ifs: {:?}, \
              value_terminator: {:?}, display_order: {:?}, env: {:?}, unified_ord: {:?}, \
-             default_value: {:?}, validator: {}, validator_os: {} \
-             }}",
+             default_value: {:?}, default_missing_value: {:?}, validator: {}, validator_os: {}, \
+             validator_error: {} }}",
             self.id,
             self.name,
             self.help,
@@ -4234,11 +5035,16 @@ impl<'help> fmt::Debug for Arg<'help> {
             self.index,
             self.long,
             self.aliases,
+            self.short_aliases,
             self.possible_vals,
+            self.possible_vals_os,
+            self.value_hint,
             self.val_names,
             self.num_vals,
             self.max_vals,
             self.min_vals,
+            self.max_occurs,
+            self.min_occurs,
             self.val_delim,
             self.default_vals_ifs,
             self.terminator,
@@ -4246,8 +5052,10 @@ impl<'help> fmt::Debug for Arg<'help> {
             self.env,
             self.unified_ord,
             self.default_vals,
+            self.default_missing_vals,
             self.validator.as_ref().map_or("None", |_| "Some(Fn)"),
-            self.validator_os.as_ref().map_or("None", |_| "Some(Fn)")
+            self.validator_os.as_ref().map_or("None", |_| "Some(Fn)"),
+            self.validator_error.as_ref().map_or("None", |_| "Some(Fn)")
         )
     }
 }
@@ -4347,6 +5155,16 @@ mod test {
         assert_eq!(&*format!("{}", o), "--option <opt>");
     }
 
+    #[test]
+    fn option_display_default_missing_value() {
+        let o = Arg::with_name("color")
+            .long("color")
+            .value_name("WHEN")
+            .default_missing_value("always");
+
+        assert_eq!(&*format!("{}", o), "--color[=<WHEN>]");
+    }
+
     // Positionals
 
     #[test]
@@ -4357,6 +5175,14 @@ mod test {
         assert_eq!(&*format!("{}", p), "<pos>...");
     }
 
+    #[test]
+    fn positional_display_mult_occurrences() {
+        let mut p = Arg::with_name("pos").index(1);
+        p.setb(ArgSettings::MultipleOccurrences);
+
+        assert_eq!(&*format!("{}", p), "<pos>...");
+    }
+
     #[test]
     fn positional_display_required() {
         let mut p2 = Arg::with_name("pos").index(1);
@@ -24,6 +24,13 @@ bitflags! {
         const HIDDEN_SHORT_H   = 1 << 18;
         const HIDDEN_LONG_H    = 1 << 19;
         const MULTIPLE_VALS    = 1 << 20 | Self::TAKES_VAL.bits;
+        const HIDE_ENV         = 1 << 21;
+        const DELIM_ESCAPE     = 1 << 22;
+        const NO_WRAP          = 1 << 23;
+        const RAW              = 1 << 24;
+        const STDIN_DASH       = 1 << 25;
+        const SORT_PV          = 1 << 26;
+        const OVERRIDE_ALWAYS  = 1 << 27;
     }
 }
 
@@ -53,9 +60,16 @@ impl ArgFlags {
         Last => Flags::LAST,
         IgnoreCase => Flags::CASE_INSENSITIVE,
         HideEnvValues => Flags::HIDE_ENV_VALS,
+        HideEnv => Flags::HIDE_ENV,
+        AllowDelimiterEscape => Flags::DELIM_ESCAPE,
         HideDefaultValue => Flags::HIDE_DEFAULT_VAL,
         HiddenShortHelp => Flags::HIDDEN_SHORT_H,
-        HiddenLongHelp => Flags::HIDDEN_LONG_H
+        HiddenLongHelp => Flags::HIDDEN_LONG_H,
+        NoWrap => Flags::NO_WRAP,
+        Raw => Flags::RAW,
+        AllowStdinDash => Flags::STDIN_DASH,
+        SortPossibleValues => Flags::SORT_PV,
+        OverridesRegardlessOfOrder => Flags::OVERRIDE_ALWAYS
     }
 }
 
@@ -88,6 +102,9 @@ pub enum ArgSettings {
     /// Enables a delimiter to break up arguments `--option val1,val2,val3` becomes three values
     /// (`val1`, `val2`, and `val3`) instead of the default one (`val1,val2,val3`)
     UseValueDelimiter,
+    /// Allows escaping the value delimiter with a backslash so `--option val1\,val2` becomes the
+    /// single value `val1,val2`
+    AllowDelimiterEscape,
     /// Tells an arg to display it's help on the line below the arg itself in the help message
     NextLineHelp,
     /// Says that arg *must* use a delimiter to separate values
@@ -108,10 +125,28 @@ pub enum ArgSettings {
     /// Hides any values currently assigned to ENV variables in the help message (good for sensitive
     /// information)
     HideEnvValues,
+    /// Hides the ENV variable of an arg, along with its value, from the help message
+    HideEnv,
     /// The argument should **not** be shown in short help text
     HiddenShortHelp,
     /// The argument should **not** be shown in long help text
     HiddenLongHelp,
+    /// Displays the help text as is instead of wrapping it at the terminal width, which keeps
+    /// preformatted text like tables or code samples intact. Lines after the first are still
+    /// indented to line up under it.
+    NoWrap,
+    /// Once a positional arg is reached, takes all the remaining arguments as its values, even
+    /// those starting with a hyphen
+    Raw,
+    /// A value of exactly `-` means "read from stdin" and is accepted regardless of possible
+    /// values and validators
+    AllowStdinDash,
+    /// Sorts the possible values in the help message and shell completions instead of listing them
+    /// in the order they were given
+    SortPossibleValues,
+    /// The args this arg overrides (or is overridden by) are removed whenever it's used, no matter
+    /// if they were used before or after it
+    OverridesRegardlessOfOrder,
     #[doc(hidden)]
     RequiredUnlessAll,
     #[doc(hidden)]
@@ -127,6 +162,7 @@ impl FromStr for ArgSettings {
             "hidden" => Ok(ArgSettings::Hidden),
             "takesvalue" => Ok(ArgSettings::TakesValue),
             "usevaluedelimiter" => Ok(ArgSettings::UseValueDelimiter),
+            "allowdelimiterescape" => Ok(ArgSettings::AllowDelimiterEscape),
             "nextlinehelp" => Ok(ArgSettings::NextLineHelp),
             "requiredunlessall" => Ok(ArgSettings::RequiredUnlessAll),
             "requiredelimiter" => Ok(ArgSettings::RequireDelimiter),
@@ -138,8 +174,14 @@ impl FromStr for ArgSettings {
             "hidedefaultvalue" => Ok(ArgSettings::HideDefaultValue),
             "ignorecase" => Ok(ArgSettings::IgnoreCase),
             "hideenvvalues" => Ok(ArgSettings::HideEnvValues),
+            "hideenv" => Ok(ArgSettings::HideEnv),
             "hiddenshorthelp" => Ok(ArgSettings::HiddenShortHelp),
             "hiddenlonghelp" => Ok(ArgSettings::HiddenLongHelp),
+            "nowrap" => Ok(ArgSettings::NoWrap),
+            "raw" => Ok(ArgSettings::Raw),
+            "allowstdindash" => Ok(ArgSettings::AllowStdinDash),
+            "sortpossiblevalues" => Ok(ArgSettings::SortPossibleValues),
+            "overridesregardlessoforder" => Ok(ArgSettings::OverridesRegardlessOfOrder),
             _ => Err("unknown ArgSetting, cannot convert from str".to_owned()),
         }
     }
@@ -191,6 +233,10 @@ mod test {
             "usevaluedelimiter".parse::<ArgSettings>().unwrap(),
             ArgSettings::UseValueDelimiter
         );
+        assert_eq!(
+            "allowdelimiterescape".parse::<ArgSettings>().unwrap(),
+            ArgSettings::AllowDelimiterEscape
+        );
         assert_eq!(
             "valuedelimiternotset".parse::<ArgSettings>().unwrap(),
             ArgSettings::ValueDelimiterNotSet
@@ -212,6 +258,10 @@ mod test {
             "hideenvvalues".parse::<ArgSettings>().unwrap(),
             ArgSettings::HideEnvValues
         );
+        assert_eq!(
+            "hideenv".parse::<ArgSettings>().unwrap(),
+            ArgSettings::HideEnv
+        );
         assert_eq!(
             "hiddenshorthelp".parse::<ArgSettings>().unwrap(),
             ArgSettings::HiddenShortHelp
@@ -220,6 +270,23 @@ mod test {
             "hiddenlonghelp".parse::<ArgSettings>().unwrap(),
             ArgSettings::HiddenLongHelp
         );
+        assert_eq!(
+            "nowrap".parse::<ArgSettings>().unwrap(),
+            ArgSettings::NoWrap
+        );
+        assert_eq!("raw".parse::<ArgSettings>().unwrap(), ArgSettings::Raw);
+        assert_eq!(
+            "allowstdindash".parse::<ArgSettings>().unwrap(),
+            ArgSettings::AllowStdinDash
+        );
+        assert_eq!(
+            "sortpossiblevalues".parse::<ArgSettings>().unwrap(),
+            ArgSettings::SortPossibleValues
+        );
+        assert_eq!(
+            "overridesregardlessoforder".parse::<ArgSettings>().unwrap(),
+            ArgSettings::OverridesRegardlessOfOrder
+        );
         assert!("hahahaha".parse::<ArgSettings>().is_err());
     }
 }
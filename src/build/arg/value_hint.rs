@@ -0,0 +1,75 @@
+// Std
+use std::str::FromStr;
+
+/// Tells the shell completion generators in [`complete`] what kind of value an argument takes, so
+/// that the shell can offer matching candidates (i.e. only directories). Set with
+/// [`Arg::value_hint`].
+///
+/// **NOTE:** Not every shell can complete every kind of value, generators fall back to their
+/// default completion for the hints they don't support. An argument with [possible values] always
+/// completes those instead.
+///
+/// [`complete`]: ./complete/index.html
+/// [`Arg::value_hint`]: ./struct.Arg.html#method.value_hint
+/// [possible values]: ./struct.Arg.html#method.possible_values
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ValueHint {
+    /// Nothing is known about the value, the shell completes it as it does by default
+    Unknown,
+    /// The value is something else entirely, so no completions are offered
+    Other,
+    /// Any existing path, file or directory
+    AnyPath,
+    /// The path of a file
+    FilePath,
+    /// The path of a directory
+    DirPath,
+    /// The path of an executable file
+    ExecutablePath,
+    /// The name of a command found in `PATH`
+    CommandName,
+    /// The name of a user on this system
+    Username,
+    /// The name of a host
+    Hostname,
+}
+
+impl Default for ValueHint {
+    fn default() -> Self { ValueHint::Unknown }
+}
+
+impl FromStr for ValueHint {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        match &*s.to_ascii_lowercase() {
+            "unknown" => Ok(ValueHint::Unknown),
+            "other" => Ok(ValueHint::Other),
+            "anypath" => Ok(ValueHint::AnyPath),
+            "filepath" => Ok(ValueHint::FilePath),
+            "dirpath" => Ok(ValueHint::DirPath),
+            "executablepath" => Ok(ValueHint::ExecutablePath),
+            "commandname" => Ok(ValueHint::CommandName),
+            "username" => Ok(ValueHint::Username),
+            "hostname" => Ok(ValueHint::Hostname),
+            _ => Err("unknown ValueHint, cannot convert from str".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ValueHint;
+
+    #[test]
+    fn value_hint_fromstr() {
+        assert_eq!(
+            "dirpath".parse::<ValueHint>().unwrap(),
+            ValueHint::DirPath
+        );
+        assert_eq!(
+            "Hostname".parse::<ValueHint>().unwrap(),
+            ValueHint::Hostname
+        );
+        assert!("directory".parse::<ValueHint>().is_err());
+    }
+}
@@ -96,6 +96,8 @@ pub struct ArgGroup<'a> {
     pub conflicts: Option<Vec<Id>>,
     #[doc(hidden)]
     pub multiple: bool,
+    #[doc(hidden)]
+    pub help: Option<&'a str>,
 }
 
 impl<'a> ArgGroup<'a> {
@@ -434,6 +436,42 @@ impl<'a> ArgGroup<'a> {
         }
         self
     }
+
+    /// Sets the help text for the group. When set, the group's args are written to the help
+    /// message as a single entry, i.e. `--json|--yaml|--toml`, described by this text instead of
+    /// each arg's own help. Args that aren't shown (i.e. [`Arg::hidden`]) are left out of the
+    /// entry.
+    ///
+    /// **NOTE:** Args from different help sections (i.e. flags and options) each get their own
+    /// entry in their section, unless [`AppSettings::UnifiedHelpMessage`] is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("json")
+    ///         .long("json"))
+    ///     .arg(Arg::with_name("yaml")
+    ///         .long("yaml"))
+    ///     .group(ArgGroup::with_name("format")
+    ///         .args(&["json", "yaml"])
+    ///         .help("Output format"));
+    /// ```
+    ///
+    /// The help message then lists the flags as:
+    ///
+    /// ```notrust
+    /// FLAGS:
+    ///         --json|--yaml    Output format
+    ///     -h, --help           Prints help information
+    /// ```
+    /// [`Arg::hidden`]: ./struct.Arg.html#method.hidden
+    /// [`AppSettings::UnifiedHelpMessage`]: ./enum.AppSettings.html#variant.UnifiedHelpMessage
+    pub fn help(mut self, h: &'a str) -> Self {
+        self.help = Some(h);
+        self
+    }
 }
 
 impl<'a> Debug for ArgGroup<'a> {
@@ -462,6 +500,7 @@ impl<'a, 'z> From<&'z ArgGroup<'a>> for ArgGroup<'a> {
             requires: g.requires.clone(),
             conflicts: g.conflicts.clone(),
             multiple: g.multiple,
+            help: g.help,
         }
     }
 }
@@ -498,6 +537,12 @@ impl<'a> From<&'a yaml_rust::yaml::Hash> for ArgGroup<'a> {
                 }
                 "requires" => yaml_vec_or_str!(v, a, requires),
                 "conflicts_with" => yaml_vec_or_str!(v, a, conflicts_with),
+                "help" => {
+                    if let Some(ys) = v.as_str() {
+                        a = a.help(ys);
+                    }
+                    a
+                }
                 "name" => {
                     if let Some(ys) = v.as_str() {
                         a.name = ys;
@@ -644,6 +689,7 @@ impl<'a> Clone for ArgGroup<'a> {
             requires: self.requires.clone(),
             conflicts: self.conflicts.clone(),
             multiple: self.multiple,
+            help: self.help,
         }
     }
 }
@@ -7,7 +7,7 @@ pub mod arg;
 mod arg_group;
 mod usage_parser;
 
-pub use self::app::{App, AppFlags, AppSettings, Propagation};
-pub use self::arg::{Arg, ArgFlags, ArgSettings};
+pub use self::app::{App, AppFlags, AppSettings, Propagation, UnknownArgAction};
+pub use self::arg::{Arg, ArgFlags, ArgSettings, ValueHint};
 pub use self::arg_group::ArgGroup;
 pub use self::usage_parser::UsageParser;
@@ -68,6 +68,12 @@ impl<'a> UsageParser<'a> {
             // We had a positional and need to set mult vals too
             arg.setb(ArgSettings::MultipleValues);
         }
+        if arg.name.is_empty() {
+            panic!(
+                "Unable to determine an argument name from the usage string {:?}",
+                self.usage
+            );
+        }
         debugln!("UsageParser::parse: vals...{:?}", arg.val_names);
         arg
     }
@@ -84,8 +90,15 @@ impl<'a> UsageParser<'a> {
         {
             arg.setb(ArgSettings::Required);
         }
+        let open = self.usage.as_bytes()[self.pos];
         self.pos += 1;
         self.stop_at(name_end);
+        if (open == b'<' || open == b'[') && self.pos >= self.usage.len() {
+            panic!(
+                "Unclosed '{}' in the usage string {:?}",
+                open as char, self.usage
+            );
+        }
         let name = &self.usage[self.start..self.pos];
         if self.prev == UsageToken::Unknown {
             debugln!("UsageParser::name: setting name...{}", name);
@@ -126,13 +139,14 @@ impl<'a> UsageParser<'a> {
     fn short_or_long(&mut self, arg: &mut Arg<'a>) {
         debugln!("UsageParser::short_or_long;");
         self.pos += 1;
-        if *self
-            .usage
-            .as_bytes()
-            .get(self.pos)
-            .expect(INTERNAL_ERROR_MSG)
-            == b'-'
-        {
+        let next = match self.usage.as_bytes().get(self.pos) {
+            Some(&b) if b != b' ' => b,
+            _ => panic!(
+                "Expected a short or long name after '-' in the usage string {:?}",
+                self.usage
+            ),
+        };
+        if next == b'-' {
             self.pos += 1;
             self.long(arg);
             return;
@@ -144,6 +158,12 @@ impl<'a> UsageParser<'a> {
         debugln!("UsageParser::long;");
         self.stop_at(long_end);
         let name = &self.usage[self.start..self.pos];
+        if name.is_empty() {
+            panic!(
+                "Expected a long name after '--' in the usage string {:?}",
+                self.usage
+            );
+        }
         if !self.explicit_name_set {
             debugln!("UsageParser::long: setting name...{}", name);
             arg.id = name.key();
@@ -197,6 +217,12 @@ impl<'a> UsageParser<'a> {
         self.stop_at(help_start);
         self.start = self.pos + 1;
         self.pos = self.usage.len() - 1;
+        if self.start > self.pos || self.usage.as_bytes()[self.pos] != b'\'' {
+            panic!(
+                "Unterminated help text, expected a closing ' in the usage string {:?}",
+                self.usage
+            );
+        }
         debugln!(
             "UsageParser::help: setting help...{}",
             &self.usage[self.start..self.pos]
@@ -1354,4 +1380,24 @@ mod test {
         );
         assert_eq!(a.help, Some("hælp"));
     }
+
+    #[test]
+    #[should_panic(expected = "Unable to determine an argument name")]
+    fn create_no_name() { let _ = Arg::from("'some help info'"); }
+
+    #[test]
+    #[should_panic(expected = "Expected a short or long name after '-'")]
+    fn create_dangling_dash() { let _ = Arg::from("- 'some help info'"); }
+
+    #[test]
+    #[should_panic(expected = "Expected a long name after '--'")]
+    fn create_dangling_double_dash() { let _ = Arg::from("-o, -- 'some help info'"); }
+
+    #[test]
+    #[should_panic(expected = "Unclosed '<'")]
+    fn create_unclosed_val_name() { let _ = Arg::from("--opt <FILE 'some help info'"); }
+
+    #[test]
+    #[should_panic(expected = "Unterminated help text")]
+    fn create_unterminated_help() { let _ = Arg::from("--opt <FILE> 'some help info"); }
 }
@@ -0,0 +1,175 @@
+// Std
+use std::io::Write;
+
+// Internal
+use super::{built, cmd_path, function_name, longs, quote, shorts, switches, walk};
+use crate::build::{App, AppSettings, Arg, ArgSettings, ValueHint};
+use crate::parse::errors::Result as ClapResult;
+
+/// Writes a [bash] completion script for `app` to `buf`, completing the binary `bin_name`.
+/// The arguments are the same as [`complete::fish`], so switching shells only requires changing
+/// the function called.
+///
+/// The script defines a completion function which works out the subcommand path typed so far,
+/// then offers the flags, options (along with their visible aliases), [possible values] of
+/// positional arguments and subcommands valid at that point. Right after an option, its possible
+/// values are completed, or else what its [`ValueHint`] asks for. Hidden args and subcommands are
+/// left out.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, complete};
+/// let app = App::new("myprog")
+///     .arg(Arg::with_name("verbose")
+///         .long("verbose")
+///         .help("Be verbose"))
+///     .subcommand(App::new("test")
+///         .about("Tests things"));
+///
+/// let mut buf = Vec::new();
+/// complete::bash(&app, "myprog", &mut buf).unwrap();
+/// let script = String::from_utf8(buf).unwrap();
+///
+/// assert!(script.contains("complete -F _myprog -o bashdefault -o default 'myprog'"));
+/// assert!(script.contains("'myprog;test')"));
+/// ```
+/// [bash]: https://www.gnu.org/software/bash/
+/// [possible values]: ../struct.Arg.html#method.possible_values
+/// [`ValueHint`]: ../enum.ValueHint.html
+/// [`complete::fish`]: ./fn.fish.html
+pub fn bash<W: Write>(app: &App, bin_name: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::bash: bin_name={}", bin_name);
+    let app = built(app);
+    let fn_name = function_name(bin_name);
+
+    writeln!(
+        buf,
+        r#"{fn_name}() {{
+    local cur prev cmd word
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    cmd={bin}
+    for word in "${{COMP_WORDS[@]:1:COMP_CWORD-1}}"; do
+        case "${{cmd}};${{word}}" in"#,
+        fn_name = fn_name,
+        bin = quote(bin_name)
+    )?;
+    // Only a subcommand's name moves on to its path, any other word leaves the path as it is
+    walk(&app, &mut vec![], &mut |app, path| {
+        let cmd = cmd_path(bin_name, path);
+        for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+            writeln!(buf, "            {})", quote(&*format!("{};{}", cmd, sc.name)))?;
+            writeln!(buf, "                cmd=\"${{cmd}};${{word}}\"")?;
+            writeln!(buf, "                ;;")?;
+        }
+        Ok(())
+    })?;
+    writeln!(
+        buf,
+        r#"        esac
+    done
+
+    case "${{cmd}}" in"#
+    )?;
+    walk(&app, &mut vec![], &mut |app, path| {
+        write_app(app, &*cmd_path(bin_name, path), buf)
+    })?;
+    writeln!(
+        buf,
+        r#"    esac
+}}
+
+complete -F {fn_name} -o bashdefault -o default {bin}"#,
+        fn_name = fn_name,
+        bin = quote(bin_name)
+    )?;
+    Ok(())
+}
+
+// Writes the completions for `app`, used when the subcommand path typed so far is `path`
+fn write_app<W: Write>(app: &App, path: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::bash::write_app: path={}", path);
+    writeln!(buf, "        {})", quote(path))?;
+
+    let opts: Vec<&Arg> = switches(app, true).collect();
+    if !opts.is_empty() {
+        writeln!(buf, "            case \"${{prev}}\" in")?;
+        for o in opts {
+            let names: Vec<String> = shorts(o)
+                .into_iter()
+                .map(|s| format!("-{}", s))
+                .chain(longs(o).into_iter().map(|l| format!("--{}", l)))
+                .map(|n| quote(&*n))
+                .collect();
+            writeln!(buf, "                {})", names.join("|"))?;
+            writeln!(buf, "                    {}", values(o))?;
+            writeln!(buf, "                    return 0")?;
+            writeln!(buf, "                    ;;")?;
+        }
+        writeln!(buf, "            esac")?;
+    }
+
+    let mut words = vec![];
+    for a in switches(app, true).chain(switches(app, false)) {
+        words.extend(shorts(a).into_iter().map(|s| format!("-{}", s)));
+        words.extend(longs(a).into_iter().map(|l| format!("--{}", l)));
+    }
+    for p in positionals!(app).filter(|a| !a.is_set(ArgSettings::Hidden)) {
+        if let Some(pv) = p.possible_vals_lossy() {
+            words.extend(pv.into_iter().map(String::from));
+        }
+    }
+    for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+        words.push(sc.name.clone());
+    }
+    writeln!(
+        buf,
+        "            COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))",
+        quote(&*word_list(&*words))
+    )?;
+    writeln!(buf, "            ;;")?;
+    Ok(())
+}
+
+// The command completing the values of the option `o`, without a hint bash completes files
+fn values(o: &Arg) -> String {
+    if let Some(pv) = o.possible_vals_lossy() {
+        return format!(
+            "COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))",
+            quote(&*word_list(&*pv))
+        );
+    }
+    let action = match o.value_hint {
+        ValueHint::Other => return String::from("COMPREPLY=()"),
+        ValueHint::Unknown
+        | ValueHint::AnyPath
+        | ValueHint::FilePath
+        | ValueHint::ExecutablePath => "-f",
+        ValueHint::DirPath => "-d",
+        ValueHint::CommandName => "-c",
+        ValueHint::Username => "-u",
+        ValueHint::Hostname => "-A hostname",
+    };
+    format!("COMPREPLY=($(compgen {} -- \"${{cur}}\"))", action)
+}
+
+// The word list given to `compgen -W`, which expands each word and removes quotes from it, so the
+// characters the shell would act on are escaped
+fn word_list<S: AsRef<str>>(words: &[S]) -> String {
+    words
+        .iter()
+        .map(|w| {
+            let mut escaped = String::with_capacity(w.as_ref().len());
+            for c in w.as_ref().chars() {
+                match c {
+                    '\\' | '"' | '\'' | '$' | '`' | ' ' | '\t' => escaped.push('\\'),
+                    _ => (),
+                }
+                escaped.push(c);
+            }
+            escaped
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,122 @@
+// Std
+use std::io::Write;
+
+// Internal
+use super::{built, longs, shorts, switches, walk};
+use crate::build::{App, AppSettings, ArgSettings};
+use crate::parse::errors::Result as ClapResult;
+
+/// Writes an [elvish] completion script for `app` to `buf`, completing the binary `bin_name`.
+/// The arguments are the same as [`complete::fish`], so switching shells only requires changing
+/// the function called.
+///
+/// The script registers an `edit:completion:arg-completer` which works out the subcommand path
+/// typed so far, then offers the flags and options (along with their visible aliases),
+/// [possible values] of positional arguments and subcommands valid at that point. Help text (or the
+/// about text of subcommands) is shown next to each candidate. Hidden args and subcommands are left
+/// out.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, complete};
+/// let app = App::new("myprog")
+///     .arg(Arg::with_name("verbose")
+///         .long("verbose")
+///         .help("Be verbose"))
+///     .subcommand(App::new("test")
+///         .about("Tests things"));
+///
+/// let mut buf = Vec::new();
+/// complete::elvish(&app, "myprog", &mut buf).unwrap();
+/// let script = String::from_utf8(buf).unwrap();
+///
+/// assert!(script.contains("set edit:completion:arg-completer[myprog] = {|@words|"));
+/// assert!(script.contains("cand '--verbose' 'Be verbose'"));
+/// assert!(script.contains("&'myprog;test'= {"));
+/// ```
+/// [elvish]: https://elv.sh/
+/// [possible values]: ../struct.Arg.html#method.possible_values
+/// [`complete::fish`]: ./fn.fish.html
+pub fn elvish<W: Write>(app: &App, bin_name: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::elvish: bin_name={}", bin_name);
+    let app = built(app);
+    let bin_name = escape(bin_name);
+
+    writeln!(
+        buf,
+        r#"use builtin;
+use str;
+
+set edit:completion:arg-completer[{bin}] = {{|@words|
+    fn spaces {{|n|
+        builtin:repeat $n ' ' | str:join ''
+    }}
+    fn cand {{|text desc|
+        edit:complex-candidate $text &display=$text' '(spaces (- 14 (wcswidth $text)))$desc
+    }}
+    var command = '{bin}'
+    for word $words[1..-1] {{
+        if (str:has-prefix $word '-') {{
+            break
+        }}
+        set command = $command';'$word
+    }}
+    var completions = ["#,
+        bin = bin_name
+    )?;
+    walk(&app, &mut vec![], &mut |app, path| {
+        let mut cmd = bin_name.clone();
+        for sc in path {
+            cmd.push(';');
+            cmd.push_str(&*escape(sc));
+        }
+        write_app(app, &cmd, buf)
+    })?;
+    writeln!(
+        buf,
+        r#"    ]
+    $completions[$command]
+}}"#
+    )?;
+    Ok(())
+}
+
+// Writes the candidates for `app`, offered when the subcommand path typed so far is `path`
+fn write_app<W: Write>(app: &App, path: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::elvish::write_app: path={}", path);
+    writeln!(buf, "        &'{}'= {{", path)?;
+
+    for a in switches(app, true).chain(switches(app, false)) {
+        let desc = a.help.unwrap_or("");
+        for s in shorts(a) {
+            write_cand(buf, &format!("-{}", s), desc)?;
+        }
+        for l in longs(a) {
+            write_cand(buf, &format!("--{}", l), desc)?;
+        }
+    }
+
+    for p in positionals!(app).filter(|a| !a.is_set(ArgSettings::Hidden)) {
+        if let Some(pv) = p.possible_vals_lossy() {
+            for v in pv {
+                write_cand(buf, &*v, p.help.unwrap_or(""))?;
+            }
+        }
+    }
+
+    for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+        write_cand(buf, &sc.name, sc.about.unwrap_or(""))?;
+    }
+
+    writeln!(buf, "        }}")?;
+    Ok(())
+}
+
+fn write_cand<W: Write>(buf: &mut W, text: &str, desc: &str) -> ClapResult<()> {
+    writeln!(buf, "            cand '{}' '{}'", escape(text), escape(desc))?;
+    Ok(())
+}
+
+// Single quoted elvish strings only need quotes doubled
+fn escape(s: &str) -> String { s.replace("'", "''") }
@@ -0,0 +1,157 @@
+// Std
+use std::io::Write;
+
+// Internal
+use super::{built, longs, shorts, switches, walk};
+use crate::build::{App, AppSettings, Arg, ArgSettings, ValueHint};
+use crate::parse::errors::Result as ClapResult;
+
+/// Writes a [fish] completion script for `app` to `buf`, completing the binary `bin_name`.
+///
+/// Flags and options are completed by their short and long names as well as their visible
+/// aliases, options with [possible values] complete those values, and subcommands are only
+/// offered before any other subcommand has been used. Hidden args and subcommands are left out.
+/// Values are completed according to their [`ValueHint`], i.e. only directories for
+/// [`ValueHint::DirPath`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, complete};
+/// let app = App::new("myprog")
+///     .arg(Arg::with_name("color")
+///         .long("color")
+///         .help("When to use colors")
+///         .possible_values(&["always", "never"]));
+///
+/// let mut buf = Vec::new();
+/// complete::fish(&app, "myprog", &mut buf).unwrap();
+/// let script = String::from_utf8(buf).unwrap();
+///
+/// assert!(script.contains("-l color -d 'When to use colors' -r -f -a \"always never\""));
+/// ```
+/// [fish]: https://fishshell.com/
+/// [possible values]: ../struct.Arg.html#method.possible_values
+/// [`ValueHint`]: ../enum.ValueHint.html
+/// [`ValueHint::DirPath`]: ../enum.ValueHint.html#variant.DirPath
+pub fn fish<W: Write>(app: &App, bin_name: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::fish: bin_name={}", bin_name);
+    let app = built(app);
+    walk(&app, &mut vec![], &mut |app, path| {
+        write_app(app, bin_name, path.last().cloned(), buf)
+    })
+}
+
+fn write_app<W: Write>(
+    app: &App,
+    bin_name: &str,
+    sc: Option<&str>,
+    buf: &mut W,
+) -> ClapResult<()> {
+    debugln!("complete::fish::write_app: app={}", app.name);
+    // The root only completes until a subcommand has been used, each subcommand only once it has
+    let cond = match sc {
+        Some(name) => format!("__fish_seen_subcommand_from {}", name),
+        None => String::from("__fish_use_subcommand"),
+    };
+    let base = format!("complete -c {} -n \"{}\"", bin_name, cond);
+
+    for o in switches(app, true) {
+        let mut line = base.clone();
+        push_names(&mut line, o);
+        if let Some(h) = o.help {
+            line.push_str(&format!(" -d '{}'", escape(h)));
+        }
+        line.push_str(" -r");
+        if let Some(pv) = o.possible_vals_lossy() {
+            line.push_str(&format!(" -f -a \"{}\"", words(&*pv)));
+        } else {
+            line.push_str(hint_flags(o.value_hint));
+        }
+        writeln!(buf, "{}", line)?;
+    }
+
+    for f in switches(app, false) {
+        let mut line = base.clone();
+        push_names(&mut line, f);
+        if let Some(h) = f.help {
+            line.push_str(&format!(" -d '{}'", escape(h)));
+        }
+        writeln!(buf, "{}", line)?;
+    }
+
+    for p in positionals!(app).filter(|a| !a.is_set(ArgSettings::Hidden)) {
+        let mut line = base.clone();
+        if let Some(pv) = p.possible_vals_lossy() {
+            line.push_str(&format!(" -f -a \"{}\"", words(&*pv)));
+        } else if p.value_hint != ValueHint::Unknown {
+            line.push_str(hint_flags(p.value_hint));
+        } else {
+            continue;
+        }
+        if let Some(h) = p.help {
+            line.push_str(&format!(" -d '{}'", escape(h)));
+        }
+        writeln!(buf, "{}", line)?;
+    }
+
+    for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+        let mut line = base.clone();
+        line.push_str(&format!(" -f -a \"{}\"", escape_word(&*sc.name)));
+        if let Some(a) = sc.about {
+            line.push_str(&format!(" -d '{}'", escape(a)));
+        }
+        writeln!(buf, "{}", line)?;
+    }
+    Ok(())
+}
+
+// Adds the shorts and longs of `a`, including its visible aliases
+fn push_names(line: &mut String, a: &Arg) {
+    for s in shorts(a) {
+        line.push_str(&format!(" -s {}", s));
+    }
+    for l in longs(a) {
+        line.push_str(&format!(" -l {}", l));
+    }
+}
+
+// Completes a value of the kind `hint`, without a hint fish completes files
+fn hint_flags(hint: ValueHint) -> &'static str {
+    match hint {
+        ValueHint::Unknown => "",
+        ValueHint::Other => " -f",
+        ValueHint::AnyPath | ValueHint::FilePath | ValueHint::ExecutablePath => " -F",
+        ValueHint::DirPath => " -f -a \"(__fish_complete_directories)\"",
+        ValueHint::CommandName => " -f -a \"(__fish_complete_command)\"",
+        ValueHint::Username => " -f -a \"(__fish_complete_users)\"",
+        ValueHint::Hostname => " -f -a \"(__fish_print_hostnames)\"",
+    }
+}
+
+fn escape(s: &str) -> String { s.replace("\\", "\\\\").replace("'", "\\'") }
+
+// The words given to `-a` inside double quotes, which fish expands and splits into candidates
+fn words<S: AsRef<str>>(vals: &[S]) -> String {
+    vals.iter()
+        .map(|v| escape_word(v.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Escapes `s` so fish takes it as a single, literal candidate. The double quotes are removed first,
+// which takes one backslash off `\`, `"` and `$`, and the backslash left over keeps fish from then
+// splitting or expanding at the character
+fn escape_word(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | '"' | '$' => escaped.push_str("\\\\\\"),
+            ' ' | '\t' | '\'' | '(' | ')' | '[' | ']' | '{' | '}' | '*' | '?' | '~' | '#' | ';'
+            | '&' | '|' | '<' | '>' => escaped.push_str("\\\\"),
+            _ => (),
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,102 @@
+//! Generates shell completion scripts from an [`App`], so that the completions always match the
+//! arguments and subcommands which are actually accepted.
+//!
+//! [`App`]: ../struct.App.html
+
+mod bash;
+mod elvish;
+mod fish;
+mod powershell;
+mod zsh;
+
+pub use self::bash::bash;
+pub use self::elvish::elvish;
+pub use self::fish::fish;
+pub use self::powershell::powershell;
+pub use self::zsh::zsh;
+
+// Internal
+use crate::build::{App, AppSettings, Arg, ArgSettings, Propagation};
+use crate::parse::errors::Result as ClapResult;
+
+// Builds a copy of the app and all of its subcommands, so auto generated args such as `--help`
+// and propagated global args are included in the completions
+fn built<'b>(app: &App<'b>) -> App<'b> {
+    fn build(app: &mut App) {
+        app._build();
+        app._propagate(Propagation::NextLevel);
+        for sc in &mut app.subcommands {
+            build(sc);
+        }
+    }
+    let mut app = app.clone();
+    build(&mut app);
+    app
+}
+
+// Calls `f` with `app` and then, depth first, every subcommand which isn't hidden, along with the
+// names of the subcommands leading to it (empty for `app` itself)
+fn walk<'a, 'b, F>(app: &'a App<'b>, path: &mut Vec<&'a str>, f: &mut F) -> ClapResult<()>
+where
+    F: FnMut(&'a App<'b>, &[&'a str]) -> ClapResult<()>,
+{
+    f(app, &*path)?;
+    for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+        path.push(&*sc.name);
+        walk(sc, path, f)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+// The options (or the flags if `takes_value` is false) of `app` which aren't hidden, including the
+// ones listed under a help heading
+fn switches<'a, 'b>(app: &'a App<'b>, takes_value: bool) -> impl Iterator<Item = &'a Arg<'b>> {
+    app.args.args.iter().filter(move |a| {
+        a.has_switch()
+            && a.is_set(ArgSettings::TakesValue) == takes_value
+            && !a.is_set(ArgSettings::Hidden)
+    })
+}
+
+// The short of `a` followed by its visible short aliases
+fn shorts(a: &Arg) -> Vec<char> {
+    let mut shorts: Vec<char> = a.short.into_iter().collect();
+    if let Some(ref als) = a.short_aliases {
+        shorts.extend(als.iter().filter(|&&(_, vis)| vis).map(|&(s, _)| s));
+    }
+    shorts
+}
+
+// The long of `a` followed by its visible aliases
+fn longs<'b>(a: &Arg<'b>) -> Vec<&'b str> {
+    let mut longs: Vec<&str> = a.long.into_iter().collect();
+    if let Some(ref als) = a.aliases {
+        longs.extend(als.iter().filter(|&&(_, vis)| vis).map(|&(l, _)| l));
+    }
+    longs
+}
+
+// The subcommand path `path` of the binary `bin_name` as the scripts spell it, i.e. `myprog;test`
+fn cmd_path(bin_name: &str, path: &[&str]) -> String {
+    let mut cmd = String::from(bin_name);
+    for sc in path {
+        cmd.push(';');
+        cmd.push_str(sc);
+    }
+    cmd
+}
+
+// The name of the completion function for `bin_name` in bash and zsh, which only allow some
+// characters in it
+fn function_name(bin_name: &str) -> String {
+    let name: String = bin_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("_{}", name)
+}
+
+// Single quotes `s` for bash and zsh. A single quote can't be escaped inside single quotes, so the
+// quotes are closed around it instead
+fn quote(s: &str) -> String { format!("'{}'", s.replace("'", "'\\''")) }
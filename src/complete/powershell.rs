@@ -0,0 +1,129 @@
+// Std
+use std::io::Write;
+
+// Internal
+use super::{built, longs, shorts, switches, walk};
+use crate::build::{App, AppSettings};
+use crate::parse::errors::Result as ClapResult;
+
+/// Writes a [PowerShell] completion script for `app` to `buf`, completing the binary `bin_name`.
+/// The arguments are the same as [`complete::fish`], so switching shells only requires changing
+/// the function called.
+///
+/// The script registers an argument completer which works out the subcommand path typed so far,
+/// then offers the flags, options (along with their visible aliases) and subcommands valid at that
+/// point. Help text (or the about text of subcommands) is shown as the tooltip. Hidden args and
+/// subcommands are left out.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, complete};
+/// let app = App::new("myprog")
+///     .arg(Arg::with_name("verbose")
+///         .long("verbose")
+///         .help("Be verbose"))
+///     .subcommand(App::new("test")
+///         .about("Tests things"));
+///
+/// let mut buf = Vec::new();
+/// complete::powershell(&app, "myprog", &mut buf).unwrap();
+/// let script = String::from_utf8(buf).unwrap();
+///
+/// assert!(script.contains("Register-ArgumentCompleter -Native -CommandName 'myprog'"));
+/// assert!(script.contains("'myprog;test' {"));
+/// ```
+/// [PowerShell]: https://docs.microsoft.com/powershell/
+/// [`complete::fish`]: ./fn.fish.html
+pub fn powershell<W: Write>(app: &App, bin_name: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::powershell: bin_name={}", bin_name);
+    let app = built(app);
+    let bin_name = escape(bin_name);
+
+    writeln!(
+        buf,
+        r#"using namespace System.Management.Automation
+using namespace System.Management.Automation.Language
+
+Register-ArgumentCompleter -Native -CommandName '{bin}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $commandElements = $commandAst.CommandElements
+    $command = @(
+        '{bin}'
+        for ($i = 1; $i -lt $commandElements.Count; $i++) {{
+            $element = $commandElements[$i]
+            if ($element -isnot [StringConstantExpressionAst] -or
+                $element.StringConstantType -ne [StringConstantType]::BareWord -or
+                $element.Value.StartsWith('-')) {{
+                break
+            }}
+            $element.Value
+        }}) -join ';'
+
+    $completions = @(switch ($command) {{"#,
+        bin = bin_name
+    )?;
+    walk(&app, &mut vec![], &mut |app, path| {
+        let mut cmd = bin_name.clone();
+        for sc in path {
+            cmd.push(';');
+            cmd.push_str(&*escape(sc));
+        }
+        write_app(app, &cmd, buf)
+    })?;
+    writeln!(
+        buf,
+        r#"    }})
+
+    $completions.Where{{ $_.CompletionText -like "$wordToComplete*" }} |
+        Sort-Object -Property ListItemText
+}}"#
+    )?;
+    Ok(())
+}
+
+// Writes the completions for `app`, matched when the subcommand path typed so far is `path`
+fn write_app<W: Write>(app: &App, path: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::powershell::write_app: path={}", path);
+    writeln!(buf, "        '{}' {{", path)?;
+
+    for a in switches(app, true).chain(switches(app, false)) {
+        let tooltip = escape(a.help.unwrap_or(a.name));
+        for s in shorts(a) {
+            write_result(buf, &format!("-{}", s), &s.to_string(), "ParameterName", &tooltip)?;
+        }
+        for l in longs(a) {
+            let l = escape(l);
+            write_result(buf, &format!("--{}", l), &l, "ParameterName", &tooltip)?;
+        }
+    }
+
+    for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+        let name = escape(&sc.name);
+        let tooltip = escape(sc.about.unwrap_or(&sc.name));
+        write_result(buf, &name, &name, "ParameterValue", &tooltip)?;
+    }
+
+    writeln!(buf, "            break")?;
+    writeln!(buf, "        }}")?;
+    Ok(())
+}
+
+fn write_result<W: Write>(
+    buf: &mut W,
+    text: &str,
+    list_text: &str,
+    kind: &str,
+    tooltip: &str,
+) -> ClapResult<()> {
+    writeln!(
+        buf,
+        "            [CompletionResult]::new('{}', '{}', [CompletionResultType]::{}, '{}')",
+        text, list_text, kind, tooltip
+    )?;
+    Ok(())
+}
+
+// Single quoted PowerShell strings only need quotes doubled
+fn escape(s: &str) -> String { s.replace("'", "''") }
@@ -0,0 +1,168 @@
+// Std
+use std::io::Write;
+
+// Internal
+use super::{built, cmd_path, function_name, longs, quote, shorts, switches, walk};
+use crate::build::{App, AppSettings, Arg, ArgSettings, ValueHint};
+use crate::parse::errors::Result as ClapResult;
+
+/// Writes a [zsh] completion script for `app` to `buf`, completing the binary `bin_name`.
+/// The arguments are the same as [`complete::fish`], so switching shells only requires changing
+/// the function called.
+///
+/// The script is a `#compdef` function which works out the subcommand path typed so far, then
+/// offers the flags, options (along with their visible aliases), [possible values] of positional
+/// arguments and subcommands valid at that point, each described by its help text (or the about
+/// text of subcommands). Right after an option, its possible values are completed, or else what
+/// its [`ValueHint`] asks for. Hidden args and subcommands are left out.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, complete};
+/// let app = App::new("myprog")
+///     .arg(Arg::with_name("verbose")
+///         .long("verbose")
+///         .help("Be verbose"))
+///     .subcommand(App::new("test")
+///         .about("Tests things"));
+///
+/// let mut buf = Vec::new();
+/// complete::zsh(&app, "myprog", &mut buf).unwrap();
+/// let script = String::from_utf8(buf).unwrap();
+///
+/// assert!(script.starts_with("#compdef myprog\n"));
+/// assert!(script.contains("'--verbose:Be verbose'"));
+/// assert!(script.contains("('myprog;test')"));
+/// ```
+/// [zsh]: https://www.zsh.org/
+/// [possible values]: ../struct.Arg.html#method.possible_values
+/// [`ValueHint`]: ../enum.ValueHint.html
+/// [`complete::fish`]: ./fn.fish.html
+pub fn zsh<W: Write>(app: &App, bin_name: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::zsh: bin_name={}", bin_name);
+    let app = built(app);
+    let fn_name = function_name(bin_name);
+
+    writeln!(
+        buf,
+        r#"#compdef {bin_name}
+
+{fn_name}() {{
+    local cmd word
+    local -a candidates
+    cmd={bin}
+    for word in "${{(@)words[2,CURRENT-1]}}"; do
+        case "${{cmd}};${{word}}" in"#,
+        bin_name = bin_name,
+        fn_name = fn_name,
+        bin = quote(bin_name)
+    )?;
+    // Only a subcommand's name moves on to its path, any other word leaves the path as it is
+    walk(&app, &mut vec![], &mut |app, path| {
+        let cmd = cmd_path(bin_name, path);
+        for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+            writeln!(buf, "            ({})", quote(&*format!("{};{}", cmd, sc.name)))?;
+            writeln!(buf, "                cmd=\"${{cmd}};${{word}}\"")?;
+            writeln!(buf, "                ;;")?;
+        }
+        Ok(())
+    })?;
+    writeln!(
+        buf,
+        r#"        esac
+    done
+
+    case "${{cmd}}" in"#
+    )?;
+    walk(&app, &mut vec![], &mut |app, path| {
+        write_app(app, &*cmd_path(bin_name, path), buf)
+    })?;
+    writeln!(
+        buf,
+        r#"    esac
+    _describe -t commands {bin} candidates
+}}
+
+{fn_name} "$@""#,
+        fn_name = fn_name,
+        bin = quote(bin_name)
+    )?;
+    Ok(())
+}
+
+// Writes the completions for `app`, used when the subcommand path typed so far is `path`
+fn write_app<W: Write>(app: &App, path: &str, buf: &mut W) -> ClapResult<()> {
+    debugln!("complete::zsh::write_app: path={}", path);
+    writeln!(buf, "        ({})", quote(path))?;
+
+    let opts: Vec<&Arg> = switches(app, true).collect();
+    if !opts.is_empty() {
+        writeln!(buf, "            case \"${{words[CURRENT-1]}}\" in")?;
+        for o in opts {
+            let names: Vec<String> = shorts(o)
+                .into_iter()
+                .map(|s| format!("-{}", s))
+                .chain(longs(o).into_iter().map(|l| format!("--{}", l)))
+                .map(|n| quote(&*n))
+                .collect();
+            writeln!(buf, "                ({})", names.join("|"))?;
+            writeln!(buf, "                    {}", values(o))?;
+            writeln!(buf, "                    return")?;
+            writeln!(buf, "                    ;;")?;
+        }
+        writeln!(buf, "            esac")?;
+    }
+
+    writeln!(buf, "            candidates=(")?;
+    for a in switches(app, true).chain(switches(app, false)) {
+        let desc = a.help.unwrap_or("");
+        for s in shorts(a) {
+            write_candidate(buf, &*format!("-{}", s), desc)?;
+        }
+        for l in longs(a) {
+            write_candidate(buf, &*format!("--{}", l), desc)?;
+        }
+    }
+    for p in positionals!(app).filter(|a| !a.is_set(ArgSettings::Hidden)) {
+        if let Some(pv) = p.possible_vals_lossy() {
+            for v in pv {
+                write_candidate(buf, &*v, p.help.unwrap_or(""))?;
+            }
+        }
+    }
+    for sc in subcommands!(app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+        write_candidate(buf, &*sc.name, sc.about.unwrap_or(""))?;
+    }
+    writeln!(buf, "            )")?;
+    writeln!(buf, "            ;;")?;
+    Ok(())
+}
+
+// `_describe` takes `name:description`, so a colon in the name has to be escaped
+fn write_candidate<W: Write>(buf: &mut W, name: &str, desc: &str) -> ClapResult<()> {
+    let cand = name.replace("\\", "\\\\").replace(":", "\\:");
+    if desc.is_empty() {
+        writeln!(buf, "                {}", quote(&*cand))?;
+    } else {
+        writeln!(buf, "                {}", quote(&*format!("{}:{}", cand, desc)))?;
+    }
+    Ok(())
+}
+
+// The command completing the values of the option `o`, without a hint zsh completes files
+fn values(o: &Arg) -> String {
+    if let Some(pv) = o.possible_vals_lossy() {
+        let vals: Vec<String> = pv.iter().map(|v| quote(v)).collect();
+        return format!("compadd -- {}", vals.join(" "));
+    }
+    String::from(match o.value_hint {
+        ValueHint::Other => "_message 'value'",
+        ValueHint::Unknown | ValueHint::AnyPath | ValueHint::FilePath => "_files",
+        ValueHint::DirPath => "_files -/",
+        ValueHint::ExecutablePath => "_files -g '*(-*)'",
+        ValueHint::CommandName => "_command_names -e",
+        ValueHint::Username => "_users",
+        ValueHint::Hostname => "_hosts",
+    })
+}
@@ -328,8 +328,8 @@
 //!
 //! * `suggestions`: Turns on the `Did you mean '--myoption'?` feature for when users make typos. (builds dependency `strsim`)
 //! * `color`: Turns on colored error messages. This feature only works on non-Windows OSs. (builds dependency `ansi-term` and `atty`)
-//! * `wrap_help`: Wraps the help at the actual terminal width when
-//!  available, instead of 120 chracters. (builds dependency `textwrap`
+//! * `wrap_help`: Wraps the help at the actual terminal width (or the `COLUMNS` environment
+//!  variable) when available, instead of 120 chracters. (builds dependency `textwrap`
 //! with feature `term_size`)
 //!
 //! To disable these, add this to your `Cargo.toml`:
@@ -556,10 +556,15 @@ extern crate vec_map;
 #[cfg(feature = "yaml")]
 extern crate yaml_rust;
 
-pub use crate::build::{App, AppSettings, Arg, ArgGroup, ArgSettings, Propagation};
-pub use crate::output::fmt::Format;
+pub use crate::build::{
+    App, AppSettings, Arg, ArgGroup, ArgSettings, Propagation, UnknownArgAction, ValueHint,
+};
+pub use crate::output::fmt::{ColorChoice, Format, TermColor};
+pub use crate::output::{
+    DefaultUsageRenderer, HelpEntry, HelpSection, HelpTree, UsageParts, UsageRenderer, UsageSpec,
+};
 pub use crate::parse::errors::{Error, ErrorKind, Result};
-pub use crate::parse::{ArgMatches, OsValues, Values};
+pub use crate::parse::{ArgMatches, Indices, Occurrences, OsValues, ValueSource, Values};
 #[cfg(feature = "yaml")]
 pub use yaml_rust::YamlLoader;
 
@@ -572,6 +577,8 @@ use std::result::Result as StdResult;
 #[macro_use]
 mod macros;
 
+pub mod complete;
+
 mod build;
 mod mkeymap;
 mod output;
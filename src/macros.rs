@@ -998,16 +998,36 @@ macro_rules! find_subcmd {
     }};
 }
 
-macro_rules! longs {
+macro_rules! find_subcmd_ignore_case {
+    ($app:expr, $sc:expr) => {{
+        let sc = $sc.to_lowercase();
+        subcommands!($app).find(|a| {
+            a.name.to_lowercase() == sc
+                || a.aliases
+                    .as_ref()
+                    .map_or(false, |als| als.iter().any(|al| al.0.to_lowercase() == sc))
+        })
+    }};
+}
+
+// The longs (and their aliases) of all args which aren't hidden
+macro_rules! visible_longs {
     ($app:expr) => {{
         use crate::mkeymap::KeyType;
-        $app.args.keys.iter().map(|x| &x.key).filter_map(|a| {
-            if let KeyType::Long(v) = a {
-                Some(v)
-            } else {
-                None
-            }
-        })
+        $app.args
+            .keys
+            .iter()
+            .filter(|k| {
+                !$app.args.args[k.index]
+                    .is_set(crate::build::ArgSettings::Hidden)
+            })
+            .filter_map(|k| {
+                if let KeyType::Long(ref v) = k.key {
+                    Some(v)
+                } else {
+                    None
+                }
+            })
     }};
 }
 
@@ -1031,6 +1051,21 @@ macro_rules! sc_names {
     }};
 }
 
+macro_rules! visible_sc_names {
+    ($app:expr) => {{
+        $app.subcommands
+            .iter()
+            .filter(|s| !s.is_set(crate::build::AppSettings::Hidden))
+            .flat_map(|s| {
+                std::iter::once(&*s.name).chain(
+                    s.aliases
+                        .iter()
+                        .flat_map(|als| als.iter().filter(|a| a.1).map(|a| a.0)),
+                )
+            })
+    }};
+}
+
 macro_rules! match_alias {
     ($a:expr, $to:expr, $what:expr) => {{
         $what == $to
@@ -139,36 +139,6 @@ impl<'b> MKeyMap<'b> {
         }
     }
 
-    pub fn make_entries_by_index(&mut self, index: usize) {
-        let short;
-        let positional;
-        let mut longs: Vec<_>;
-
-        {
-            let arg = &self.args[index];
-            short = arg.short.map(KeyType::Short);
-            positional = arg.index.map(KeyType::Position);
-
-            longs = arg
-                .aliases
-                .clone()
-                .map(|v| {
-                    v.iter()
-                        .map(|(n, _)| KeyType::Long(OsString::from(n)))
-                        .collect()
-                })
-                .unwrap_or_default();
-            longs.extend(arg.long.map(|l| KeyType::Long(OsString::from(l))));
-        }
-
-        if let Some(s) = short {
-            self.insert_key(s, index)
-        }
-        if let Some(p) = positional {
-            self.insert_key(p, index)
-        }
-    }
-
     pub fn find_by_name(&mut self, name: &str) -> usize {
         self.args
             .iter()
@@ -229,6 +199,9 @@ fn _get_keys(arg: &Arg) -> Vec<KeyType> {
     if let Some(c) = arg.short {
         keys.push(KeyType::Short(c));
     }
+    if let Some(ref aliases) = arg.short_aliases {
+        keys.extend(aliases.iter().map(|&(c, _)| KeyType::Short(c)));
+    }
     if let Some(ref aliases) = arg.aliases {
         for long in aliases
             .iter()
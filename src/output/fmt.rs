@@ -2,12 +2,13 @@
 use ansi_term::ANSIString;
 
 #[cfg(all(feature = "color", not(target_os = "windows")))]
-use ansi_term::Colour::{Green, Red, Yellow};
+use ansi_term::Colour::{self, Black, Blue, Cyan, Fixed, Green, Purple, Red, White, Yellow, RGB};
 
 #[cfg(feature = "color")]
 use atty;
 use std::env;
 use std::fmt;
+use std::str::FromStr;
 
 #[doc(hidden)]
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -17,6 +18,48 @@ pub enum ColorWhen {
     Never,
 }
 
+/// When to color the help and error messages, set with [`App::color`]. It can also be parsed
+/// from the usual values of a `--color` flag, i.e. `"always"`.
+///
+/// **NOTE:** Colors need the `color` cargo feature and aren't supported on Windows.
+///
+/// [`App::color`]: ./struct.App.html#method.color
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Colors the output when it goes to a terminal which isn't `dumb`
+    Auto,
+    /// Always colors the output, even when it's redirected
+    Always,
+    /// Never colors the output
+    Never,
+}
+
+impl Default for ColorChoice {
+    fn default() -> Self { ColorChoice::Auto }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        match &*s.to_ascii_lowercase() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err("unknown ColorChoice, cannot convert from str".to_owned()),
+        }
+    }
+}
+
+impl From<ColorChoice> for ColorWhen {
+    fn from(c: ColorChoice) -> Self {
+        match c {
+            ColorChoice::Auto => ColorWhen::Auto,
+            ColorChoice::Always => ColorWhen::Always,
+            ColorChoice::Never => ColorWhen::Never,
+        }
+    }
+}
+
 #[cfg(feature = "color")]
 pub fn is_a_tty(stderr: bool) -> bool {
     debugln!("is_a_tty: stderr={:?}", stderr);
@@ -34,24 +77,119 @@ pub fn is_a_tty(_: bool) -> bool {
     false
 }
 
+#[cfg(feature = "color")]
+pub fn is_stdin_a_tty() -> bool {
+    debugln!("is_stdin_a_tty;");
+    atty::is(atty::Stream::Stdin)
+}
+
+// Without atty there's no way to tell, so assume a user is there to answer
+#[cfg(not(feature = "color"))]
+pub fn is_stdin_a_tty() -> bool {
+    debugln!("is_stdin_a_tty;");
+    true
+}
+
 pub fn is_term_dumb() -> bool { env::var("TERM").ok() == Some(String::from("dumb")) }
 
+/// A color used in place of one of the default colors of help and error messages, set with
+/// [`App::good_color`], [`App::warning_color`] and [`App::error_color`].
+///
+/// [`App::good_color`]: ./struct.App.html#method.good_color
+/// [`App::warning_color`]: ./struct.App.html#method.warning_color
+/// [`App::error_color`]: ./struct.App.html#method.error_color
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TermColor {
+    /// One of the 16 basic colors, `0..=7` are the normal and `8..=15` the bright variants
+    /// (values above that are treated like `Ansi256`)
+    Ansi16(u8),
+    /// A color from the 256 color palette
+    Ansi256(u8),
+    /// A truecolor red, green, blue triple
+    Rgb(u8, u8, u8),
+}
+
+#[cfg(all(feature = "color", not(target_os = "windows")))]
+impl TermColor {
+    fn colour(self) -> Colour {
+        match self {
+            TermColor::Ansi16(n) if n < 8 => {
+                [Black, Red, Green, Yellow, Blue, Purple, Cyan, White][n as usize]
+            }
+            TermColor::Ansi16(n) | TermColor::Ansi256(n) => Fixed(n),
+            TermColor::Rgb(r, g, b) => RGB(r, g, b),
+        }
+    }
+}
+
+// When to color an error message and the colors replacing the default ones, as set on the App
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorSpec {
+    pub when: ColorWhen,
+    pub good: Option<TermColor>,
+    pub warning: Option<TermColor>,
+    pub error: Option<TermColor>,
+}
+
+impl From<ColorWhen> for ColorSpec {
+    fn from(when: ColorWhen) -> Self {
+        ColorSpec {
+            when,
+            good: None,
+            warning: None,
+            error: None,
+        }
+    }
+}
+
 #[doc(hidden)]
 pub struct ColorizerOption {
     pub use_stderr: bool,
     pub when: ColorWhen,
+    pub good_color: Option<TermColor>,
+    pub warning_color: Option<TermColor>,
+    pub error_color: Option<TermColor>,
+}
+
+impl Default for ColorizerOption {
+    fn default() -> Self {
+        ColorizerOption {
+            use_stderr: true,
+            when: ColorWhen::Auto,
+            good_color: None,
+            warning_color: None,
+            error_color: None,
+        }
+    }
+}
+
+// Error messages go to stderr
+impl From<ColorSpec> for ColorizerOption {
+    fn from(spec: ColorSpec) -> Self {
+        ColorizerOption {
+            use_stderr: true,
+            when: spec.when,
+            good_color: spec.good,
+            warning_color: spec.warning,
+            error_color: spec.error,
+        }
+    }
 }
 
 #[doc(hidden)]
 pub struct Colorizer {
     when: ColorWhen,
+    good_color: Option<TermColor>,
+    warning_color: Option<TermColor>,
+    error_color: Option<TermColor>,
 }
 
 macro_rules! color {
-    ($_self:ident, $c:ident, $m:expr) => {
+    ($_self:ident, $c:ident, $m:expr $(, $extra:expr)*) => {
         match $_self.when {
-            ColorWhen::Auto => Format::$c($m),
-            ColorWhen::Always => Format::$c($m),
+            ColorWhen::Auto => Format::$c($m $(, $extra)*),
+            ColorWhen::Always => Format::$c($m $(, $extra)*),
             ColorWhen::Never => Format::None($m),
         }
     };
@@ -59,23 +197,33 @@ macro_rules! color {
 
 impl Colorizer {
     pub fn new(option: &ColorizerOption) -> Colorizer {
-        let is_a_tty = is_a_tty(option.use_stderr);
-        let is_term_dumb = is_term_dumb();
+        // Only `Auto` depends on where the output goes, `Always` colors even a String or a pipe
+        let when = match option.when {
+            ColorWhen::Auto if !is_a_tty(option.use_stderr) || is_term_dumb() => ColorWhen::Never,
+            when => when,
+        };
         Colorizer {
-            when: if is_a_tty && !is_term_dumb {
-                option.when
-            } else {
-                ColorWhen::Never
-            },
+            when,
+            good_color: option.good_color,
+            warning_color: option.warning_color,
+            error_color: option.error_color,
         }
     }
 
+    // Whether anything but plain text will be written, i.e. ANSI colors
+    pub fn is_enabled(&self) -> bool {
+        cfg!(all(feature = "color", not(target_os = "windows"))) && self.when != ColorWhen::Never
+    }
+
     pub fn good<T>(&self, msg: T) -> Format<T>
     where
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::good;");
-        color!(self, Good, msg)
+        match self.good_color {
+            Some(c) => color!(self, Custom, msg, c, false),
+            None => color!(self, Good, msg),
+        }
     }
 
     pub fn warning<T>(&self, msg: T) -> Format<T>
@@ -83,7 +231,10 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::warning;");
-        color!(self, Warning, msg)
+        match self.warning_color {
+            Some(c) => color!(self, Custom, msg, c, false),
+            None => color!(self, Warning, msg),
+        }
     }
 
     pub fn error<T>(&self, msg: T) -> Format<T>
@@ -91,7 +242,18 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::error;");
-        color!(self, Error, msg)
+        match self.error_color {
+            Some(c) => color!(self, Custom, msg, c, true),
+            None => color!(self, Error, msg),
+        }
+    }
+
+    pub fn hint<T>(&self, msg: T) -> Format<T>
+    where
+        T: fmt::Display + AsRef<str>,
+    {
+        debugln!("Colorizer::hint;");
+        color!(self, Hint, msg)
     }
 
     pub fn none<T>(&self, msg: T) -> Format<T>
@@ -105,15 +267,12 @@ impl Colorizer {
 
 impl Default for Colorizer {
     fn default() -> Self {
-        Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: ColorWhen::Auto,
-        })
+        Colorizer::new(&ColorizerOption::default())
     }
 }
 
 /// Defines styles for different types of error messages. Defaults to Error=Red, Warning=Yellow,
-/// and Good=Green
+/// Good=Green, and Hint=Cyan
 #[derive(Debug)]
 #[doc(hidden)]
 pub enum Format<T> {
@@ -123,6 +282,10 @@ pub enum Format<T> {
     Warning(T),
     /// Defines the style used for good values, defaults to Green
     Good(T),
+    /// Defines the style used for supplementary values such as defaults, defaults to Cyan
+    Hint(T),
+    /// Defines a style with a color given by the user, and whether it's bold
+    Custom(T, TermColor, bool),
     /// Defines no formatting style
     None(T),
 }
@@ -134,6 +297,9 @@ impl<T: AsRef<str>> Format<T> {
             Format::Error(ref e) => Red.bold().paint(e.as_ref()),
             Format::Warning(ref e) => Yellow.paint(e.as_ref()),
             Format::Good(ref e) => Green.paint(e.as_ref()),
+            Format::Hint(ref e) => Cyan.paint(e.as_ref()),
+            Format::Custom(ref e, c, true) => c.colour().bold().paint(e.as_ref()),
+            Format::Custom(ref e, c, false) => c.colour().paint(e.as_ref()),
             Format::None(ref e) => ANSIString::from(e.as_ref()),
         }
     }
@@ -147,6 +313,8 @@ impl<T: fmt::Display> Format<T> {
             Format::Error(ref e) => e,
             Format::Warning(ref e) => e,
             Format::Good(ref e) => e,
+            Format::Hint(ref e) => e,
+            Format::Custom(ref e, _, _) => e,
             Format::None(ref e) => e,
         }
     }
@@ -164,9 +332,9 @@ impl<T: fmt::Display> fmt::Display for Format<T> {
 
 #[cfg(all(test, feature = "color", not(target_os = "windows")))]
 mod test {
-    use super::Format;
+    use super::{ColorWhen, Colorizer, ColorizerOption, Format, TermColor};
     use ansi_term::ANSIString;
-    use ansi_term::Colour::{Green, Red, Yellow};
+    use ansi_term::Colour::{Blue, Cyan, Fixed, Green, Red, Yellow, RGB};
 
     #[test]
     fn colored_output() {
@@ -179,10 +347,69 @@ mod test {
         assert_eq!(&*format!("{}", good), &*format!("{}", Green.paint("good")));
         let warn = Format::Warning("warn");
         assert_eq!(&*format!("{}", warn), &*format!("{}", Yellow.paint("warn")));
+        let hint = Format::Hint("hint");
+        assert_eq!(&*format!("{}", hint), &*format!("{}", Cyan.paint("hint")));
+        let custom = Format::Custom("custom", TermColor::Rgb(1, 2, 3), false);
+        assert_eq!(
+            &*format!("{}", custom),
+            &*format!("{}", RGB(1, 2, 3).paint("custom"))
+        );
+        let custom = Format::Custom("custom", TermColor::Ansi256(208), true);
+        assert_eq!(
+            &*format!("{}", custom),
+            &*format!("{}", Fixed(208).bold().paint("custom"))
+        );
+        let custom = Format::Custom("custom", TermColor::Ansi16(4), false);
+        assert_eq!(&*format!("{}", custom), &*format!("{}", Blue.paint("custom")));
         let none = Format::None("none");
         assert_eq!(
             &*format!("{}", none),
             &*format!("{}", ANSIString::from("none"))
         );
     }
+
+    #[test]
+    fn colorizer_overrides() {
+        let c = Colorizer {
+            when: ColorWhen::Always,
+            good_color: Some(TermColor::Rgb(10, 20, 30)),
+            warning_color: None,
+            error_color: Some(TermColor::Ansi256(160)),
+        };
+        assert_eq!(
+            &*format!("{}", c.good("good")),
+            &*format!("{}", RGB(10, 20, 30).paint("good"))
+        );
+        assert_eq!(
+            &*format!("{}", c.warning("warn")),
+            &*format!("{}", Yellow.paint("warn"))
+        );
+        assert_eq!(
+            &*format!("{}", c.error("error")),
+            &*format!("{}", Fixed(160).bold().paint("error"))
+        );
+    }
+
+    #[test]
+    fn colorizer_always_without_tty() {
+        // Whether or not the tests write to a terminal
+        let c = Colorizer::new(&ColorizerOption {
+            when: ColorWhen::Always,
+            ..Default::default()
+        });
+        assert_eq!(
+            &*format!("{}", c.good("good")),
+            &*format!("{}", Green.paint("good"))
+        );
+    }
+
+    #[test]
+    fn colorizer_overrides_never() {
+        let c = Colorizer::new(&ColorizerOption {
+            when: ColorWhen::Never,
+            good_color: Some(TermColor::Rgb(10, 20, 30)),
+            ..Default::default()
+        });
+        assert_eq!(&*format!("{}", c.good("good")), "good");
+    }
 }
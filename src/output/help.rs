@@ -2,16 +2,19 @@
 use std::borrow::Cow;
 use std::cmp;
 use std::collections::BTreeMap;
+#[cfg(feature = "wrap_help")]
+use std::env;
 use std::io::{self, Cursor, Read, Write};
+use std::str;
 use std::usize;
 
 // Internal
 use crate::build::{App, AppSettings, Arg, ArgSettings};
-use crate::output::fmt::{Colorizer, ColorizerOption, Format};
+use crate::output::fmt::{ColorWhen, Colorizer, ColorizerOption, Format};
 use crate::output::Usage;
-use crate::parse::errors::{Error, Result as ClapResult};
+use crate::parse::errors::{Error, ErrorKind, Result as ClapResult};
 use crate::parse::Parser;
-use crate::util::VecMap;
+use crate::util::{VecMap, HELP_HASH};
 use crate::INTERNAL_ERROR_MSG;
 
 // Third Party
@@ -20,65 +23,208 @@ use term_size;
 use textwrap;
 use unicode_width::UnicodeWidthStr;
 
-#[cfg(not(feature = "wrap_help"))]
-mod term_size {
-    pub fn dimensions() -> Option<(usize, usize)> { None }
+// The width of the terminal, from the `COLUMNS` environment variable when it's set to a positive
+// number since detection fails in some environments, otherwise asking the terminal itself
+#[cfg(feature = "wrap_help")]
+fn detect_term_width() -> Option<usize> {
+    term_width_from(env::var("COLUMNS").ok().as_ref().map(|c| &**c))
+}
+
+// `columns` is the value of `COLUMNS`, if it's set
+#[cfg(feature = "wrap_help")]
+fn term_width_from(columns: Option<&str>) -> Option<usize> {
+    columns
+        .and_then(|c| c.trim().parse::<usize>().ok())
+        .filter(|&w| w > 0)
+        .or_else(|| term_size::dimensions().map(|(w, _)| w))
 }
 
+#[cfg(not(feature = "wrap_help"))]
+fn detect_term_width() -> Option<usize> { None }
+
+type Id = u64;
+
 fn str_width(s: &str) -> usize { UnicodeWidthStr::width(s) }
 
-const TAB: &str = "    ";
+// The width of `s` as displayed, not counting ANSI escape sequences such as colors
+fn display_width(s: &str) -> usize {
+    if s.contains('\x1b') {
+        str_width(&*strip_ansi(s).0)
+    } else {
+        str_width(s)
+    }
+}
+
+/// Returns the width of an arg as written in the help message, not counting the short of an arg
+/// that also has a long (i.e. `--config <FILE>`). That's exactly how the arg's `Display` writes
+/// it, including the `[=` and `]` around the values of an arg with a default missing value.
+fn arg_width(arg: &Arg) -> usize { display_width(&*arg.to_string()) }
+
+/// A structured model of an [`App`]'s help message, for tools which display the help in their own
+/// way rather than as text. See [`App::generate_help_tree`].
+///
+/// [`App`]: ./struct.App.html
+/// [`App::generate_help_tree`]: ./struct.App.html#method.generate_help_tree
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HelpTree<'b> {
+    /// The name of the app
+    pub name: String,
+    /// The name of the binary, if it was set or known from parsing
+    pub bin_name: Option<String>,
+    /// The version of the app
+    pub version: Option<&'b str>,
+    /// The author(s) of the app
+    pub author: Option<&'b str>,
+    /// The about text of the app, the long variant for the long help
+    pub about: Option<&'b str>,
+    /// The usage string, without the `USAGE:` title
+    pub usage: String,
+    /// The args sections in the order they're written, i.e. `ARGS`, `FLAGS` and `OPTIONS`
+    pub sections: Vec<HelpSection<'b>>,
+    /// The visible subcommands in the order they're written
+    pub subcommands: Vec<HelpEntry<'b>>,
+}
+
+/// A titled section of a [`HelpTree`]
+///
+/// [`HelpTree`]: ./struct.HelpTree.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HelpSection<'b> {
+    /// The title of the section without the trailing colon, i.e. `FLAGS`
+    pub title: String,
+    /// The entries of the section in the order they're written
+    pub entries: Vec<HelpEntry<'b>>,
+}
+
+/// A single entry of a [`HelpTree`], i.e. an arg or a subcommand
+///
+/// [`HelpTree`]: ./struct.HelpTree.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HelpEntry<'b> {
+    /// The entry as written in the help message, i.e. `-c, --config <FILE>` or a subcommand name
+    pub name: String,
+    /// The help text, the long variant for the long help
+    pub help: Option<&'b str>,
+    /// The special values written after the help text, i.e. `[default: auto]`, or empty
+    pub spec_vals: String,
+}
+
+const TAB_WIDTH: usize = 4;
+const MIN_TERM_WIDTH: usize = 20;
+const MAX_PARTIAL_DEPTH: usize = 8;
+
+// Keeps what was written since the last newline, so templates know which column a tag starts at
+struct ColumnWriter<'w> {
+    inner: &'w mut dyn Write,
+    line: Vec<u8>,
+}
+
+impl<'w> ColumnWriter<'w> {
+    fn new(inner: &'w mut dyn Write) -> Self { ColumnWriter { inner, line: vec![] } }
+
+    fn column(&self) -> usize { display_width(&*String::from_utf8_lossy(&*self.line)) }
+}
+
+impl<'w> Write for ColumnWriter<'w> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        match buf[..n].iter().rposition(|&b| b == b'\n') {
+            Some(i) => {
+                self.line.clear();
+                self.line.extend_from_slice(&buf[i + 1..n]);
+            }
+            None => self.line.extend_from_slice(&buf[..n]),
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
 
 /// `clap` Help Writer.
 ///
 /// Wraps a writer stream providing different methods to generate help for `clap` objects.
 pub struct Help<'b, 'c, 'd, 'w> {
-    writer: &'w mut dyn Write,
+    writer: ColumnWriter<'w>,
     parser: &'d Parser<'b, 'c>,
     next_line_help: bool,
     hide_pv: bool,
     term_w: usize,
+    break_words: bool,
+    tab: String,
+    gutter: Option<usize>,
+    help_col: usize,
     color: bool,
+    hyperlinks: bool,
     cizer: Colorizer,
     longest: usize,
     force_next_line: bool,
     use_long: bool,
+    partial_depth: usize,
 }
 
 // Public Functions
 impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
-    /// Create a new `Help` instance.
+    /// Create a new `Help` instance. `stderr` says where the caller will end up writing the
+    /// message, which is only used to decide whether color is possible; it's `true` only when help
+    /// is shown as part of an error.
     pub fn new(w: &'w mut dyn Write, parser: &'d Parser<'b, 'c>, use_long: bool, stderr: bool) -> Self {
         debugln!("Help::new;");
         let term_w = match parser.app.term_w {
             Some(0) => usize::MAX,
             Some(w) => w,
             None => cmp::min(
-                term_size::dimensions().map_or(120, |(w, _)| w),
+                detect_term_width().unwrap_or(120),
                 match parser.app.max_w {
                     None | Some(0) => usize::MAX,
                     Some(mw) => mw,
                 },
             ),
         };
+        // Wrapping at only a few columns is unreadable, but no wrapping at all is fine
+        let term_w = if term_w == usize::MAX {
+            term_w
+        } else {
+            cmp::max(term_w, parser.app.min_w.unwrap_or(MIN_TERM_WIDTH))
+        };
+        let tab = " ".repeat(parser.app.tab_w.unwrap_or(TAB_WIDTH));
+        let break_words = parser.is_set(AppSettings::BreakLongWords);
         let nlh = parser.is_set(AppSettings::NextLineHelp);
         let hide_pv = parser.is_set(AppSettings::HidePossibleValuesInHelp);
-        let color = parser.is_set(AppSettings::ColoredHelp);
+        // DisableColoredHelp wins over every other color setting and terminal detection
+        let no_color = parser.is_set(AppSettings::DisableColoredHelp);
+        let color = parser.app.colored_help() && !no_color;
         let cizer = Colorizer::new(&ColorizerOption {
             use_stderr: stderr,
-            when: parser.app.color(),
+            when: if no_color {
+                ColorWhen::Never
+            } else {
+                parser.app.color_when()
+            },
+            good_color: parser.app.good_color,
+            warning_color: parser.app.warning_color,
+            error_color: parser.app.error_color,
         });
+        // Links are escape sequences too, so they're only written along with the colors
+        let hyperlinks =
+            color && parser.is_set(AppSettings::HelpHyperlinks) && cizer.is_enabled();
         Help {
-            writer: w,
+            writer: ColumnWriter::new(w),
             parser,
             next_line_help: nlh,
             hide_pv,
             term_w,
+            break_words,
+            tab,
+            gutter: parser.app.gutter_w,
+            help_col: 0,
             color,
+            hyperlinks,
             longest: 0,
             force_next_line: false,
             cizer,
             use_long,
+            partial_depth: 0,
         }
     }
 
@@ -97,6 +243,102 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
 
         Ok(())
     }
+
+    /// Builds the structured model of the parser help instead of writing it, without any colors.
+    pub fn help_tree(&mut self) -> HelpTree<'b> {
+        debugln!("Help::help_tree;");
+        self.color = false;
+        self.hyperlinks = false;
+        let parser = self.parser;
+        let app: &'d App<'b> = &*parser.app;
+        let use_long = self.use_long;
+
+        let mut sections = vec![];
+        for section in self.arg_sections() {
+            let groups = self.combined_groups(&*section.args);
+            let entries = self
+                .section_entries(&*section.args, &*groups, section.sorted)
+                .into_iter()
+                .map(|entry| match entry {
+                    SectionEntry::Arg(arg) => HelpEntry {
+                        name: arg_label(arg),
+                        help: if use_long {
+                            arg.long_help.or(arg.help)
+                        } else {
+                            arg.help.or(arg.long_help)
+                        },
+                        spec_vals: self.spec_vals(arg).trim_start().to_owned(),
+                    },
+                    SectionEntry::Group(i) => HelpEntry {
+                        name: groups[i].label.clone(),
+                        help: Some(groups[i].help),
+                        spec_vals: String::new(),
+                    },
+                })
+                .collect();
+            sections.push(HelpSection {
+                title: section.title,
+                entries,
+            });
+        }
+
+        let subcommands = ordered_subcommands(app, false)
+            .into_iter()
+            .map(|sc| HelpEntry {
+                name: sc.name.clone(),
+                help: if use_long {
+                    sc.long_about.or(sc.about)
+                } else {
+                    sc.about.or(sc.long_about)
+                },
+                spec_vals: self.sc_spec_vals(sc).trim_start().to_owned(),
+            })
+            .collect();
+
+        HelpTree {
+            name: app.name.clone(),
+            bin_name: app.bin_name.clone(),
+            version: app.version,
+            author: app.author,
+            about: if use_long {
+                app.long_about.or(app.about)
+            } else {
+                app.about.or(app.long_about)
+            },
+            usage: Usage::new(parser).create_usage_no_title(&[]),
+            sections,
+            subcommands,
+        }
+    }
+
+    /// Writes a single section of the parser help to the wrapped stream, one of `flags`,
+    /// `options`, `positionals`, `subcommands` or `usage`. Any other section writes the full help.
+    pub fn write_help_section(&mut self, section: &str) -> ClapResult<()> {
+        debugln!("Help::write_help_section: section={}", section);
+        match section {
+            "flags" => self.write_args(&*flags!(self.parser.app).collect::<Vec<_>>())?,
+            "options" => self.write_args(&*opts!(self.parser.app).collect::<Vec<_>>())?,
+            "positionals" => {
+                let pos_v = positionals!(self.parser.app).collect::<Vec<_>>();
+                if self.parser.is_set(AppSettings::SortPositionals) {
+                    self.write_args(&*pos_v)?;
+                } else {
+                    self.write_args_unsorted(&*pos_v)?;
+                }
+            }
+            "subcommands" => self.write_subcommands(self.parser.app)?,
+            "usage" => write!(
+                self.writer,
+                "{}",
+                Usage::new(self.parser).create_usage_no_title(&[])
+            )?,
+            _ => return self.write_help(),
+        }
+
+        writeln!(self.writer)?;
+
+        Ok(())
+    }
 }
 
 // Methods to write Arg help.
@@ -131,65 +373,158 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     /// Writes help for each argument in the order they were declared to the wrapped stream.
     fn write_args_unsorted(&mut self, args: &[&Arg<'b>]) -> io::Result<()> {
         debugln!("Help::write_args_unsorted;");
+        self.write_section(args, false)
+    }
+
+    /// Sorts arguments by length and display order and write their help to the wrapped stream.
+    fn write_args(&mut self, args: &[&Arg<'b>]) -> io::Result<()> {
+        debugln!("Help::write_args;");
+        self.write_section(args, true)
+    }
+
+    /// Writes the entries of an args section, see `Help::section_entries` for their order.
+    fn write_section(&mut self, args: &[&Arg<'b>], sorted: bool) -> io::Result<()> {
+        let groups = self.combined_groups(args);
+        let entries = self.section_entries(args, &*groups, sorted);
         // The shortest an arg can legally be is 2 (i.e. '-x')
         self.longest = 2;
-        let mut arg_v = Vec::with_capacity(10);
-        let use_long = self.use_long;
-        for arg in args.iter().filter(|arg| should_show_arg(use_long, *arg)) {
-            if arg.longest_filter() {
-                self.longest = cmp::max(self.longest, str_width(arg.to_string().as_str()));
+        for g in &groups {
+            self.longest = cmp::max(self.longest, str_width(&*g.label));
+        }
+        for entry in &entries {
+            // If it's NextLineHelp we don't care to compute how long it is because it may be
+            // NextLineHelp on purpose simply *because* it's so long and would throw off all other
+            // args alignment
+            if let SectionEntry::Arg(arg) = *entry {
+                if arg.longest_filter() {
+                    debugln!("Help::write_section: Current Longest...{}", self.longest);
+                    self.longest = cmp::max(self.longest, arg_width(arg));
+                    debugln!("Help::write_section: New Longest...{}", self.longest);
+                }
             }
-            arg_v.push(arg)
         }
         let mut first = true;
-        let arg_c = arg_v.len();
-        for (i, arg) in arg_v.iter().enumerate() {
+        for entry in &entries {
             if first {
                 first = false;
             } else {
                 self.writer.write_all(b"\n")?;
             }
-            self.write_arg(arg, i < arg_c)?;
+            match *entry {
+                SectionEntry::Arg(arg) => self.write_arg(arg, !sorted)?,
+                SectionEntry::Group(g) => self.write_group(&groups[g], !sorted)?,
+            }
         }
         Ok(())
     }
 
-    /// Sorts arguments by length and display order and write their help to the wrapped stream.
-    fn write_args(&mut self, args: &[&Arg<'b>]) -> io::Result<()> {
-        debugln!("Help::write_args;");
-        // The shortest an arg can legally be is 2 (i.e. '-x')
-        self.longest = 2;
-        let mut ord_m = VecMap::new();
+    /// Orders the shown entries of an args section, either in the order they were declared or
+    /// by display order and then name. A combined group takes the place of its first shown arg.
+    fn section_entries<'a>(
+        &self,
+        args: &[&'a Arg<'b>],
+        groups: &[CombinedGroup<'b>],
+        sorted: bool,
+    ) -> Vec<SectionEntry<'a, 'b>> {
         let use_long = self.use_long;
-        // Determine the longest
-        for arg in args.iter().filter(|arg| {
-            // If it's NextLineHelp we don't care to compute how long it is because it may be
-            // NextLineHelp on purpose simply *because* it's so long and would throw off all other
-            // args alignment
-            should_show_arg(use_long, *arg)
-        }) {
-            if arg.longest_filter() {
-                debugln!("Help::write_args: Current Longest...{}", self.longest);
-                self.longest = cmp::max(self.longest, str_width(arg.to_string().as_str()));
-                debugln!("Help::write_args: New Longest...{}", self.longest);
+        let mut placed = vec![false; groups.len()];
+        let mut entries = vec![];
+        let mut ord_m = VecMap::new();
+        for arg in args.iter().filter(|arg| should_show_arg(use_long, *arg)) {
+            let entry = match groups.iter().position(|g| g.args.contains(&arg.id)) {
+                Some(i) if placed[i] => continue,
+                Some(i) => {
+                    placed[i] = true;
+                    SectionEntry::Group(i)
+                }
+                None => SectionEntry::Arg(*arg),
+            };
+            if sorted {
+                // We use name here for alphabetic sorting, a group is sorted by its own name
+                let name = match entry {
+                    SectionEntry::Arg(a) => a.name,
+                    SectionEntry::Group(i) => groups[i].name,
+                };
+                let btm = ord_m.entry(arg.disp_ord).or_insert(BTreeMap::new());
+                btm.insert(name, entry);
+            } else {
+                entries.push(entry);
             }
-            let btm = ord_m.entry(arg.disp_ord).or_insert(BTreeMap::new());
-            // We use name here for alphabetic sorting
-            // @TODO @maybe perhaps we could do some sort of ordering off of keys?
-            btm.insert(arg.name, arg);
         }
-        let mut first = true;
-        for btm in ord_m.values() {
-            for arg in btm.values() {
-                if first {
-                    first = false;
-                } else {
-                    self.writer.write_all(b"\n")?;
-                }
-                self.write_arg(arg, false)?;
+        if sorted {
+            for btm in ord_m.values() {
+                entries.extend(btm.values().cloned());
             }
         }
-        Ok(())
+        entries
+    }
+
+    /// Finds the groups with their own help among `args`, whose shown args are written as a
+    /// single entry instead of one each.
+    fn combined_groups(&self, args: &[&Arg<'b>]) -> Vec<CombinedGroup<'b>> {
+        let use_long = self.use_long;
+        let mut groups = vec![];
+        for g in self.parser.app.groups.iter() {
+            let help = match g.help {
+                Some(h) => h,
+                None => continue,
+            };
+            let members: Vec<&Arg> = g
+                .args
+                .iter()
+                .filter_map(|id| args.iter().find(|a| a.id == *id))
+                .filter(|a| should_show_arg(use_long, a))
+                .cloned()
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            groups.push(CombinedGroup {
+                name: g.name,
+                label: members
+                    .iter()
+                    .map(|a| group_member_label(a))
+                    .collect::<Vec<_>>()
+                    .join("|"),
+                help,
+                has_switch: members.iter().any(|a| a.has_switch()),
+                args: members.iter().map(|a| a.id).collect(),
+            });
+        }
+        groups
+    }
+
+    /// Writes a group's args as a single entry, i.e. `--json|--yaml|--toml`, followed by the
+    /// group's help.
+    fn write_group(&mut self, g: &CombinedGroup, prevent_nlh: bool) -> io::Result<()> {
+        debugln!("Help::write_group: group={}", g.name);
+        write!(self.writer, "{}", self.tab)?;
+        if g.has_switch {
+            // Line up with the longs of args that have no short
+            write!(self.writer, "    ")?;
+        }
+        self.color(Format::Good(&*g.label))?;
+
+        let nlh = self.next_line_help;
+        let self_len = str_width(&*g.label);
+        let taken = if let Some(gutter) = self.gutter {
+            let pad = if g.has_switch { 4 } else { 0 };
+            self.tab.len() + pad + self_len + gutter
+        } else {
+            self.longest + self.tab.len() * 2 + 4
+        };
+        self.help_col = taken;
+        self.force_next_line = !nlh && self.should_force_next_line(taken, str_width(g.help));
+        if !(nlh || self.force_next_line) {
+            let spcs = match self.gutter {
+                Some(gutter) => gutter,
+                None if g.has_switch => self.longest - self_len + self.tab.len(),
+                None => self.longest + self.tab.len() - self_len,
+            };
+            write_nspaces!(self.writer, spcs);
+        }
+        let nlh = nlh || self.use_long;
+        self.help_text(g.help, "", nlh, g.has_switch, prevent_nlh, true)
     }
 
     /// Writes help for an argument to the wrapped stream.
@@ -205,11 +540,12 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     /// Writes argument's short command to the wrapped stream.
     fn short(&mut self, arg: &Arg<'c>) -> io::Result<()> {
         debugln!("Help::short;");
-        write!(self.writer, "{}", TAB)?;
+        write!(self.writer, "{}", self.tab)?;
         if let Some(s) = arg.short {
             self.color(Format::Good(&*format!("-{}", s)))
         } else if arg.has_switch() {
-            write!(self.writer, "{}", TAB)
+            // Pad out the missing "-x, " so longs still line up
+            write!(self.writer, "    ")
         } else {
             Ok(())
         }
@@ -229,7 +565,9 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 self.color(Format::Good(&*format!("--{}", l)))?
             }
 
-            let sep = if arg.is_set(ArgSettings::RequireEquals) {
+            let sep = if arg.default_missing_vals.is_some() {
+                "[="
+            } else if arg.is_set(ArgSettings::RequireEquals) {
                 "="
             } else {
                 " "
@@ -247,56 +585,40 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     /// Writes argument's possible values to the wrapped stream.
     fn val(&mut self, arg: &Arg<'c>) -> Result<String, io::Error> {
         debugln!("Help::val: arg={}", arg.name);
-        let mult =
-            arg.is_set(ArgSettings::MultipleValues) || arg.is_set(ArgSettings::MultipleOccurrences);
-        if arg.is_set(ArgSettings::TakesValue) || arg.index.is_some() {
-            let delim = if arg.is_set(ArgSettings::RequireDelimiter) {
-                arg.val_delim.expect(INTERNAL_ERROR_MSG)
-            } else {
-                ' '
-            };
-            if let Some(ref vec) = arg.val_names {
-                let mut it = vec.iter().peekable();
-                while let Some((_, val)) = it.next() {
-                    self.color(Format::Good(&*format!("<{}>", val)))?;
-                    if it.peek().is_some() {
-                        write!(self.writer, "{}", delim)?;
-                    }
-                }
-                let num = vec.len();
-                if mult && num == 1 {
-                    self.color(Format::Good("..."))?;
-                }
-            } else if let Some(num) = arg.num_vals {
-                let mut it = (0..num).peekable();
-                while let Some(_) = it.next() {
-                    self.color(Format::Good(&*format!("<{}>", arg.name)))?;
-                    if it.peek().is_some() {
-                        write!(self.writer, "{}", delim)?;
-                    }
-                }
-                if mult && num == 1 {
-                    self.color(Format::Good("..."))?;
-                }
-            } else if arg.has_switch() {
-                self.color(Format::Good(&*format!("<{}>", arg.name)))?;
-                if mult {
-                    self.color(Format::Good("..."))?;
-                }
-            } else {
-                self.color(Format::Good(&*arg.to_string()))?;
+        let (vals, ellipsis) = arg.val_placeholders();
+        let delim = if !vals.is_empty() && arg.is_set(ArgSettings::RequireDelimiter) {
+            arg.val_delim.expect(INTERNAL_ERROR_MSG)
+        } else {
+            ' '
+        };
+        let mut it = vals.iter().peekable();
+        while let Some(val) = it.next() {
+            self.color(Format::Good(val.as_str()))?;
+            if it.peek().is_some() {
+                write!(self.writer, "{}", delim)?;
             }
         }
+        if ellipsis {
+            self.color(Format::Good("..."))?;
+        }
+        if arg.has_switch() && arg.default_missing_vals.is_some() {
+            write!(self.writer, "]")?;
+        }
 
         let spec_vals = self.spec_vals(arg);
         let h = arg.help.unwrap_or("");
-        let h_w = str_width(h) + str_width(&*spec_vals);
+        let h_w = display_width(h) + display_width(&*spec_vals);
         let nlh = self.next_line_help || arg.is_set(ArgSettings::NextLineHelp);
-        let taken = self.longest + 12;
-        self.force_next_line = !nlh
-            && self.term_w >= taken
-            && (taken as f32 / self.term_w as f32) > 0.40
-            && h_w > (self.term_w - taken);
+        let self_len = arg_width(arg);
+        let taken = if let Some(g) = self.gutter {
+            // Account for the '-x, ' (or its padding) that precedes a long
+            let pad = if arg.long.is_some() { 4 } else { 0 };
+            self.tab.len() + pad + self_len + g
+        } else {
+            self.longest + self.tab.len() * 2 + 4
+        };
+        self.help_col = taken;
+        self.force_next_line = !nlh && self.should_force_next_line(taken, h_w);
 
         debug!("Help::val: Has switch...");
         if arg.has_switch() {
@@ -314,18 +636,18 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
             debug!("Help::val: next_line...");
             if !(nlh || self.force_next_line) {
                 sdebugln!("No");
-                let self_len = str_width(arg.to_string().as_str());
-                // subtract ourself
-                let mut spcs = self.longest - self_len;
-                // Since we're writing spaces from the tab point we first need to know if we
-                // had a long and short, or just short
-                if arg.long.is_some() {
-                    // Only account 4 after the val
-                    spcs += 4;
+                let spcs = if let Some(g) = self.gutter {
+                    g
+                } else if arg.long.is_some() {
+                    // subtract ourself
+                    // Since we're writing spaces from the tab point we first need to know if we
+                    // had a long and short, or just short
+                    // Only account for a tab after the val
+                    self.longest - self_len + self.tab.len()
                 } else {
-                    // Only account for ', --' + 4 after the val
-                    spcs += 8;
-                }
+                    // Only account for ', --' + a tab after the val
+                    self.longest - self_len + self.tab.len() + 4
+                };
 
                 write_nspaces!(self.writer, spcs);
             } else {
@@ -333,16 +655,28 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
             }
         } else if !(nlh || self.force_next_line) {
             sdebugln!("No, and not next_line");
-            write_nspaces!(
-                self.writer,
-                self.longest + 4 - (str_width(arg.to_string().as_str()))
-            );
+            let spcs = match self.gutter {
+                Some(g) => g,
+                None => self.longest + self.tab.len() - self_len,
+            };
+            write_nspaces!(self.writer, spcs);
         } else {
             sdebugln!("No");
         }
         Ok(spec_vals)
     }
 
+    /// Decides whether help starting at column `taken` should move to its own line instead.
+    fn should_force_next_line(&self, taken: usize, h_w: usize) -> bool {
+        if self.gutter.is_some() && taken >= self.term_w {
+            // A fixed gutter can push the help past the edge no matter how short it is
+            return h_w > 0;
+        }
+        self.term_w >= taken
+            && (taken as f32 / self.term_w as f32) > 0.40
+            && h_w > (self.term_w - taken)
+    }
+
     fn write_before_after_help(&mut self, h: &str) -> io::Result<()> {
         debugln!("Help::write_before_after_help;");
         let mut help = String::from(h);
@@ -366,14 +700,34 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 "Help::write_before_after_help: Usable space: {}",
                 self.term_w
             );
-            help = wrap_help(&help.replace("{n}", "\n"), self.term_w);
+            help = wrap_help_keep_indent(
+                &help.replace("{n}", "\n"),
+                self.term_w,
+                self.break_words,
+            );
         } else {
             sdebugln!("No");
         }
+        let help = self.link_urls(h, help);
         write!(self.writer, "{}", help)?;
         Ok(())
     }
 
+    // Links the URLs of `orig` (the text before wrapping) in the wrapped `help`. This is done last
+    // so the escape sequences never count towards any width, and a URL split by
+    // `AppSettings::BreakLongWords` is left alone.
+    fn link_urls(&self, orig: &str, help: String) -> String {
+        if !self.hyperlinks {
+            return help;
+        }
+        let orig = orig.replace("{n}", "\n");
+        let urls = url_spans(&*orig)
+            .into_iter()
+            .map(|(start, end)| &orig[start..end])
+            .collect::<Vec<_>>();
+        hyperlink_urls(&*help, &*urls)
+    }
+
     /// Writes argument's help to the wrapped stream.
     fn help(&mut self, arg: &Arg<'c>, spec_vals: &str, prevent_nlh: bool) -> io::Result<()> {
         debugln!("Help::help;");
@@ -382,46 +736,68 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
         } else {
             arg.help.unwrap_or_else(|| arg.long_help.unwrap_or(""))
         };
-        let mut help = String::from(h) + spec_vals;
         let nlh = self.next_line_help || arg.is_set(ArgSettings::NextLineHelp) || self.use_long;
-        debugln!("Help::help: Next Line...{:?}", nlh);
+        let wrap = !arg.is_set(ArgSettings::NoWrap);
+        self.help_text(h, spec_vals, nlh, arg.has_switch(), prevent_nlh, wrap)
+    }
+
+    /// Writes the help text of an entry (an arg or a combined group) to the wrapped stream.
+    fn help_text(
+        &mut self,
+        h: &str,
+        spec_vals: &str,
+        nlh: bool,
+        has_switch: bool,
+        prevent_nlh: bool,
+        wrap: bool,
+    ) -> io::Result<()> {
+        let mut help = String::from(h) + spec_vals;
+        debugln!("Help::help_text: Next Line...{:?}", nlh);
 
         let spcs = if nlh || self.force_next_line {
-            12 // "tab" * 3
+            self.tab.len() * 3
+        } else if self.gutter.is_some() {
+            self.help_col
         } else {
-            self.longest + 12
+            self.longest + self.tab.len() * 2 + 4
         };
 
-        let too_long = spcs + str_width(h) + str_width(&*spec_vals) >= self.term_w;
+        let too_long = spcs + display_width(h) + display_width(&*spec_vals) >= self.term_w;
 
         // Is help on next line, if so then indent
         if nlh || self.force_next_line {
-            write!(self.writer, "\n{}{}{}", TAB, TAB, TAB)?;
+            write!(self.writer, "\n{}{}{}", self.tab, self.tab, self.tab)?;
         }
 
-        debug!("Help::help: Too long...");
-        if too_long && spcs <= self.term_w || h.contains("{n}") {
+        debug!("Help::help_text: Too long...");
+        if !wrap {
+            sdebugln!("Not wrapping");
+            help = help.replace("{n}", "\n");
+        } else if too_long && spcs <= self.term_w || h.contains("{n}") {
             sdebugln!("Yes");
-            debugln!("Help::help: help...{}", help);
-            debugln!("Help::help: help width...{}", str_width(&*help));
+            debugln!("Help::help_text: help...{}", help);
+            debugln!("Help::help_text: help width...{}", str_width(&*help));
             // Determine how many newlines we need to insert
             let avail_chars = self.term_w - spcs;
-            debugln!("Help::help: Usable space...{}", avail_chars);
-            help = wrap_help(&help.replace("{n}", "\n"), avail_chars);
+            debugln!("Help::help_text: Usable space...{}", avail_chars);
+            help = wrap_help(&help.replace("{n}", "\n"), avail_chars, self.break_words);
         } else {
             sdebugln!("No");
         }
+        let help = self.link_urls(&*(String::from(h) + spec_vals), help);
         if let Some(part) = help.lines().next() {
             write!(self.writer, "{}", part)?;
         }
         for part in help.lines().skip(1) {
             writeln!(self.writer)?;
             if nlh || self.force_next_line {
-                write!(self.writer, "{}{}{}", TAB, TAB, TAB)?;
-            } else if arg.has_switch() {
-                write_nspaces!(self.writer, self.longest + 12);
+                write!(self.writer, "{}{}{}", self.tab, self.tab, self.tab)?;
+            } else if self.gutter.is_some() {
+                write_nspaces!(self.writer, self.help_col);
+            } else if has_switch {
+                write_nspaces!(self.writer, self.longest + self.tab.len() * 2 + 4);
             } else {
-                write_nspaces!(self.writer, self.longest + 8);
+                write_nspaces!(self.writer, self.longest + self.tab.len() * 2);
             }
             write!(self.writer, "{}", part)?;
         }
@@ -434,24 +810,26 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     fn spec_vals(&self, a: &Arg) -> String {
         debugln!("Help::spec_vals: a={}", a);
         let mut spec_vals = vec![];
-        if let Some(ref env) = a.env {
-            debugln!(
-                "Help::spec_vals: Found environment variable...[{:?}:{:?}]",
-                env.0,
-                env.1
-            );
-            let env_val = if !a.is_set(ArgSettings::HideEnvValues) {
-                format!(
-                    "={}",
+        if !a.is_set(ArgSettings::HideEnv) {
+            if let Some(ref env) = a.env {
+                debugln!(
+                    "Help::spec_vals: Found environment variable...[{:?}:{:?}]",
+                    env.0,
                     env.1
-                        .as_ref()
-                        .map_or(Cow::Borrowed(""), |val| val.to_string_lossy())
-                )
-            } else {
-                String::new()
-            };
-            let env_info = format!(" [env: {}{}]", env.0.to_string_lossy(), env_val);
-            spec_vals.push(env_info);
+                );
+                let env_val = if !a.is_set(ArgSettings::HideEnvValues) {
+                    format!(
+                        "={}",
+                        env.1
+                            .as_ref()
+                            .map_or(Cow::Borrowed(""), |val| val.to_string_lossy())
+                    )
+                } else {
+                    String::new()
+                };
+                let env_info = format!(" [env: {}{}]", env.0.to_string_lossy(), env_val);
+                spec_vals.push(env_info);
+            }
         }
         if !a.is_set(ArgSettings::HideDefaultValue) {
             if let Some(ref pv) = a.default_vals {
@@ -460,7 +838,7 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 let pvs = if self.color {
                     pv
                         .iter()
-                        .map(|&pvs| format!("{}", self.cizer.good(pvs.to_string_lossy())))
+                        .map(|&pvs| format!("{}", self.cizer.hint(pvs.to_string_lossy())))
                         .collect::<Vec<_>>()
                         .join(" ")
                 } else {
@@ -479,7 +857,7 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 aliases
                     .iter()
                     .filter(|&als| als.1) // visible
-                    .map(|&als| format!("{}", self.cizer.good(als.0))) // name
+                    .map(|&als| format!("{}", self.cizer.hint(als.0))) // name
                     .collect::<Vec<_>>()
                     .join(", ")
             } else {
@@ -494,19 +872,59 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 spec_vals.push(format!(" [aliases: {}]", als));
             }
         }
+        if let Some(ref aliases) = a.short_aliases {
+            debugln!("Help::spec_vals: Found short aliases...{:?}", aliases);
+            let als = aliases
+                .iter()
+                .filter(|&als| als.1) // visible
+                .map(|&als| {
+                    let name = format!("-{}", als.0);
+                    if self.color {
+                        format!("{}", self.cizer.hint(name))
+                    } else {
+                        name
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            if !als.is_empty() {
+                spec_vals.push(format!(" [short aliases: {}]", als));
+            }
+        }
         if !self.hide_pv && !a.is_set(ArgSettings::HidePossibleValues) {
-            if let Some(ref pv) = a.possible_vals {
+            if let Some(pv) = a.possible_vals_lossy() {
                 debugln!("Help::spec_vals: Found possible vals...{:?}", pv);
-                spec_vals.push(if self.color {
-                    format!(
-                        " [possible values: {}]",
-                        pv.iter()
-                            .map(|v| format!("{}", self.cizer.good(v)))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    )
+                // The long help always lists every value, and so does a help message without a help
+                // flag to point to
+                let help_long = self
+                    .parser
+                    .app
+                    .args
+                    .args
+                    .iter()
+                    .find(|x| x.id == HELP_HASH)
+                    .and_then(|x| x.long);
+                let cap = match a.possible_vals_cap {
+                    Some(cap) if !self.use_long && help_long.is_some() && cap < pv.len() => cap,
+                    _ => pv.len(),
+                };
+                let pvs = if self.color {
+                    pv[..cap]
+                        .iter()
+                        .map(|v| format!("{}", self.cizer.hint(v)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
                 } else {
-                    format!(" [possible values: {}]", pv.join(", "))
+                    pv[..cap].join(", ")
+                };
+                spec_vals.push(match help_long {
+                    Some(long) if cap == 0 && !pv.is_empty() => {
+                        format!(" [possible values: see --{}]", long)
+                    }
+                    Some(long) if cap < pv.len() => {
+                        format!(" [possible values: {}, ... (see --{} for all)]", pvs, long)
+                    }
+                    _ => format!(" [possible values: {}]", pvs),
                 });
             }
         }
@@ -518,7 +936,7 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
 impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     fn write_subcommand(&mut self, app: &App<'b>) -> io::Result<()> {
         debugln!("Help::write_subcommand;");
-        write!(self.writer, "{}", TAB)?;
+        write!(self.writer, "{}", self.tab)?;
         self.color(Format::Good(&*app.name))?;
         let spec_vals = self.sc_val(app)?;
         self.sc_help(app, &*spec_vals)?;
@@ -529,19 +947,22 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
         debugln!("Help::sc_val: app={}", app.name);
         let spec_vals = self.sc_spec_vals(app);
         let h = app.about.unwrap_or("");
-        let h_w = str_width(h) + str_width(&*spec_vals);
+        let h_w = display_width(h) + display_width(&*spec_vals);
         let nlh = self.next_line_help;
-        let taken = self.longest + 12;
-        self.force_next_line = !nlh
-            && self.term_w >= taken
-            && (taken as f32 / self.term_w as f32) > 0.40
-            && h_w > (self.term_w - taken);
+        let self_len = str_width(app.to_string().as_str());
+        let taken = match self.gutter {
+            Some(g) => self.tab.len() + self_len + g,
+            None => self.longest + self.tab.len() * 2 + 4,
+        };
+        self.help_col = taken;
+        self.force_next_line = !nlh && self.should_force_next_line(taken, h_w);
 
         if !(nlh || self.force_next_line) {
-            write_nspaces!(
-                self.writer,
-                self.longest + 4 - (str_width(app.to_string().as_str()))
-            );
+            let spcs = match self.gutter {
+                Some(g) => g,
+                None => self.longest + self.tab.len() - self_len,
+            };
+            write_nspaces!(self.writer, spcs);
         }
         Ok(spec_vals)
     }
@@ -555,7 +976,7 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 aliases
                     .iter()
                     .filter(|&als| als.1) // visible
-                    .map(|&als| format!("{}", self.cizer.good(als.0))) // name
+                    .map(|&als| format!("{}", self.cizer.hint(als.0))) // name
                     .collect::<Vec<_>>()
                     .join(", ")
             } else {
@@ -585,16 +1006,18 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
         debugln!("Help::sc_help: Next Line...{:?}", nlh);
 
         let spcs = if nlh || self.force_next_line {
-            12 // "tab" * 3
+            self.tab.len() * 3
+        } else if self.gutter.is_some() {
+            self.help_col
         } else {
-            self.longest + 12
+            self.longest + self.tab.len() * 2 + 4
         };
 
-        let too_long = spcs + str_width(h) + str_width(&*spec_vals) >= self.term_w;
+        let too_long = spcs + display_width(h) + display_width(&*spec_vals) >= self.term_w;
 
         // Is help on next line, if so then indent
         if nlh || self.force_next_line {
-            write!(self.writer, "\n{}{}{}", TAB, TAB, TAB)?;
+            write!(self.writer, "\n{}{}{}", self.tab, self.tab, self.tab)?;
         }
 
         debug!("Help::sc_help: Too long...");
@@ -605,19 +1028,22 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
             // Determine how many newlines we need to insert
             let avail_chars = self.term_w - spcs;
             debugln!("Help::sc_help: Usable space...{}", avail_chars);
-            help = wrap_help(&help.replace("{n}", "\n"), avail_chars);
+            help = wrap_help(&help.replace("{n}", "\n"), avail_chars, self.break_words);
         } else {
             sdebugln!("No");
         }
+        let help = self.link_urls(&*(String::from(h) + spec_vals), help);
         if let Some(part) = help.lines().next() {
             write!(self.writer, "{}", part)?;
         }
         for part in help.lines().skip(1) {
             writeln!(self.writer)?;
             if nlh || self.force_next_line {
-                write!(self.writer, "{}{}{}", TAB, TAB, TAB)?;
+                write!(self.writer, "{}{}{}", self.tab, self.tab, self.tab)?;
+            } else if self.gutter.is_some() {
+                write_nspaces!(self.writer, self.help_col);
             } else {
-                write_nspaces!(self.writer, self.longest + 8);
+                write_nspaces!(self.writer, self.longest + self.tab.len() * 2);
             }
             write!(self.writer, "{}", part)?;
         }
@@ -634,99 +1060,19 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     /// including titles of a Parser Object to the wrapped stream.
     pub fn write_all_args(&mut self) -> ClapResult<()> {
         debugln!("Help::write_all_args;");
-        let flags = self.parser.has_flags();
-        // Strange filter/count vs fold... https://github.com/rust-lang/rust/issues/33038
-        let pos = positionals!(self.parser.app).fold(0, |acc, arg| {
-            if should_show_arg(self.use_long, arg) {
-                acc + 1
-            } else {
-                acc
-            }
-        }) > 0;
-        let opts = self.parser.has_opts();
+        let sections = self.arg_sections();
         let subcmds = self.parser.has_visible_subcommands();
 
-        let custom_headings = self.parser.app.args.args.iter().fold(0, |acc, arg| {
-            if arg.help_heading.is_some() {
-                acc + 1
-            } else {
-                acc
-            }
-        }) > 0;
-
         let mut first = true;
-
-        if pos {
+        for section in &sections {
             if !first {
                 self.writer.write_all(b"\n\n")?;
             }
-            self.color(Format::Warning("ARGS:\n"))?;
-            self.write_args_unsorted(&*positionals!(self.parser.app).collect::<Vec<_>>())?;
+            self.color(Format::Warning(&*format!("{}:\n", section.title)))?;
+            self.write_section(&*section.args, section.sorted)?;
             first = false;
         }
 
-        let unified_help = self.parser.is_set(AppSettings::UnifiedHelpMessage);
-
-        if unified_help && (flags || opts) {
-            let opts_flags = self
-                .parser
-                .app
-                .args
-                .args
-                .iter()
-                .filter(|a| a.has_switch())
-                .collect::<Vec<_>>();
-            if !first {
-                self.writer.write_all(b"\n\n")?;
-            }
-            self.color(Format::Warning("OPTIONS:\n"))?;
-            self.write_args(&*opts_flags)?;
-            first = false;
-        } else {
-            if flags {
-                if !first {
-                    self.writer.write_all(b"\n\n")?;
-                }
-                self.color(Format::Warning("FLAGS:\n"))?;
-                let flags_v: Vec<_> = flags!(self.parser.app).collect();
-                self.write_args(&*flags_v)?;
-                first = false;
-            }
-            if opts {
-                if !first {
-                    self.writer.write_all(b"\n\n")?;
-                }
-                self.color(Format::Warning("OPTIONS:\n"))?;
-                self.write_args(&*opts!(self.parser.app).collect::<Vec<_>>())?;
-                first = false;
-            }
-            if custom_headings {
-                for heading in self
-                    .parser
-                    .app
-                    .help_headings
-                    .iter()
-                    .filter(|heading| heading.is_some())
-                    .map(|heading| heading.unwrap())
-                {
-                    if !first {
-                        self.writer.write_all(b"\n\n")?;
-                    }
-                    self.color(Format::Warning(&*format!("{}:\n", heading)))?;
-                    let args = self
-                        .parser
-                        .app
-                        .args
-                        .args
-                        .iter()
-                        .filter(|a| a.help_heading.is_some() && a.help_heading.unwrap() == heading)
-                        .collect::<Vec<_>>();
-                    self.write_args(&*args)?;
-                    first = false
-                }
-            }
-        }
-
         if subcmds {
             if !first {
                 self.writer.write_all(b"\n\n")?;
@@ -738,28 +1084,113 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
         Ok(())
     }
 
+    /// Finds the args sections of the help message in the order they're written, leaving out
+    /// those where none of the args shows up in this help variant.
+    fn arg_sections(&self) -> Vec<ArgSection<'d, 'b>> {
+        let parser = self.parser;
+        let app: &'d App<'b> = &*parser.app;
+        let use_long = self.use_long;
+        let flags = flags!(app).any(|arg| should_show_arg(use_long, arg));
+        let opts = opts!(app).any(|arg| should_show_arg(use_long, arg));
+        let unified = parser.is_set(AppSettings::UnifiedHelpMessage) && (flags || opts);
+        // Positionals with a heading are listed under it, except with a unified help message
+        // which has no custom sections
+        let args_pos: Vec<&'d Arg<'b>> = positionals!(app)
+            .filter(|arg| unified || arg.help_heading.is_none())
+            .collect();
+        let pos = args_pos.iter().any(|arg| should_show_arg(use_long, arg));
+
+        // Headings are displayed in the order they were first used
+        let mut custom_headings: Vec<&str> = Vec::new();
+        for heading in app
+            .args
+            .args
+            .iter()
+            .filter(|arg| should_show_arg(use_long, arg))
+            .filter_map(|arg| arg.help_heading)
+        {
+            if !custom_headings.contains(&heading) {
+                custom_headings.push(heading);
+            }
+        }
+
+        let mut sections = vec![];
+
+        if pos {
+            sections.push(ArgSection {
+                title: String::from("ARGS"),
+                args: args_pos,
+                sorted: parser.is_set(AppSettings::SortPositionals),
+            });
+        }
+
+        if unified {
+            sections.push(ArgSection {
+                title: String::from("OPTIONS"),
+                args: app.args.args.iter().filter(|a| a.has_switch()).collect(),
+                sorted: true,
+            });
+            return sections;
+        }
+
+        if flags {
+            sections.push(ArgSection {
+                title: String::from("FLAGS"),
+                args: flags!(app).collect(),
+                sorted: true,
+            });
+        }
+        if opts {
+            sections.push(ArgSection {
+                title: String::from("OPTIONS"),
+                args: opts!(app).collect(),
+                sorted: true,
+            });
+        }
+        for heading in custom_headings {
+            sections.push(ArgSection {
+                title: String::from(heading),
+                args: app
+                    .args
+                    .args
+                    .iter()
+                    .filter(|a| a.help_heading == Some(heading))
+                    .collect(),
+                sorted: true,
+            });
+        }
+        sections
+    }
+
     /// Writes help for subcommands of a Parser Object to the wrapped stream.
     fn write_subcommands(&mut self, app: &App<'b>) -> io::Result<()> {
         debugln!("Help::write_subcommands;");
+        self._write_subcommands(app, false)
+    }
+
+    /// Writes help for all subcommands of a Parser Object, including hidden ones, to the wrapped
+    /// stream.
+    fn write_all_subcommands(&mut self, app: &App<'b>) -> io::Result<()> {
+        debugln!("Help::write_all_subcommands;");
+        self._write_subcommands(app, true)
+    }
+
+    fn _write_subcommands(&mut self, app: &App<'b>, include_hidden: bool) -> io::Result<()> {
+        let scs = ordered_subcommands(app, include_hidden);
         // The shortest an arg can legally be is 2 (i.e. '-x')
         self.longest = 2;
-        let mut ord_m = VecMap::new();
-        for sc in subcommands!(app).filter(|s| !s.is_set(AppSettings::Hidden)) {
-            let btm = ord_m.entry(sc.disp_ord).or_insert(BTreeMap::new());
+        for sc in &scs {
             self.longest = cmp::max(self.longest, str_width(sc.name.as_str()));
-            btm.insert(sc.name.clone(), sc.clone());
         }
 
         let mut first = true;
-        for btm in ord_m.values() {
-            for sc in btm.values() {
-                if first {
-                    first = false;
-                } else {
-                    self.writer.write_all(b"\n")?;
-                }
-                self.write_subcommand(sc)?;
+        for sc in scs {
+            if first {
+                first = false;
+            } else {
+                self.writer.write_all(b"\n")?;
             }
+            self.write_subcommand(sc)?;
         }
         Ok(())
     }
@@ -775,11 +1206,13 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     fn write_bin_name(&mut self) -> io::Result<()> {
         debugln!("Help::write_bin_name;");
         let term_w = self.term_w;
+        let break_words = self.break_words;
         macro_rules! write_name {
             () => {{
                 self.color(Format::Good(&*wrap_help(
                     &self.parser.app.name.replace("{n}", "\n"),
                     term_w,
+                    break_words,
                 )))?;
             }};
         }
@@ -796,10 +1229,42 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
         Ok(())
     }
 
+    /// Writes the usage wrapped to the term width, with continuation lines indented to line up
+    /// under the first argument. `col` is the column the usage starts at.
+    fn write_wrapped_usage(&mut self, usage: &str, col: usize) -> io::Result<()> {
+        debugln!("Help::write_wrapped_usage: col={}", col);
+        let app = &self.parser.app;
+        let name = app.usage.as_ref().or(app.bin_name.as_ref()).unwrap_or(&app.name);
+        let hang = col + if usage.starts_with(&**name) && usage.len() > name.len() {
+            name.len() + 1
+        } else {
+            usage.find(' ').map_or(0, |i| i + 1)
+        };
+        if usage.contains('\n') || hang >= self.term_w {
+            return write!(self.writer, "{}", usage);
+        }
+        let initial = " ".repeat(col);
+        let subsequent = " ".repeat(hang);
+        let wrapped = textwrap::Wrapper::new(self.term_w)
+            .break_words(self.break_words)
+            .initial_indent(&*initial)
+            .subsequent_indent(&*subsequent)
+            .fill(usage);
+        write!(self.writer, "{}", &wrapped[col..])
+    }
+
     /// Writes default help for a Parser Object to the wrapped stream.
     pub fn write_default_help(&mut self) -> ClapResult<()> {
         debugln!("Help::write_default_help;");
-        if let Some(h) = self.parser.app.pre_help {
+        let (pre_help, more_help) = if self.use_long {
+            (
+                self.parser.app.long_pre_help.or(self.parser.app.pre_help),
+                self.parser.app.long_more_help.or(self.parser.app.more_help),
+            )
+        } else {
+            (self.parser.app.pre_help, self.parser.app.more_help)
+        };
+        if let Some(h) = pre_help {
             self.write_before_after_help(h)?;
             self.writer.write_all(b"\n\n")?;
         }
@@ -809,7 +1274,7 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 write!(
                     self.writer,
                     "{}\n",
-                    wrap_help(&$thing.replace("{n}", "\n"), self.term_w)
+                    wrap_help(&$thing.replace("{n}", "\n"), self.term_w, self.break_words)
                 )?
             }};
         }
@@ -829,13 +1294,17 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
             write_thing!(about)
         }
 
-        self.color(Format::Warning("\nUSAGE:"))?;
-        write!(
-            self.writer,
-            "\n{}{}\n\n",
-            TAB,
-            Usage::new(self.parser).create_usage_no_title(&[])
-        )?;
+        if self.parser.is_set(AppSettings::HideUsage) {
+            self.writer.write_all(b"\n")?;
+        } else {
+            self.color(Format::Warning("\nUSAGE:"))?;
+            write!(
+                self.writer,
+                "\n{}{}\n\n",
+                self.tab,
+                Usage::new(self.parser).create_usage_no_title(&[])
+            )?;
+        }
 
         let flags = self.parser.has_flags();
         let pos = self.parser.has_positionals();
@@ -846,7 +1315,7 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
             self.write_all_args()?;
         }
 
-        if let Some(h) = self.parser.app.more_help {
+        if let Some(h) = more_help {
             if flags || opts || pos || subcmds {
                 self.writer.write_all(b"\n\n")?;
             }
@@ -954,6 +1423,46 @@ fn copy_and_capture<R: Read, W: Write>(
     }
 }
 
+/// Writes a version message in the format defined by the template, see
+/// `App::version_template`.
+///
+/// Valid tags are:
+///     * `{bin}`     - Binary name.
+///     * `{version}` - Version number.
+///     * `{build}`   - Build information, as set by `App::build_info`.
+pub(crate) fn write_templated_version<W: Write>(
+    w: &mut W,
+    template: &str,
+    bin: &str,
+    ver: &str,
+    build: Option<&str>,
+) -> io::Result<()> {
+    debugln!("write_templated_version;");
+    let mut tmplr = Cursor::new(&template);
+    let mut tag_buf = Cursor::new(vec![0u8; 20]);
+
+    loop {
+        let tag_length = match copy_and_capture(&mut tmplr, w, &mut tag_buf) {
+            None => return Ok(()),
+            Some(Err(e)) => return Err(e),
+            Some(Ok(val)) if val > 0 => val,
+            _ => continue,
+        };
+
+        match &tag_buf.get_ref()[0..tag_length] {
+            b"bin" => write!(w, "{}", bin)?,
+            b"version" => write!(w, "{}", ver)?,
+            b"build" => write!(w, "{}", build.unwrap_or(""))?,
+            // Unknown tag, write it back.
+            r => {
+                w.write_all(b"{")?;
+                w.write_all(r)?;
+                w.write_all(b"}")?;
+            }
+        }
+    }
+}
+
 // Methods to write Parser help using templates.
 impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     /// Write help to stream for the parser in the format defined by the template.
@@ -971,15 +1480,22 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     ///     * `{options}`     - Help for options.
     ///     * `{positionals}` - Help for positionals arguments.
     ///     * `{subcommands}` - Help for subcommands.
+    ///     * `{subcommands-all}` - Help for subcommands, including hidden ones.
     ///     * `{after-help}`  - Info to be displayed after the help message.
     ///     * `{before-help}` - Info to be displayed before the help message.
+    ///     * `{after-long-help}` - Info to be displayed after the long help message.
+    ///     * `{before-long-help}` - Info to be displayed before the long help message.
+    ///     * `{tab}`         - One level of indentation, as set by `App::tab_width`.
+    ///     * `{columns:N}`   - Wrap the remainder of the template at `N` columns, or not at all
+    ///                         for `0`. `N` is raised to the minimum width.
+    ///     * `{@name}`       - The partial template registered as `name`.
     ///
     /// The template system is, on purpose, very simple. Therefore the tags have to writen
     /// in the lowercase and without spacing.
     fn write_templated_help(&mut self, template: &str) -> ClapResult<()> {
         debugln!("Help::write_templated_help;");
         let mut tmplr = Cursor::new(&template);
-        let mut tag_buf = Cursor::new(vec![0u8; 15]);
+        let mut tag_buf = Cursor::new(vec![0u8; 20]);
 
         // The strategy is to copy the template from the reader to wrapped stream
         // until a tag is found. Depending on its value, the appropriate content is copied
@@ -1039,11 +1555,12 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                     )?;
                 }
                 b"usage" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        Usage::new(self.parser).create_usage_no_title(&[])
-                    )?;
+                    if !self.parser.is_set(AppSettings::HideUsage) {
+                        // Where the tag starts on its line, so wrapped lines can be lined up
+                        let col = self.writer.column();
+                        let usage = Usage::new(self.parser).create_usage_no_title(&[]);
+                        self.write_wrapped_usage(&*usage, col)?;
+                    }
                 }
                 b"all-args" => {
                     self.write_all_args()?;
@@ -1071,6 +1588,9 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                 b"subcommands" => {
                     self.write_subcommands(self.parser.app)?;
                 }
+                b"subcommands-all" => {
+                    self.write_all_subcommands(self.parser.app)?;
+                }
                 b"after-help" => {
                     write!(
                         self.writer,
@@ -1085,6 +1605,62 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
                         self.parser.app.pre_help.unwrap_or("unknown before-help")
                     )?;
                 }
+                b"after-long-help" => {
+                    let h = self.parser.app.long_more_help.or(self.parser.app.more_help);
+                    write!(self.writer, "{}", h.unwrap_or("unknown after-long-help"))?;
+                }
+                b"before-long-help" => {
+                    let h = self.parser.app.long_pre_help.or(self.parser.app.pre_help);
+                    write!(self.writer, "{}", h.unwrap_or("unknown before-long-help"))?;
+                }
+                b"tab" => {
+                    write!(self.writer, "{}", self.tab)?;
+                }
+                r if r.starts_with(b"columns:") => {
+                    match str::from_utf8(&r[8..])
+                        .ok()
+                        .and_then(|n| n.parse::<usize>().ok())
+                    {
+                        // Like a term width of 0, i.e. no wrapping at all
+                        Some(0) => self.term_w = usize::MAX,
+                        Some(n) => {
+                            let min_w = self.parser.app.min_w.unwrap_or(MIN_TERM_WIDTH);
+                            self.term_w = cmp::max(n, min_w);
+                        }
+                        None => {
+                            self.writer.write_all(b"{")?;
+                            self.writer.write_all(r)?;
+                            self.writer.write_all(b"}")?;
+                        }
+                    }
+                }
+                r if r.starts_with(b"@") => {
+                    let partial = self
+                        .parser
+                        .app
+                        .help_partials
+                        .iter()
+                        .find(|&&(n, _)| n.as_bytes() == &r[1..])
+                        .map(|&(_, p)| p);
+                    if let Some(p) = partial {
+                        if self.partial_depth == MAX_PARTIAL_DEPTH {
+                            return Err(Error::with_description(
+                                &format!(
+                                    "The help partial '{}' is nested too deeply",
+                                    String::from_utf8_lossy(&r[1..])
+                                ),
+                                ErrorKind::Format,
+                            ));
+                        }
+                        self.partial_depth += 1;
+                        self.write_templated_help(p)?;
+                        self.partial_depth -= 1;
+                    } else {
+                        self.writer.write_all(b"{")?;
+                        self.writer.write_all(r)?;
+                        self.writer.write_all(b"}")?;
+                    }
+                }
                 // Unknown tag, write it back.
                 r => {
                     self.writer.write_all(b"{")?;
@@ -1096,6 +1672,77 @@ impl<'b, 'c, 'd, 'w> Help<'b, 'c, 'd, 'w> {
     }
 }
 
+// A titled section of args in the help message, i.e. `FLAGS`
+struct ArgSection<'a, 'b> {
+    title: String,
+    args: Vec<&'a Arg<'b>>,
+    // Whether the args are sorted by display order and name, or kept in declared order
+    sorted: bool,
+}
+
+// An entry of an args section in the help message
+#[derive(Copy, Clone)]
+enum SectionEntry<'a, 'b> {
+    Arg(&'a Arg<'b>),
+    // Index into the section's combined groups
+    Group(usize),
+}
+
+// A group written to the help message as a single entry, see `ArgGroup::help`
+struct CombinedGroup<'b> {
+    name: &'b str,
+    label: String,
+    help: &'b str,
+    has_switch: bool,
+    args: Vec<Id>,
+}
+
+// The subcommands of `app` in the order they're written, by display order and then name
+fn ordered_subcommands<'a, 'b>(app: &'a App<'b>, include_hidden: bool) -> Vec<&'a App<'b>> {
+    let mut ord_m = VecMap::new();
+    for sc in subcommands!(app).filter(|s| include_hidden || !s.is_set(AppSettings::Hidden)) {
+        let btm = ord_m.entry(sc.disp_ord).or_insert(BTreeMap::new());
+        btm.insert(sc.name.as_str(), sc);
+    }
+    let mut scs = vec![];
+    for btm in ord_m.values() {
+        scs.extend(btm.values().cloned());
+    }
+    scs
+}
+
+// How an arg is written as an entry of the help message, i.e. `-c, --config <FILE>`
+fn arg_label(arg: &Arg) -> String {
+    match (arg.short, arg.long) {
+        (Some(s), Some(_)) => format!("-{}, {}", s, arg),
+        _ => arg.to_string(),
+    }
+}
+
+// How an arg is written inside a combined group's entry, i.e. `--json` or `--format <FMT>`
+fn group_member_label(arg: &Arg) -> String {
+    let (vals, ellipsis) = arg.val_placeholders();
+    let mut label = match (arg.long, arg.short) {
+        (Some(l), _) => format!("--{}", l),
+        (None, Some(s)) => format!("-{}", s),
+        (None, None) => String::new(),
+    };
+    if !vals.is_empty() {
+        if !label.is_empty() {
+            label.push(if arg.is_set(ArgSettings::RequireEquals) {
+                '='
+            } else {
+                ' '
+            });
+        }
+        label.push_str(&*vals.join(" "));
+    }
+    if ellipsis {
+        label.push_str("...");
+    }
+    label
+}
+
 fn should_show_arg(use_long: bool, arg: &Arg) -> bool {
     debugln!(
         "Help::should_show_arg: use_long={:?}, arg={}",
@@ -1107,24 +1754,240 @@ fn should_show_arg(use_long: bool, arg: &Arg) -> bool {
     }
     (!arg.is_set(ArgSettings::HiddenLongHelp) && use_long)
         || (!arg.is_set(ArgSettings::HiddenShortHelp) && !use_long)
-        || arg.is_set(ArgSettings::NextLineHelp)
 }
 
-fn wrap_help(help: &str, avail_chars: usize) -> String {
-    let wrapper = textwrap::Wrapper::new(avail_chars).break_words(false);
+fn wrap_help(help: &str, avail_chars: usize, break_words: bool) -> String {
+    let wrapper = textwrap::Wrapper::new(avail_chars).break_words(break_words);
+    help.lines()
+        .map(|line| fill_ansi(&wrapper, line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Like `wrap_help`, but lines starting with whitespace keep that indentation on every line they
+// are wrapped onto.
+fn wrap_help_keep_indent(help: &str, avail_chars: usize, break_words: bool) -> String {
     help.lines()
-        .map(|line| wrapper.fill(line))
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            if indent.is_empty() || trimmed.is_empty() {
+                wrap_help(line, avail_chars, break_words)
+            } else {
+                let wrapper = textwrap::Wrapper::new(avail_chars)
+                    .break_words(break_words)
+                    .initial_indent(indent)
+                    .subsequent_indent(indent);
+                fill_ansi(&wrapper, trimmed)
+            }
+        })
         .collect::<Vec<String>>()
         .join("\n")
 }
 
+// Fills a single line like `Wrapper::fill`, but ANSI escape sequences take up no width. They're
+// taken out before wrapping and put back in front of the same characters afterwards.
+fn fill_ansi(wrapper: &textwrap::Wrapper<textwrap::HyphenSplitter>, line: &str) -> String {
+    let (plain, escapes) = strip_ansi(line);
+    if escapes.is_empty() {
+        return wrapper.fill(line);
+    }
+    let plain: Vec<char> = plain.chars().collect();
+    let mut escapes = escapes.into_iter().peekable();
+    let mut out = String::with_capacity(line.len() * 2);
+    let mut i = 0;
+    for c in wrapper.fill(&*plain.iter().collect::<String>()).chars() {
+        // Whitespace the wrapping dropped (i.e. replaced with a newline) still ends any colors
+        while i < plain.len() && plain[i] != c && plain[i].is_whitespace() {
+            while escapes.peek().map_or(false, |&(at, _)| at <= i) {
+                out.push_str(&*escapes.next().expect(INTERNAL_ERROR_MSG).1);
+            }
+            i += 1;
+        }
+        let matched = i < plain.len() && plain[i] == c;
+        let end = if matched { i + 1 } else { i };
+        while escapes.peek().map_or(false, |&(at, _)| at < end) {
+            out.push_str(&*escapes.next().expect(INTERNAL_ERROR_MSG).1);
+        }
+        out.push(c);
+        if matched {
+            i += 1;
+        }
+    }
+    for (_, esc) in escapes {
+        out.push_str(&*esc);
+    }
+    out
+}
+
+// Splits ANSI escape sequences (CSI, i.e. colors, and OSC, i.e. hyperlinks) from `s`, returning
+// the plain text and each sequence with the index of the plain character it precedes
+fn strip_ansi(s: &str) -> (String, Vec<(usize, String)>) {
+    let mut plain = String::with_capacity(s.len());
+    let mut escapes = vec![];
+    let mut count = 0;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            plain.push(c);
+            count += 1;
+            continue;
+        }
+        let mut esc = String::from(c);
+        match chars.next() {
+            Some('[') => {
+                esc.push('[');
+                // Parameters up to the final byte
+                for c in chars.by_ref() {
+                    esc.push(c);
+                    if c >= '@' && c <= '~' {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                esc.push(']');
+                // Terminated by BEL or ESC \
+                while let Some(c) = chars.next() {
+                    esc.push(c);
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        esc.push('\\');
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(c) => esc.push(c),
+            None => {}
+        }
+        escapes.push((count, esc));
+    }
+    (plain, escapes)
+}
+
+const URL_SCHEMES: [&str; 3] = ["https://", "http://", "file://"];
+
+// Returns the byte ranges of the URLs in `s`. A URL runs from its scheme up to the next
+// whitespace, without any trailing punctuation.
+fn url_spans(s: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut i = 0;
+    while let Some((off, scheme)) = URL_SCHEMES
+        .iter()
+        .filter_map(|sch| s[i..].find(sch).map(|off| (off, sch.len())))
+        .min()
+    {
+        let start = i + off;
+        let len = s[start..]
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '<' || c == '>')
+            .unwrap_or(s.len() - start);
+        let url = s[start..start + len].trim_end_matches(|c: char| ".,;:!?')".contains(c));
+        if url.len() > scheme {
+            spans.push((start, start + url.len()));
+        }
+        i = start + len;
+    }
+    spans
+}
+
+// Wraps every URL of `s` which is one of `urls` in an OSC 8 hyperlink
+fn hyperlink_urls(s: &str, urls: &[&str]) -> String {
+    let mut linked = String::with_capacity(s.len());
+    let mut last = 0;
+    for (start, end) in url_spans(s) {
+        let url = &s[start..end];
+        if !urls.contains(&url) {
+            continue;
+        }
+        linked.push_str(&s[last..start]);
+        linked.push_str(&*format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, url));
+        last = end;
+    }
+    linked.push_str(&s[last..]);
+    linked
+}
+
 #[cfg(test)]
 mod test {
-    use super::wrap_help;
+    use super::{
+        display_width, hyperlink_urls, str_width, url_spans, wrap_help, wrap_help_keep_indent,
+    };
 
     #[test]
     fn wrap_help_last_word() {
         let help = String::from("foo bar baz");
-        assert_eq!(wrap_help(&help, 5), "foo\nbar\nbaz");
+        assert_eq!(wrap_help(&help, 5, false), "foo\nbar\nbaz");
+    }
+
+    #[test]
+    fn wrap_help_break_words() {
+        let help = String::from("foo barbazqux");
+        assert_eq!(wrap_help(&help, 5, false), "foo\nbarbazqux");
+        assert_eq!(wrap_help(&help, 5, true), "foo\nbarba\nzqux");
+    }
+
+    #[test]
+    fn wrap_help_keeps_indent() {
+        let help = String::from("list:\n  - aaa bbb ccc");
+        assert_eq!(
+            wrap_help_keep_indent(&help, 10, false),
+            "list:\n  - aaa\n  bbb ccc"
+        );
+    }
+
+    #[test]
+    fn url_spans_trim_punctuation() {
+        let s = "see https://a.io/x. or (http://b.io) and https:// only";
+        let urls = url_spans(s)
+            .into_iter()
+            .map(|(start, end)| &s[start..end])
+            .collect::<Vec<_>>();
+        assert_eq!(urls, ["https://a.io/x", "http://b.io"]);
+    }
+
+    #[test]
+    fn hyperlink_only_known_urls() {
+        assert_eq!(
+            hyperlink_urls("at https://a.io or\nhttps://b", &["https://a.io"]),
+            "at \x1b]8;;https://a.io\x1b\\https://a.io\x1b]8;;\x1b\\ or\nhttps://b"
+        );
+    }
+
+    #[test]
+    fn display_width_ignores_ansi() {
+        assert_eq!(display_width("plain text"), str_width("plain text"));
+        assert_eq!(display_width("[default: \x1b[36mauto\x1b[0m]"), 15);
+        assert_eq!(
+            display_width("\x1b]8;;https://a.io\x1b\\https://a.io\x1b]8;;\x1b\\"),
+            12
+        );
+    }
+
+    #[test]
+    fn wrap_help_ignores_ansi() {
+        let help = "foo \x1b[1mbar\x1b[0m baz";
+        assert_eq!(wrap_help(help, 7, false), "foo \x1b[1mbar\x1b[0m\nbaz");
+        assert_eq!(wrap_help(help, 3, false), "foo\n\x1b[1mbar\x1b[0m\nbaz");
+    }
+
+    #[test]
+    fn wrap_help_plain_unchanged() {
+        let help = "foo bar baz";
+        assert_eq!(wrap_help(help, 7, false), "foo bar\nbaz");
+    }
+
+    #[cfg(feature = "wrap_help")]
+    #[test]
+    fn term_width_prefers_columns() {
+        use super::term_width_from;
+
+        assert_eq!(term_width_from(Some(" 73 ")), Some(73));
+        let detected = term_size::dimensions().map(|(w, _)| w);
+        assert_eq!(term_width_from(Some("0")), detected);
+        assert_eq!(term_width_from(Some("wide")), detected);
+        assert_eq!(term_width_from(None), detected);
     }
 }
@@ -3,5 +3,8 @@ mod usage;
 
 pub mod fmt;
 
-pub use self::help::Help;
-pub use self::usage::Usage;
+pub use self::help::{Help, HelpEntry, HelpSection, HelpTree};
+pub(crate) use self::help::write_templated_version;
+pub use self::usage::{
+    DefaultUsageRenderer, Usage, UsageParts, UsageRenderer, UsageRendererHandle, UsageSpec,
+};
@@ -1,14 +1,115 @@
 // std
 use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::rc::Rc;
 
 // Internal
 use crate::build::AppSettings as AS;
 use crate::build::{Arg, ArgSettings};
+use crate::output::fmt::{Colorizer, ColorizerOption};
 use crate::parse::{ArgMatcher, Parser};
+use crate::util::{HELP_HASH, VERSION_HASH};
 use crate::INTERNAL_ERROR_MSG;
 
 type Id = u64;
 
+// The most subcommand names listed in the usage string with AppSettings::ShowSubcommandsInUsage
+const MAX_USAGE_SUBCOMMANDS: usize = 5;
+
+/// A structured description of an [`App`]'s usage, for tools which need to inspect it rather than
+/// display it. See [`App::generate_usage_spec`].
+///
+/// [`App`]: ./struct.App.html
+/// [`App::generate_usage_spec`]: ./struct.App.html#method.generate_usage_spec
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageSpec<'b> {
+    /// The names of all visible required arguments, in the order they were declared
+    pub required: Vec<&'b str>,
+    /// The names of all positional arguments, in index order
+    pub positionals: Vec<&'b str>,
+    /// The names of all optional flags, i.e. arguments which don't take a value
+    pub flags: Vec<&'b str>,
+    /// The names of all optional options, i.e. arguments which take a value
+    pub options: Vec<&'b str>,
+    /// Whether a subcommand can be used, i.e. the app has visible subcommands or allows
+    /// [external subcommands]
+    ///
+    /// [external subcommands]: ./enum.AppSettings.html#variant.AllowExternalSubcommands
+    pub has_subcommands: bool,
+}
+
+/// The categorized arguments of an [`App`], handed to a [`UsageRenderer`] to build the usage
+/// string from.
+///
+/// [`App`]: ./struct.App.html
+/// [`UsageRenderer`]: ./trait.UsageRenderer.html
+#[derive(Debug)]
+pub struct UsageParts<'a, 'b>
+where
+    'b: 'a,
+{
+    /// The name the usage string starts with, i.e. the binary name
+    pub name: &'a str,
+    /// The required arguments already written as they're used (i.e. `<input>` or
+    /// `--config <FILE>`), positional arguments first in index order. When the usage is for an
+    /// error these also include the arguments which were used.
+    pub required: Vec<String>,
+    /// The positional arguments which aren't required, in index order
+    pub positionals: Vec<&'a Arg<'b>>,
+    /// The flags which aren't required, i.e. arguments which don't take a value
+    pub flags: Vec<&'a Arg<'b>>,
+    /// The options which aren't required, i.e. arguments which take a value
+    pub options: Vec<&'a Arg<'b>>,
+    /// Whether a subcommand can be used, i.e. the app has visible subcommands or allows
+    /// [external subcommands]
+    ///
+    /// [external subcommands]: ./enum.AppSettings.html#variant.AllowExternalSubcommands
+    pub has_subcommands: bool,
+    default: String,
+}
+
+impl<'a, 'b> UsageParts<'a, 'b> {
+    /// The usage string `clap` would have used without a [`UsageRenderer`]
+    ///
+    /// [`UsageRenderer`]: ./trait.UsageRenderer.html
+    pub fn default_usage(&self) -> &str { &*self.default }
+}
+
+/// Builds the usage string (without the `USAGE:` title) from the categorized arguments of an
+/// [`App`], for both the help message and errors. Set with [`App::usage_renderer`].
+///
+/// The provided method is `clap`'s own usage string, so implementors only need to override
+/// [`UsageRenderer::render`] to change it.
+///
+/// [`App`]: ./struct.App.html
+/// [`App::usage_renderer`]: ./struct.App.html#method.usage_renderer
+/// [`UsageRenderer::render`]: ./trait.UsageRenderer.html#method.render
+pub trait UsageRenderer {
+    /// Returns the usage string for `parts`
+    fn render(&self, parts: &UsageParts) -> String { parts.default_usage().to_owned() }
+}
+
+/// The [`UsageRenderer`] producing `clap`'s own usage string, i.e. `myprog [FLAGS] [OPTIONS]
+/// <input>`
+///
+/// [`UsageRenderer`]: ./trait.UsageRenderer.html
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DefaultUsageRenderer;
+
+impl UsageRenderer for DefaultUsageRenderer {}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct UsageRendererHandle<'b>(Rc<dyn UsageRenderer + 'b>);
+
+impl<'b> UsageRendererHandle<'b> {
+    pub(crate) fn new(r: Box<dyn UsageRenderer + 'b>) -> Self { UsageRendererHandle(Rc::from(r)) }
+}
+
+impl<'b> fmt::Debug for UsageRendererHandle<'b> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "UsageRendererHandle") }
+}
+
 pub struct Usage<'b, 'c, 'z>
 where
     'b: 'c,
@@ -30,18 +131,128 @@ impl<'b, 'c, 'z> Usage<'b, 'c, 'z> {
         usage
     }
 
+    // Creates a usage string for error messages, where the title is colored like in the help
+    // message whenever the error itself is colored
+    pub fn create_error_usage(&self, used: &[Id]) -> String {
+        debugln!("usage::create_error_usage;");
+        let c = Colorizer::new(&ColorizerOption::from(self.p.app.color_spec()));
+        format!(
+            "{}\n    {}",
+            c.warning("USAGE:"),
+            self.create_usage_no_title(used)
+        )
+    }
+
     // Creates a usage string (*without title*) if one was not provided by the user manually.
     pub fn create_usage_no_title(&self, used: &[Id]) -> String {
         debugln!("usage::create_usage_no_title;");
         if let Some(u) = self.p.app.usage_str {
             String::from(&*u)
-        } else if used.is_empty() {
+        } else if let Some(ref r) = self.p.app.usage_renderer {
+            r.0.render(&self.create_usage_parts(used))
+        } else {
+            self.create_default_usage(used)
+        }
+    }
+
+    // Creates clap's own usage string, ignoring any UsageRenderer
+    fn create_default_usage(&self, used: &[Id]) -> String {
+        if used.is_empty() {
             self.create_help_usage(true)
         } else {
             self.create_smart_usage(used)
         }
     }
 
+    // Categorizes the args for a UsageRenderer
+    fn create_usage_parts(&self, used: &[Id]) -> UsageParts<'_, 'b> {
+        debugln!("Usage::create_usage_parts;");
+        let app = &*self.p.app;
+        let visible = |a: &&Arg| !a.is_set(ArgSettings::Hidden);
+        let optional = |a: &&Arg| a.has_switch() && !a.is_set(ArgSettings::Required);
+        let mut positionals = positionals!(app)
+            .filter(visible)
+            .filter(|p| !p.is_set(ArgSettings::Required))
+            .collect::<Vec<_>>();
+        positionals.sort_by_key(|p| p.index);
+        UsageParts {
+            name: app
+                .usage
+                .as_ref()
+                .unwrap_or_else(|| app.bin_name.as_ref().unwrap_or(&app.name)),
+            required: self
+                .get_required_usage_from(used, None, !used.is_empty())
+                .into_iter()
+                .collect(),
+            positionals,
+            flags: app
+                .args
+                .args
+                .iter()
+                .filter(visible)
+                .filter(optional)
+                .filter(|a| !a.is_set(ArgSettings::TakesValue))
+                .collect(),
+            options: app
+                .args
+                .args
+                .iter()
+                .filter(visible)
+                .filter(optional)
+                .filter(|a| a.is_set(ArgSettings::TakesValue))
+                .collect(),
+            has_subcommands: app.has_visible_subcommands()
+                || self.p.is_set(AS::AllowExternalSubcommands),
+            default: self.create_default_usage(used),
+        }
+    }
+
+    // Creates a structured description of the usage for tooling, without building the usage string
+    pub fn create_usage_spec(&self) -> UsageSpec<'b> {
+        debugln!("Usage::create_usage_spec;");
+        let visible = |a: &&Arg| !a.is_set(ArgSettings::Hidden);
+        let mut pos_v = positionals!(self.p.app).filter(visible).collect::<Vec<_>>();
+        pos_v.sort_by_key(|p| p.index);
+        let optional = |a: &&Arg| a.has_switch() && !a.is_set(ArgSettings::Required);
+        UsageSpec {
+            required: self
+                .p
+                .app
+                .args
+                .args
+                .iter()
+                .filter(visible)
+                .filter(|a| a.is_set(ArgSettings::Required))
+                .map(|a| a.name)
+                .collect(),
+            positionals: pos_v.iter().map(|p| p.name).collect(),
+            flags: self
+                .p
+                .app
+                .args
+                .args
+                .iter()
+                .filter(visible)
+                .filter(optional)
+                .filter(|a| !a.is_set(ArgSettings::TakesValue))
+                .map(|a| a.name)
+                .collect(),
+            options: self
+                .p
+                .app
+                .args
+                .args
+                .iter()
+                .filter(visible)
+                .filter(optional)
+                .filter(|a| a.is_set(ArgSettings::TakesValue))
+                .map(|a| a.name)
+                .collect(),
+            has_subcommands: self.p.app.has_visible_subcommands()
+                || self.p.is_set(AS::AllowExternalSubcommands),
+        }
+    }
+
     // Creates a usage string for display in help messages (i.e. not for errors)
     pub fn create_help_usage(&self, incl_reqs: bool) -> String {
         debugln!("Usage::create_help_usage; incl_reqs={:?}", incl_reqs);
@@ -128,18 +339,18 @@ impl<'b, 'c, 'z> Usage<'b, 'c, 'z> {
                 if !self.p.is_set(AS::ArgsNegateSubcommands) {
                     usage.push_str("\n    ");
                     usage.push_str(&*self.create_help_usage(false));
-                    usage.push_str(" <SUBCOMMAND>");
+                    usage.push_str(&*format!(" <{}>", self.subcommand_tag()));
                 } else {
                     usage.push_str("\n    ");
                     usage.push_str(&*name);
-                    usage.push_str(" <SUBCOMMAND>");
+                    usage.push_str(&*format!(" <{}>", self.subcommand_tag()));
                 }
             } else if self.p.is_set(AS::SubcommandRequired)
                 || self.p.is_set(AS::SubcommandRequiredElseHelp)
             {
-                usage.push_str(" <SUBCOMMAND>");
+                usage.push_str(&*format!(" <{}>", self.subcommand_tag()));
             } else {
-                usage.push_str(" [SUBCOMMAND]");
+                usage.push_str(&*format!(" [{}]", self.subcommand_tag()));
             }
         }
         usage.shrink_to_fit();
@@ -168,12 +379,38 @@ impl<'b, 'c, 'z> Usage<'b, 'c, 'z> {
         );
         usage.push_str(&*r_string);
         if self.p.is_set(AS::SubcommandRequired) {
-            usage.push_str(" <SUBCOMMAND>");
+            usage.push_str(&*format!(" <{}>", self.subcommand_tag()));
         }
         usage.shrink_to_fit();
         usage
     }
 
+    // Gets the name shown for subcommands in the usage string, with ShowSubcommandsInUsage the
+    // visible subcommands in help order, i.e. `build|test`
+    fn subcommand_tag(&self) -> String {
+        if !self.p.is_set(AS::ShowSubcommandsInUsage) {
+            return String::from("SUBCOMMAND");
+        }
+        let mut scs = subcommands!(self.p.app)
+            .filter(|sc| !sc.is_set(AS::Hidden))
+            .map(|sc| (sc.disp_ord, &*sc.name))
+            .collect::<Vec<_>>();
+        if scs.is_empty() {
+            // Only external subcommands are allowed
+            return String::from("SUBCOMMAND");
+        }
+        scs.sort();
+        let mut names = scs
+            .iter()
+            .take(MAX_USAGE_SUBCOMMANDS)
+            .map(|&(_, name)| name)
+            .collect::<Vec<_>>();
+        if scs.len() > MAX_USAGE_SUBCOMMANDS {
+            names.push("...");
+        }
+        names.join("|")
+    }
+
     // Gets the `[ARGS]` tag for the usage string
     fn get_args_tag(&self, incl_reqs: bool) -> Option<String> {
         debugln!("usage::get_args_tag; incl_reqs = {:?}", incl_reqs);
@@ -274,11 +511,9 @@ impl<'b, 'c, 'z> Usage<'b, 'c, 'z> {
         debugln!("usage::needs_flags_tag;");
         'outer: for f in flags!(self.p.app) {
             debugln!("usage::needs_flags_tag:iter: f={};", f.name);
-            if let Some(l) = f.long {
-                if l == "help" || l == "version" {
-                    // Don't print `[FLAGS]` just for help or version
-                    continue;
-                }
+            if f.id == HELP_HASH || f.id == VERSION_HASH {
+                // Don't print `[FLAGS]` just for help or version
+                continue;
             }
             for grp_s in groups_for_arg!(self.p.app, f.id) {
                 debugln!("usage::needs_flags_tag:iter:iter: grp_s={};", grp_s);
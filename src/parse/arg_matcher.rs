@@ -7,8 +7,8 @@ use std::mem;
 use indexmap;
 
 // Internal
-use crate::build::{Arg, ArgSettings};
-use crate::parse::{ArgMatches, MatchedArg, SubCommand};
+use crate::build::{App, Arg, ArgSettings};
+use crate::parse::{ArgMatches, MatchedArg, SubCommand, ValueSource};
 
 type Id = u64;
 
@@ -23,6 +23,35 @@ impl Default for ArgMatcher {
 impl ArgMatcher {
     pub fn new() -> Self { ArgMatcher::default() }
 
+    // Remembers which args, groups and flags `app` declares so ArgMatches::is_valid_arg can tell
+    // a typo from an absent arg and ArgMatches::get_flag can catch misuse, along with which args
+    // override or conflict with each other for ArgMatches::overlay, `app` must already be built
+    pub fn for_app(app: &App) -> Self {
+        let mut am = ArgMatcher::new();
+        am.0.declared = app
+            .args
+            .args
+            .iter()
+            .map(|a| a.id)
+            .chain(app.groups.iter().map(|g| g.id))
+            .collect();
+        am.0.declared_flags = app
+            .args
+            .args
+            .iter()
+            .filter(|a| !a.is_set(ArgSettings::TakesValue) && a.index.is_none())
+            .map(|a| a.id)
+            .collect();
+        for a in &app.args.args {
+            let others = a.overrides.iter().chain(a.blacklist.iter()).flatten();
+            for &o in others.filter(|&&o| o != a.id) {
+                am.0.exclusive.push((a.id, o));
+                am.0.exclusive.push((o, a.id));
+            }
+        }
+        am
+    }
+
     pub fn into_inner(self) -> ArgMatches { self.0 }
 
     #[allow(dead_code)]
@@ -67,6 +96,11 @@ impl ArgMatcher {
             am.fill_in_global_values(global_arg_vec, vals_map);
             mem::swap(&mut am.0, &mut sc.matches);
         }
+        for sc in &mut self.0.repeated_subcommands {
+            let mut am = ArgMatcher(mem::replace(&mut sc.matches, ArgMatches::new()));
+            am.fill_in_global_values(global_arg_vec, vals_map);
+            mem::swap(&mut am.0, &mut sc.matches);
+        }
 
         for (&name, matched_arg) in vals_map.iter_mut() {
             self.0.args.insert(name, matched_arg.clone());
@@ -79,6 +113,23 @@ impl ArgMatcher {
 
     pub fn remove(&mut self, arg: Id) { self.0.args.swap_remove(&arg); }
 
+    pub fn add_override(&mut self, arg: Id, by: &str) {
+        self.0.overridden.insert(arg, by.to_owned());
+    }
+
+    pub fn add_unknown(&mut self, arg: &OsStr) { self.0.unknown.push(arg.to_os_string()); }
+
+    pub fn add_name(&mut self, arg: Id, name: &str) {
+        self.0.names.insert(arg, name.to_owned());
+    }
+
+    pub fn add_group_member(&mut self, group: Id, member: &str) {
+        self.0
+            .group_members
+            .entry(group)
+            .or_insert_with(|| member.to_owned());
+    }
+
     #[allow(dead_code)]
     pub fn remove_all(&mut self, args: &[Id]) {
         for arg in args {
@@ -98,7 +149,18 @@ impl ArgMatcher {
         self.0.args.entry(arg)
     }
 
-    pub fn subcommand(&mut self, sc: SubCommand) { self.0.subcommand = Some(Box::new(sc)); }
+    pub fn subcommand(&mut self, sc: SubCommand) {
+        if self.0.subcommand.is_some() {
+            self.0.repeated_subcommands.push(sc);
+        } else {
+            self.0.subcommand = Some(Box::new(sc));
+        }
+    }
+
+    pub fn has_subcommand(&self, id: Id) -> bool {
+        self.0.subcommand.iter().any(|sc| sc.id == id)
+            || self.0.repeated_subcommands.iter().any(|sc| sc.id == id)
+    }
 
     pub fn subcommand_name(&self) -> Option<&str> { self.0.subcommand_name() }
 
@@ -119,6 +181,8 @@ impl ArgMatcher {
             occurs: 0, // @TODO @question Shouldn't this be 1 if we're already adding a value to this arg?
             indices: Vec::with_capacity(1),
             vals: Vec::with_capacity(1),
+            source: ValueSource::CommandLine,
+            stdin: false,
         });
         ma.vals.push(val.to_owned());
     }
@@ -128,16 +192,32 @@ impl ArgMatcher {
             occurs: 0,
             indices: Vec::with_capacity(1),
             vals: Vec::new(),
+            source: ValueSource::CommandLine,
+            stdin: false,
         });
         ma.indices.push(idx);
     }
 
+    pub fn set_stdin(&mut self, arg: Id) {
+        if let Some(ma) = self.get_mut(arg) {
+            ma.stdin = true;
+        }
+    }
+
+    pub fn set_source(&mut self, arg: Id, source: ValueSource) {
+        if let Some(ma) = self.get_mut(arg) {
+            ma.source = source;
+        }
+    }
+
     pub fn needs_more_vals(&self, o: &Arg) -> bool {
         debugln!("ArgMatcher::needs_more_vals: o={}", o.name);
         if let Some(ma) = self.get(o.id) {
             if let Some(num) = o.num_vals {
                 debugln!("ArgMatcher::needs_more_vals: num_vals...{}", num);
-                return if o.is_set(ArgSettings::MultipleValues) {
+                return if o.is_set(ArgSettings::MultipleValues)
+                    || o.is_set(ArgSettings::MultipleOccurrences)
+                {
                     ((ma.vals.len() as u64) % num) != 0
                 } else {
                     num != (ma.vals.len() as u64)
@@ -6,11 +6,13 @@ use std::fmt::Display;
 use std::io::{self, Write};
 use std::process;
 use std::result::Result as StdResult;
+use std::string::FromUtf8Error;
 
 // Internal
 use crate::build::{Arg, ArgGroup};
-use crate::output::fmt::{ColorWhen, Colorizer, ColorizerOption};
+use crate::output::fmt::{ColorSpec, ColorWhen, Colorizer, ColorizerOption};
 use crate::parse::features::suggestions;
+use crate::util::collate;
 
 /// Short hand for [`Result`] type
 ///
@@ -18,6 +20,33 @@ use crate::parse::features::suggestions;
 pub type Result<T> = StdResult<T, Error>;
 
 /// Command line argument parser kind of error
+///
+/// Every [`Error`] carries one in its `kind` field, so callers of [`App::try_get_matches`] can
+/// branch on what went wrong. [`ErrorKind::HelpDisplayed`], [`ErrorKind::VersionDisplayed`] and
+/// [`ErrorKind::CompletionsDisplayed`] aren't failures, but requests by the user which
+/// [`Error::exit`] handles by printing to `stdout` and exiting with a status of `0`; every other
+/// kind exits with a status of `1` (see [`Error::exit_code`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind};
+/// let res = App::new("prog")
+///     .arg(Arg::with_name("input").required(true))
+///     .try_get_matches_from(vec!["prog", "--help"]);
+/// match res {
+///     Ok(_) => {}
+///     Err(ref e) if e.kind == ErrorKind::HelpDisplayed => {} // nothing to handle
+///     Err(e) => panic!("failed: {}", e),
+/// }
+/// ```
+/// [`Error`]: ./struct.Error.html
+/// [`App::try_get_matches`]: ./struct.App.html#method.try_get_matches
+/// [`ErrorKind::HelpDisplayed`]: ./enum.ErrorKind.html#variant.HelpDisplayed
+/// [`ErrorKind::VersionDisplayed`]: ./enum.ErrorKind.html#variant.VersionDisplayed
+/// [`ErrorKind::CompletionsDisplayed`]: ./enum.ErrorKind.html#variant.CompletionsDisplayed
+/// [`Error::exit`]: ./struct.Error.html#method.exit
+/// [`Error::exit_code`]: ./struct.Error.html#method.exit_code
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ErrorKind {
     /// Occurs when an [`Arg`] has a set of possible values,
@@ -292,6 +321,42 @@ pub enum ErrorKind {
     /// ```
     UnexpectedMultipleUsage,
 
+    /// Occurs when the user uses an argument more times than the limit set by
+    /// [`Arg::max_occurrences`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short('v')
+    ///         .max_occurrences(3))
+    ///     .try_get_matches_from(vec!["prog", "-vvvv"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::TooManyOccurrences);
+    /// ```
+    /// [`Arg::max_occurrences`]: ./struct.Arg.html#method.max_occurrences
+    TooManyOccurrences,
+
+    /// Occurs when the user uses an argument fewer times than the limit set by
+    /// [`Arg::min_occurrences`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short('v')
+    ///         .min_occurrences(2))
+    ///     .try_get_matches_from(vec!["prog", "-v"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::TooFewOccurrences);
+    /// ```
+    /// [`Arg::min_occurrences`]: ./struct.Arg.html#method.min_occurrences
+    TooFewOccurrences,
+
     /// Occurs when the user provides a value containing invalid UTF-8 for an argument and
     /// [`AppSettings::StrictUtf8`] is set.
     ///
@@ -351,6 +416,43 @@ pub enum ErrorKind {
     /// ```
     VersionDisplayed,
 
+    /// Not a true "error" as it means the subcommand added by
+    /// [`App::gen_completions_subcommand`] was used. The message is the completion script, which
+    /// will be sent to `stdout`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .gen_completions_subcommand()
+    ///     .try_get_matches_from(vec!["prog", "completions", "fish"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::CompletionsDisplayed);
+    /// ```
+    /// [`App::gen_completions_subcommand`]: ./struct.App.html#method.gen_completions_subcommand
+    CompletionsDisplayed,
+
+    /// Occurs when [`AppSettings::CollectErrors`] is set and parsing found any errors. The
+    /// message lists all of them, and they're available one by one in [`Error::errors`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .setting(AppSettings::CollectErrors)
+    ///     .arg(Arg::with_name("debug").long("debug"))
+    ///     .try_get_matches_from(vec!["prog", "--verbose", "--quiet"]);
+    /// assert!(result.is_err());
+    /// let err = result.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    /// assert_eq!(err.errors.len(), 2);
+    /// ```
+    /// [`AppSettings::CollectErrors`]: ./enum.AppSettings.html#variant.CollectErrors
+    /// [`Error::errors`]: ./struct.Error.html#structfield.errors
+    MultipleErrors,
+
     /// Occurs when using the [`value_t!`] and [`values_t!`] macros to convert an argument value
     /// into type `T`, but the argument you requested wasn't used. I.e. you asked for an argument
     /// with name `config` to be converted, but `config` wasn't used by the user.
@@ -380,26 +482,64 @@ pub struct Error {
     pub kind: ErrorKind,
     /// Any additional information passed along, such as the argument name that caused the error
     pub info: Option<Vec<String>>,
+    /// The errors reported together by an [`ErrorKind::MultipleErrors`] error, empty for every
+    /// other kind
+    ///
+    /// [`ErrorKind::MultipleErrors`]: ./enum.ErrorKind.html#variant.MultipleErrors
+    pub errors: Vec<Error>,
 }
 
 impl Error {
-    /// Should the message be written to `stdout` or not
+    /// Should the message be written to `stderr` or not. Help and version messages the user asked
+    /// for (i.e. `--help`, `--version` or the `help` subcommand, or no subcommand with
+    /// [`AppSettings::SubcommandRequiredElseHelp`]) and completion scripts go to `stdout`;
+    /// everything else, including help shown because of [`AppSettings::ArgRequiredElseHelp`],
+    /// goes to `stderr`.
+    ///
+    /// [`AppSettings::ArgRequiredElseHelp`]: ./enum.AppSettings.html#variant.ArgRequiredElseHelp
+    /// [`AppSettings::SubcommandRequiredElseHelp`]: ./enum.AppSettings.html#variant.SubcommandRequiredElseHelp
     pub fn use_stderr(&self) -> bool {
         match self.kind {
-            ErrorKind::HelpDisplayed | ErrorKind::VersionDisplayed => false,
+            ErrorKind::HelpDisplayed
+            | ErrorKind::VersionDisplayed
+            | ErrorKind::CompletionsDisplayed => false,
             _ => true,
         }
     }
 
-    /// Prints the error to `stderr` and exits with a status of `1`
+    /// Prints the error to `stderr` and exits with a status of `1`, or for help and version
+    /// messages prints to `stdout` and exits with a status of `0` (see [`Error::use_stderr`])
+    ///
+    /// [`Error::use_stderr`]: ./struct.Error.html#method.use_stderr
     pub fn exit(&self) -> ! {
         if self.use_stderr() {
             wlnerr!("{}", self.message);
-            process::exit(1);
+            process::exit(self.exit_code());
         }
         let out = io::stdout();
         writeln!(&mut out.lock(), "{}", self.message).expect("Error writing Error to stdout");
-        process::exit(0);
+        process::exit(self.exit_code());
+    }
+
+    /// The status [`Error::exit`] exits with: `0` for help and version messages and completion
+    /// scripts, `1` for everything else
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let err = App::new("prog")
+    ///     .try_get_matches_from(vec!["prog", "--unknown"])
+    ///     .unwrap_err();
+    /// assert_eq!(err.exit_code(), 1);
+    /// ```
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    pub fn exit_code(&self) -> i32 {
+        if self.use_stderr() {
+            1
+        } else {
+            0
+        }
     }
 
     #[doc(hidden)]
@@ -410,17 +550,14 @@ impl Error {
         group: &ArgGroup,
         other: Option<O>,
         usage: U,
-        color: ColorWhen,
+        color: ColorSpec,
     ) -> Self
     where
         O: Into<String>,
         U: Display,
     {
         let mut v = vec![group.name.to_owned()];
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The argument '{}' cannot be used with {}\n\n\
@@ -441,19 +578,17 @@ impl Error {
             ),
             kind: ErrorKind::ArgumentConflict,
             info: Some(v),
+            errors: vec![],
         }
     }
     #[doc(hidden)]
-    pub fn argument_conflict<O, U>(arg: &Arg, other: Option<O>, usage: U, color: ColorWhen) -> Self
+    pub fn argument_conflict<O, U>(arg: &Arg, other: Option<O>, usage: U, color: ColorSpec) -> Self
     where
         O: Into<String>,
         U: Display,
     {
         let mut v = vec![arg.name.to_owned()];
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The argument '{}' cannot be used with {}\n\n\
@@ -474,18 +609,16 @@ impl Error {
             ),
             kind: ErrorKind::ArgumentConflict,
             info: Some(v),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn empty_value<U>(arg: &Arg, usage: U, color: ColorWhen) -> Self
+    pub fn empty_value<U>(arg: &Arg, usage: U, color: ColorSpec) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The argument '{}' requires a value but none was supplied\
@@ -499,6 +632,7 @@ impl Error {
             ),
             kind: ErrorKind::EmptyValue,
             info: Some(vec![arg.name.to_owned()]),
+            errors: vec![],
         }
     }
 
@@ -508,26 +642,23 @@ impl Error {
         good_vals: &[G],
         arg: &Arg,
         usage: U,
-        color: ColorWhen,
+        color: ColorSpec,
     ) -> Self
     where
         B: AsRef<str>,
         G: AsRef<str> + Display,
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         let suffix = suggestions::did_you_mean_value_suffix(bad_val.as_ref(), good_vals.iter());
 
-        let mut sorted = vec![];
-        for v in good_vals {
-            let val = format!("{}", c.good(v));
-            sorted.push(val);
-        }
-        sorted.sort();
-        let valid_values = sorted.join(", ");
+        let mut sorted: Vec<&str> = good_vals.iter().map(AsRef::as_ref).collect();
+        sorted.sort_by(|a, b| collate(a, b));
+        let valid_values = sorted
+            .iter()
+            .map(|v| format!("{}", c.good(v)))
+            .collect::<Vec<_>>()
+            .join(", ");
         Error {
             message: format!(
                 "{} '{}' isn't a valid value for '{}'\n\t\
@@ -545,6 +676,7 @@ impl Error {
             ),
             kind: ErrorKind::InvalidValue,
             info: Some(vec![arg.name.to_owned(), bad_val.as_ref().to_owned()]),
+            errors: vec![],
         }
     }
 
@@ -554,7 +686,7 @@ impl Error {
         did_you_mean: D,
         name: N,
         usage: U,
-        color: ColorWhen,
+        color: ColorSpec,
     ) -> Self
     where
         S: Into<String>,
@@ -563,10 +695,7 @@ impl Error {
         U: Display,
     {
         let s = subcmd.into();
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The subcommand '{}' wasn't recognized\n\t\
@@ -586,20 +715,18 @@ impl Error {
             ),
             kind: ErrorKind::InvalidSubcommand,
             info: Some(vec![s]),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn unrecognized_subcommand<S, N>(subcmd: S, name: N, color: ColorWhen) -> Self
+    pub fn unrecognized_subcommand<S, N>(subcmd: S, name: N, color: ColorSpec) -> Self
     where
         S: Into<String>,
         N: Display,
     {
         let s = subcmd.into();
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The subcommand '{}' wasn't recognized\n\n\
@@ -614,19 +741,17 @@ impl Error {
             ),
             kind: ErrorKind::UnrecognizedSubcommand,
             info: Some(vec![s]),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn missing_required_argument<R, U>(required: R, usage: U, color: ColorWhen) -> Self
+    pub fn missing_required_argument<R, U>(required: R, usage: U, color: ColorSpec) -> Self
     where
         R: Display,
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The following required arguments were not provided:{}\n\n\
@@ -639,19 +764,17 @@ impl Error {
             ),
             kind: ErrorKind::MissingRequiredArgument,
             info: None,
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn missing_subcommand<N, U>(name: N, usage: U, color: ColorWhen) -> Self
+    pub fn missing_subcommand<N, U>(name: N, usage: U, color: ColorSpec) -> Self
     where
         N: AsRef<str> + Display,
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} '{}' requires a subcommand, but one was not provided\n\n\
@@ -664,18 +787,16 @@ impl Error {
             ),
             kind: ErrorKind::MissingSubcommand,
             info: None,
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn invalid_utf8<U>(usage: U, color: ColorWhen) -> Self
+    pub fn invalid_utf8<U>(usage: U, color: ColorSpec) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} Invalid UTF-8 was detected in one or more arguments\n\n\
@@ -687,20 +808,18 @@ impl Error {
             ),
             kind: ErrorKind::InvalidUtf8,
             info: None,
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn too_many_values<V, U>(val: V, arg: &Arg, usage: U, color: ColorWhen) -> Self
+    pub fn too_many_values<V, U>(val: V, arg: &Arg, usage: U, color: ColorSpec) -> Self
     where
         V: AsRef<str> + Display + ToOwned,
         U: Display,
     {
         let v = val.as_ref();
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The value '{}' was provided to '{}', but it wasn't expecting \
@@ -715,6 +834,7 @@ impl Error {
             ),
             kind: ErrorKind::TooManyValues,
             info: Some(vec![arg.name.to_owned(), v.to_owned()]),
+            errors: vec![],
         }
     }
 
@@ -724,15 +844,12 @@ impl Error {
         min_vals: u64,
         curr_vals: usize,
         usage: U,
-        color: ColorWhen,
+        color: ColorSpec,
     ) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The argument '{}' requires at least {} values, but only {} w{} \
@@ -749,15 +866,13 @@ impl Error {
             ),
             kind: ErrorKind::TooFewValues,
             info: Some(vec![arg.name.to_owned()]),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn value_validation(arg: Option<&Arg>, err: &str, color: ColorWhen) -> Self {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+    pub fn value_validation(arg: Option<&Arg>, err: &str, color: ColorSpec) -> Self {
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} Invalid value{}: {}",
@@ -771,13 +886,14 @@ impl Error {
             ),
             kind: ErrorKind::ValueValidation,
             info: None,
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
     pub fn value_validation_auto(err: &str) -> Self {
         let n: Option<&Arg> = None;
-        Error::value_validation(n, err, ColorWhen::Auto)
+        Error::value_validation(n, err, ColorWhen::Auto.into())
     }
 
     #[doc(hidden)]
@@ -787,16 +903,13 @@ impl Error {
         curr_vals: usize,
         suffix: S,
         usage: U,
-        color: ColorWhen,
+        color: ColorSpec,
     ) -> Self
     where
         S: Display,
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The argument '{}' requires {} values, but {} w{} \
@@ -813,18 +926,16 @@ impl Error {
             ),
             kind: ErrorKind::WrongNumberOfValues,
             info: Some(vec![arg.name.to_owned()]),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn unexpected_multiple_usage<U>(arg: &Arg, usage: U, color: ColorWhen) -> Self
+    pub fn unexpected_multiple_usage<U>(arg: &Arg, usage: U, color: ColorSpec) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!(
                 "{} The argument '{}' was provided more than once, but cannot \
@@ -838,20 +949,82 @@ impl Error {
             ),
             kind: ErrorKind::UnexpectedMultipleUsage,
             info: Some(vec![arg.name.to_owned()]),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn unknown_argument<A, U>(arg: A, did_you_mean: Option<String>, usage: U, color: ColorWhen) -> Self
+    pub fn too_many_occurrences<U>(
+        arg: &Arg,
+        max_occurs: u64,
+        curr_occurs: u64,
+        usage: U,
+        color: ColorSpec,
+    ) -> Self
+    where
+        U: Display,
+    {
+        let c = Colorizer::new(&ColorizerOption::from(color));
+        Error {
+            message: format!(
+                "{} The argument '{}' can be used at most {} time{}, but was used {} times\n\n\
+                 {}\n\n\
+                 For more information try {}",
+                c.error("error:"),
+                c.warning(arg.to_string()),
+                c.warning(max_occurs.to_string()),
+                if max_occurs == 1 { "" } else { "s" },
+                c.warning(curr_occurs.to_string()),
+                usage,
+                c.good("--help")
+            ),
+            kind: ErrorKind::TooManyOccurrences,
+            info: Some(vec![arg.name.to_owned()]),
+            errors: vec![],
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn too_few_occurrences<U>(
+        arg: &Arg,
+        min_occurs: u64,
+        curr_occurs: u64,
+        usage: U,
+        color: ColorSpec,
+    ) -> Self
+    where
+        U: Display,
+    {
+        let c = Colorizer::new(&ColorizerOption::from(color));
+        Error {
+            message: format!(
+                "{} The argument '{}' must be used at least {} time{}, but was only used {} \
+                 time{}\n\n\
+                 {}\n\n\
+                 For more information try {}",
+                c.error("error:"),
+                c.warning(arg.to_string()),
+                c.warning(min_occurs.to_string()),
+                if min_occurs == 1 { "" } else { "s" },
+                c.warning(curr_occurs.to_string()),
+                if curr_occurs == 1 { "" } else { "s" },
+                usage,
+                c.good("--help")
+            ),
+            kind: ErrorKind::TooFewOccurrences,
+            info: Some(vec![arg.name.to_owned()]),
+            errors: vec![],
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn unknown_argument<A, U>(arg: A, did_you_mean: Option<String>, usage: U, color: ColorSpec) -> Self
     where
         A: Into<String>,
         U: Display,
     {
         let a = arg.into();
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::new(&ColorizerOption::from(color));
 
         let suggest_pattern = format!("If you tried to supply `{}` as a PATTERN use `-- {}`", a, a);
 
@@ -876,19 +1049,18 @@ impl Error {
             ),
             kind: ErrorKind::UnknownArgument,
             info: Some(vec![a]),
+            errors: vec![],
         }
     }
 
     #[doc(hidden)]
-    pub fn io_error(e: &Error, color: ColorWhen) -> Self {
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+    pub fn io_error(e: &Error, color: ColorSpec) -> Self {
+        let c = Colorizer::new(&ColorizerOption::from(color));
         Error {
             message: format!("{} {}", c.error("error:"), e.description()),
             kind: ErrorKind::Io,
             info: None,
+            errors: vec![],
         }
     }
 
@@ -901,6 +1073,7 @@ impl Error {
         let c = Colorizer::new(&ColorizerOption {
             use_stderr: true,
             when: ColorWhen::Auto,
+            ..Default::default()
         });
         Error {
             message: format!(
@@ -910,6 +1083,21 @@ impl Error {
             ),
             kind: ErrorKind::ArgumentNotFound,
             info: Some(vec![a]),
+            errors: vec![],
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn multiple(errors: Vec<Error>) -> Self {
+        Error {
+            message: errors
+                .iter()
+                .map(|e| &*e.message)
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            kind: ErrorKind::MultipleErrors,
+            info: None,
+            errors,
         }
     }
 
@@ -921,11 +1109,13 @@ impl Error {
         let c = Colorizer::new(&ColorizerOption {
             use_stderr: true,
             when: ColorWhen::Auto,
+            ..Default::default()
         });
         Error {
             message: format!("{} {}", c.error("error:"), description),
             kind,
             info: None,
+            errors: vec![],
         }
     }
 }
@@ -947,3 +1137,9 @@ impl From<std_fmt::Error> for Error {
         Error::with_description(e.description(), ErrorKind::Format)
     }
 }
+
+impl From<FromUtf8Error> for Error {
+    fn from(e: FromUtf8Error) -> Self {
+        Error::with_description(e.description(), ErrorKind::InvalidUtf8)
+    }
+}
@@ -1 +1,2 @@
+pub mod response_files;
 pub mod suggestions;
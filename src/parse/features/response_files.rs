@@ -0,0 +1,119 @@
+// Std
+use std::ffi::OsString;
+use std::fs;
+use std::mem;
+use std::path::PathBuf;
+
+// Internal
+use crate::parse::errors::{Error, ErrorKind, Result as ClapResult};
+
+/// Expands every argument of the form `@file` into the arguments read from `file` (see
+/// `AppSettings::ExpandArgsFromFile`). Nothing after a `--` is expanded.
+pub fn expand_args<I, T>(args: I) -> ClapResult<Vec<OsString>>
+where
+    I: Iterator<Item = T>,
+    T: Into<OsString>,
+{
+    let mut expanded = vec![];
+    let mut files = vec![];
+    let mut trailing = false;
+    for arg in args {
+        expand_arg(arg.into(), &mut expanded, &mut files, &mut trailing)?;
+    }
+    Ok(expanded)
+}
+
+// `files` holds the response files currently being read, so one naming itself can be caught
+fn expand_arg(
+    arg: OsString,
+    out: &mut Vec<OsString>,
+    files: &mut Vec<PathBuf>,
+    trailing: &mut bool,
+) -> ClapResult<()> {
+    let path = match arg.to_str() {
+        Some(s) if !*trailing && s.len() > 1 && s.starts_with('@') => PathBuf::from(&s[1..]),
+        _ => {
+            if arg == "--" {
+                *trailing = true;
+            }
+            out.push(arg);
+            return Ok(());
+        }
+    };
+    debugln!("response_files::expand_arg: path={:?}", path);
+
+    // The same file may be named through different paths
+    let key = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    if files.contains(&key) {
+        return Err(Error::with_description(
+            &*format!("The arguments file '{}' includes itself", path.display()),
+            ErrorKind::Format,
+        ));
+    }
+    let contents = fs::read_to_string(&path).map_err(|e| {
+        Error::with_description(
+            &*format!("Couldn't read arguments from '{}': {}", path.display(), e),
+            ErrorKind::Io,
+        )
+    })?;
+
+    files.push(key);
+    for a in split_args(&*contents) {
+        expand_arg(OsString::from(a), out, files, trailing)?;
+    }
+    files.pop();
+    Ok(())
+}
+
+// Splits on whitespace, except inside single or double quotes which are removed
+fn split_args(s: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut cur = String::new();
+    let mut in_arg = false;
+    let mut quote = None;
+    for c in s.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => cur.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(mem::replace(&mut cur, String::new()));
+                    in_arg = false;
+                }
+            }
+            None => {
+                cur.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(cur);
+    }
+    args
+}
+
+#[cfg(test)]
+mod test {
+    use super::split_args;
+
+    #[test]
+    fn split_whitespace_and_newlines() {
+        assert_eq!(
+            split_args("  -a  --bee\n\tcee\r\n"),
+            vec!["-a", "--bee", "cee"]
+        );
+    }
+
+    #[test]
+    fn split_quoted() {
+        assert_eq!(
+            split_args("--name \"two words\" 'it''s' \"\""),
+            vec!["--name", "two words", "its", ""]
+        );
+    }
+}
@@ -52,7 +52,7 @@ where
     match did_you_mean(arg, longs) {
         Some(ref candidate) => {
             let suffix = format!(
-                "\n\tDid you mean {}{}?",
+                "\n\tDid you mean `{}{}`?",
                 Format::Good("--"),
                 Format::Good(candidate)
             );
@@ -63,7 +63,7 @@ where
                 subcommand._build();
                 if let Some(ref candidate) = did_you_mean(
                     arg,
-                    longs!(subcommand).map(|x| x.to_string_lossy().into_owned()),
+                    visible_longs!(subcommand).map(|x| x.to_string_lossy().into_owned()),
                 ) {
                     let suffix = format!(
                         "\n\tDid you mean to put '{}{}' after the subcommand '{}'?",
@@ -113,7 +113,7 @@ mod test {
     #[test]
     fn suffix_long() {
         let p_vals = ["test", "possible", "values"];
-        let suffix = "\n\tDid you mean \'--test\'?";
+        let suffix = "\n\tDid you mean `--test`?";
         assert_eq!(
             did_you_mean_flag_suffix("tst", p_vals.iter(), []),
             (suffix, Some("test"))
@@ -1,14 +1,20 @@
 // Std
 use std::borrow::Cow;
 use std::ffi::{OsStr, OsString};
+use std::fmt::{Debug, Display};
+use std::fs::File;
+use std::io::{self, Read};
 use std::iter::{Cloned, Map};
 use std::slice::Iter;
+use std::str::FromStr;
+use std::vec::IntoIter;
 
 // Third Party
 use indexmap::IndexMap;
 
 // Internal
-use crate::parse::{MatchedArg, SubCommand};
+use crate::parse::errors::Error;
+use crate::parse::{MatchedArg, SubCommand, ValueSource};
 use crate::util::Key;
 use crate::INVALID_UTF8;
 
@@ -67,6 +73,22 @@ pub struct ArgMatches {
     pub args: IndexMap<Id, MatchedArg>,
     #[doc(hidden)]
     pub subcommand: Option<Box<SubCommand>>,
+    #[doc(hidden)]
+    pub repeated_subcommands: Vec<SubCommand>,
+    #[doc(hidden)]
+    pub overridden: IndexMap<Id, String>,
+    #[doc(hidden)]
+    pub group_members: IndexMap<Id, String>,
+    #[doc(hidden)]
+    pub names: IndexMap<Id, String>,
+    #[doc(hidden)]
+    pub declared: Vec<Id>,
+    #[doc(hidden)]
+    pub declared_flags: Vec<Id>,
+    #[doc(hidden)]
+    pub exclusive: Vec<(Id, Id)>,
+    #[doc(hidden)]
+    pub unknown: Vec<OsString>,
 }
 
 impl<'a> Default for ArgMatches {
@@ -74,6 +96,14 @@ impl<'a> Default for ArgMatches {
         ArgMatches {
             args: IndexMap::new(),
             subcommand: None,
+            repeated_subcommands: Vec::new(),
+            overridden: IndexMap::new(),
+            group_members: IndexMap::new(),
+            names: IndexMap::new(),
+            declared: Vec::new(),
+            declared_flags: Vec::new(),
+            exclusive: Vec::new(),
+            unknown: Vec::new(),
         }
     }
 }
@@ -96,7 +126,9 @@ impl ArgMatches {
     ///
     /// # Panics
     ///
-    /// This method will [`panic!`] if the value contains invalid UTF-8 code points.
+    /// This method will [`panic!`] if the value contains invalid UTF-8 code points. Values which
+    /// may not be valid UTF-8, such as paths, should use [`ArgMatches::value_of_os`] to get the
+    /// raw value or [`ArgMatches::value_of_lossy`] to replace the invalid code points instead.
     ///
     /// # Examples
     ///
@@ -112,6 +144,8 @@ impl ArgMatches {
     /// [option]: ./struct.Arg.html#method.takes_value
     /// [positional]: ./struct.Arg.html#method.index
     /// [`ArgMatches::values_of`]: ./struct.ArgMatches.html#method.values_of
+    /// [`ArgMatches::value_of_os`]: ./struct.ArgMatches.html#method.value_of_os
+    /// [`ArgMatches::value_of_lossy`]: ./struct.ArgMatches.html#method.value_of_lossy
     /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
     pub fn value_of<T: Key>(&self, id: T) -> Option<&str> {
         if let Some(arg) = self.args.get(&id.key()) {
@@ -187,13 +221,48 @@ impl ArgMatches {
             .and_then(|arg| arg.vals.get(0).map(OsString::as_os_str))
     }
 
+    /// Opens the first value of an argument for reading. For an argument set to
+    /// [`Arg::allow_stdin_dash`] a value of `-` reads from stdin, any other value is opened as a
+    /// file. If the argument wasn't present at runtime it returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// use std::io::Read;
+    ///
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("input")
+    ///         .allow_stdin_dash(true))
+    ///     .get_matches_from(vec!["myprog", "-"]);
+    ///
+    /// let mut input = String::new();
+    /// m.value_reader("input")
+    ///     .unwrap()
+    ///     .expect("failed to open input")
+    ///     .read_to_string(&mut input)
+    ///     .expect("failed to read input");
+    /// ```
+    /// [`Arg::allow_stdin_dash`]: ./struct.Arg.html#method.allow_stdin_dash
+    pub fn value_reader<T: Key>(&self, id: T) -> Option<io::Result<Box<dyn Read>>> {
+        let arg = self.args.get(&id.key())?;
+        let val = arg.vals.get(0)?;
+        if arg.stdin && val == "-" {
+            Some(Ok(Box::new(io::stdin())))
+        } else {
+            Some(File::open(val).map(|f| -> Box<dyn Read> { Box::new(f) }))
+        }
+    }
+
     /// Gets a [`Values`] struct which implements [`Iterator`] for values of a specific argument
     /// (i.e. an argument that takes multiple values at runtime). If the option wasn't present at
     /// runtime it returns `None`
     ///
     /// # Panics
     ///
-    /// This method will panic if any of the values contain invalid UTF-8 code points.
+    /// This method will panic if any of the values contain invalid UTF-8 code points. Use
+    /// [`ArgMatches::values_of_os`] or [`ArgMatches::values_of_lossy`] for values which may not be
+    /// valid UTF-8.
     ///
     /// # Examples
     ///
@@ -212,6 +281,8 @@ impl ArgMatches {
     /// ```
     /// [`Values`]: ./struct.Values.html
     /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`ArgMatches::values_of_os`]: ./struct.ArgMatches.html#method.values_of_os
+    /// [`ArgMatches::values_of_lossy`]: ./struct.ArgMatches.html#method.values_of_lossy
     pub fn values_of<T: Key>(&self, id: T) -> Option<Values<'_>> {
         self.args.get(&id.key()).map(|arg| {
             fn to_str_slice(o: &OsString) -> &str { o.to_str().expect(INVALID_UTF8) }
@@ -296,6 +367,139 @@ impl ArgMatches {
         })
     }
 
+    /// Gets the value of a specific argument parsed as type `R` using its [`FromStr`]
+    /// implementation. If the argument wasn't present at runtime, or its value couldn't be
+    /// parsed, a clap [`Error`] naming the argument and the offending value is returned.
+    ///
+    /// *NOTE:* Like [`ArgMatches::value_of`] this only looks at the *first* value, for arguments
+    /// that allow multiples prefer [`ArgMatches::values_of_t`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["myapp", "--port", "8080"]);
+    ///
+    /// let port: u32 = m.value_of_t("port").unwrap();
+    /// assert_eq!(port, 8080);
+    ///
+    /// let err = m.value_of_t::<bool>("port").unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`Error`]: ./struct.Error.html
+    /// [`ArgMatches::value_of`]: ./struct.ArgMatches.html#method.value_of
+    /// [`ArgMatches::values_of_t`]: ./struct.ArgMatches.html#method.values_of_t
+    pub fn value_of_t<R>(&self, name: &str) -> Result<R, Error>
+    where
+        R: FromStr,
+        <R as FromStr>::Err: Display,
+    {
+        if let Some(v) = self.value_of(name) {
+            v.parse::<R>().map_err(|e| {
+                Error::value_validation_auto(&format!(
+                    "The value '{}' for '{}' couldn't be parsed: {}",
+                    v, name, e
+                ))
+            })
+        } else {
+            Err(Error::argument_not_found_auto(name))
+        }
+    }
+
+    /// Works like [`ArgMatches::value_of_t`] but prints the error and exits the process, as clap
+    /// does for its own errors, instead of returning a [`Result`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["myapp", "--port", "8080"]);
+    ///
+    /// let port: u32 = m.value_of_t_or_exit("port");
+    /// assert_eq!(port, 8080);
+    /// ```
+    /// [`ArgMatches::value_of_t`]: ./struct.ArgMatches.html#method.value_of_t
+    /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+    pub fn value_of_t_or_exit<R>(&self, name: &str) -> R
+    where
+        R: FromStr,
+        <R as FromStr>::Err: Display,
+    {
+        self.value_of_t(name).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Gets all values of a specific argument parsed as type `R` using its [`FromStr`]
+    /// implementation. If the argument wasn't present at runtime, or any of its values couldn't
+    /// be parsed, a clap [`Error`] naming the argument and the offending value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("seq")
+    ///         .multiple(true))
+    ///     .get_matches_from(vec!["myapp", "1", "2", "3"]);
+    ///
+    /// let vals: Vec<u32> = m.values_of_t("seq").unwrap();
+    /// assert_eq!(vals, [1, 2, 3]);
+    /// ```
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`Error`]: ./struct.Error.html
+    pub fn values_of_t<R>(&self, name: &str) -> Result<Vec<R>, Error>
+    where
+        R: FromStr,
+        <R as FromStr>::Err: Display,
+    {
+        if let Some(vals) = self.values_of(name) {
+            vals.map(|v| {
+                v.parse::<R>().map_err(|e| {
+                    Error::value_validation_auto(&format!(
+                        "The value '{}' for '{}' couldn't be parsed: {}",
+                        v, name, e
+                    ))
+                })
+            })
+            .collect()
+        } else {
+            Err(Error::argument_not_found_auto(name))
+        }
+    }
+
+    /// Works like [`ArgMatches::values_of_t`] but prints the error and exits the process, as
+    /// clap does for its own errors, instead of returning a [`Result`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("seq")
+    ///         .multiple(true))
+    ///     .get_matches_from(vec!["myapp", "1", "2", "3"]);
+    ///
+    /// let vals: Vec<u32> = m.values_of_t_or_exit("seq");
+    /// assert_eq!(vals, [1, 2, 3]);
+    /// ```
+    /// [`ArgMatches::values_of_t`]: ./struct.ArgMatches.html#method.values_of_t
+    /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+    pub fn values_of_t_or_exit<R>(&self, name: &str) -> Vec<R>
+    where
+        R: FromStr,
+        <R as FromStr>::Err: Display,
+    {
+        self.values_of_t(name).unwrap_or_else(|e| e.exit())
+    }
+
     /// Returns `true` if an argument was present at runtime, otherwise `false`.
     ///
     /// # Examples
@@ -313,6 +517,178 @@ impl ArgMatches {
     /// ```
     pub fn is_present<T: Key>(&self, id: T) -> bool { self._id_is_present(id.key()) }
 
+    /// Returns `true` if a flag was used at least once at runtime, otherwise `false`. Unlike
+    /// [`ArgMatches::is_present`] this is only meant for flags, i.e. arguments which take no
+    /// values, which makes the intent clearer at the call site.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds this method will [`panic!`] (naming `id`) if `id` isn't a flag declared on
+    /// the [`App`] these matches came from, i.e. it's an option, a positional argument, a group or
+    /// a typo.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::from("-d, --debug 'turn on debugging'"))
+    ///     .arg(Arg::from("-q, --quiet 'less output'"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-d"
+    ///     ]);
+    ///
+    /// assert!(m.get_flag("debug"));
+    /// assert!(!m.get_flag("quiet"));
+    /// ```
+    /// [`ArgMatches::is_present`]: ./struct.ArgMatches.html#method.is_present
+    /// [`App`]: ./struct.App.html
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    pub fn get_flag<T: Key + Debug>(&self, id: T) -> bool {
+        let key = id.key();
+        debug_assert!(
+            self.declared.is_empty() || self.declared_flags.contains(&key),
+            "ArgMatches::get_flag: the argument {:?} isn't a flag declared on this App",
+            id
+        );
+        self.args.get(&key).map_or(false, |a| a.occurs > 0)
+    }
+
+    /// Returns `true` if an argument or [`ArgGroup`] named `id` was declared on the [`App`]
+    /// these matches came from, whether or not it was used at runtime. Methods like
+    /// [`ArgMatches::value_of`] can't tell an absent argument from a misspelled one, so this is
+    /// useful to check names which are only known at runtime.
+    ///
+    /// **NOTE:** Only the arguments of this level are known, arguments of subcommands must be
+    /// checked on the subcommand's matches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::from("-d, --debug 'turn on debugging'"))
+    ///     .get_matches_from(vec!["myprog"]);
+    ///
+    /// assert!(!m.is_present("debug"));
+    /// assert!(m.is_valid_arg("debug"));
+    /// assert!(!m.is_valid_arg("dbug"));
+    /// ```
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    /// [`App`]: ./struct.App.html
+    /// [`ArgMatches::value_of`]: ./struct.ArgMatches.html#method.value_of
+    pub fn is_valid_arg<T: Key>(&self, id: T) -> bool { self.declared.contains(&id.key()) }
+
+    /// Gets the arguments no [`Arg`] matched which the [`App::on_unknown_arg`] callback chose to
+    /// [collect], in the order they were used. Without such a callback this is always empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, UnknownArgAction};
+    /// let m = App::new("proxy")
+    ///     .arg(Arg::with_name("target"))
+    ///     .on_unknown_arg(|_| UnknownArgAction::Collect)
+    ///     .get_matches_from(vec!["proxy", "--color=always", "host", "-q"]);
+    ///
+    /// assert_eq!(m.value_of("target"), Some("host"));
+    /// assert_eq!(m.unknown_args().collect::<Vec<_>>(), ["--color=always", "-q"]);
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`App::on_unknown_arg`]: ./struct.App.html#method.on_unknown_arg
+    /// [collect]: ./enum.UnknownArgAction.html#variant.Collect
+    pub fn unknown_args<'a>(&'a self) -> OsValues<'a> {
+        fn to_str_slice(o: &OsString) -> &OsStr { &*o }
+        let to_str_slice: fn(&'a OsString) -> &'a OsStr = to_str_slice; // coerce to fn pointer
+
+        OsValues {
+            iter: self.unknown.iter().map(to_str_slice),
+        }
+    }
+
+    /// Returns the names of all arguments the user actually used at runtime, in the order they
+    /// were first used. Arguments which only have a [default value] or a value from an
+    /// [environment variable] are not included, which makes this useful for only overriding
+    /// settings the user explicitly asked for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::from("-d, --debug 'turn on debugging'"))
+    ///     .arg(Arg::from("-o, --output [file] 'output file'").default_value("out.txt"))
+    ///     .arg(Arg::from("[input] 'input file'"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "in.txt", "-d"
+    ///     ]);
+    ///
+    /// assert_eq!(m.args_present(), ["input", "debug"]);
+    /// ```
+    /// [default value]: ./struct.Arg.html#method.default_value
+    /// [environment variable]: ./struct.Arg.html#method.env
+    pub fn args_present(&self) -> Vec<&str> {
+        self.args
+            .iter()
+            .filter(|(_, ma)| ma.occurs > 0)
+            .filter_map(|(id, _)| self.names.get(id).map(String::as_str))
+            .collect()
+    }
+
+    /// Returns the name of the argument which [overrode] an argument used at runtime, or `None`
+    /// if the argument wasn't overridden. An overridden argument is removed from the matches, so
+    /// this is the only way to find out it was used at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::from("-v, --verbose 'be verbose'").overrides_with("quiet"))
+    ///     .arg(Arg::from("-q, --quiet 'be quiet'"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--verbose", "--quiet"
+    ///     ]);
+    ///
+    /// assert!(!m.is_present("verbose"));
+    /// assert_eq!(m.overridden_by("verbose"), Some("quiet"));
+    /// assert_eq!(m.overridden_by("quiet"), None);
+    /// ```
+    /// [overrode]: ./struct.Arg.html#method.overrides_with
+    pub fn overridden_by<T: Key>(&self, id: T) -> Option<&str> {
+        self.overridden.get(&id.key()).map(String::as_str)
+    }
+
+    /// Returns the name of the argument used at runtime which satisfied the [`ArgGroup`] `id`, or
+    /// `None` if no member of the group was used. If the group allows [multiple] members, the
+    /// first one used is returned.
+    ///
+    /// **NOTE:** Members which were only present due to a default value or environment variable
+    /// don't count as being chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::from("--major 'bump the major version'"))
+    ///     .arg(Arg::from("--minor 'bump the minor version'"))
+    ///     .arg(Arg::from("--patch 'bump the patch version'"))
+    ///     .group(ArgGroup::with_name("vers")
+    ///         .args(&["major", "minor", "patch"])
+    ///         .required(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--minor"
+    ///     ]);
+    ///
+    /// assert_eq!(m.group_value("vers"), Some("minor"));
+    /// ```
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    /// [multiple]: ./struct.ArgGroup.html#method.multiple
+    pub fn group_value<T: Key>(&self, id: T) -> Option<&str> {
+        self.group_members.get(&id.key()).map(String::as_str)
+    }
+
     #[doc(hidden)]
     pub fn _id_is_present(&self, arg_id: Id) -> bool {
         if let Some(ref sc) = self.subcommand {
@@ -330,6 +706,10 @@ impl ArgMatches {
     /// values. For example, `-o val1 val2 val3 -o val4` would return `2` (2 occurrences, but 4
     /// values).
     ///
+    /// **NOTE:** Every value of a positional argument is an occurrence of its own, so for
+    /// positional arguments this is the number of values supplied (i.e. `cp a b c dst` would return
+    /// `3` for the sources).
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -366,6 +746,37 @@ impl ArgMatches {
         self.args.get(&id.key()).map_or(0, |a| a.occurs)
     }
 
+    /// Gets where the values of an argument came from: the command line, an [environment
+    /// variable], a [default value] or a [conditional default value]. Returns `None` if the
+    /// argument wasn't present at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ValueSource};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .takes_value(true)
+    ///         .default_value("auto"))
+    ///     .arg(Arg::with_name("level")
+    ///         .long("level")
+    ///         .takes_value(true));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["myprog"]);
+    /// assert_eq!(m.value_source("color"), Some(ValueSource::DefaultValue));
+    /// assert_eq!(m.value_source("level"), None);
+    ///
+    /// let m = app.get_matches_from(vec!["myprog", "--color", "never"]);
+    /// assert_eq!(m.value_source("color"), Some(ValueSource::CommandLine));
+    /// ```
+    /// [environment variable]: ./struct.Arg.html#method.env
+    /// [default value]: ./struct.Arg.html#method.default_value
+    /// [conditional default value]: ./struct.Arg.html#method.default_value_if
+    pub fn value_source<T: Key>(&self, id: T) -> Option<ValueSource> {
+        self.args.get(&id.key()).map(|a| a.source)
+    }
+
     /// Gets the starting index of the argument in respect to all other arguments. Indices are
     /// similar to argv indices, but are not exactly 1:1.
     ///
@@ -584,6 +995,55 @@ impl ArgMatches {
         })
     }
 
+    /// Gets an [`Occurrences`] struct which implements [`Iterator`] over the values of several
+    /// arguments at once, as `(id, value, index)` tuples sorted by the [index] each value was
+    /// found at. This allows processing the values of different arguments in the exact order the
+    /// user supplied them, i.e. interleaving an `--include` and `--exclude` that may both be used
+    /// multiple times.
+    ///
+    /// **NOTE:** Only values are returned, so flags don't appear in the iterator. Arguments which
+    /// weren't present at runtime are skipped.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if any of the values contain invalid UTF-8 code points.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("include")
+    ///         .long("include")
+    ///         .takes_value(true)
+    ///         .multiple_occurrences(true))
+    ///     .arg(Arg::with_name("exclude")
+    ///         .long("exclude")
+    ///         .takes_value(true)
+    ///         .multiple_occurrences(true))
+    ///     .get_matches_from(vec!["myapp", "--include", "a", "--exclude", "b", "--include", "c"]);
+    ///
+    /// let order = m.occurrences_with_indices(&["include", "exclude"]).collect::<Vec<_>>();
+    /// assert_eq!(order, [("include", "a", 2), ("exclude", "b", 4), ("include", "c", 6)]);
+    /// ```
+    /// [`Occurrences`]: ./struct.Occurrences.html
+    /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [index]: ./struct.ArgMatches.html#method.index_of
+    pub fn occurrences_with_indices<T: Key + Copy>(&self, ids: &[T]) -> Occurrences<'_, T> {
+        let mut occurs = Vec::new();
+        for &id in ids {
+            if let Some(arg) = self.args.get(&id.key()) {
+                for (v, &i) in arg.vals.iter().zip(arg.indices.iter()) {
+                    occurs.push((id, v.to_str().expect(INVALID_UTF8), i));
+                }
+            }
+        }
+        occurs.sort_by_key(|&(_, _, i)| i);
+        Occurrences {
+            iter: occurs.into_iter(),
+        }
+    }
+
     /// Because [`Subcommand`]s are essentially "sub-[`App`]s" they have their own [`ArgMatches`]
     /// as well. This method returns the [`ArgMatches`] for a particular subcommand or `None` if
     /// the subcommand wasn't present at runtime.
@@ -736,6 +1196,185 @@ impl ArgMatches {
             .as_ref()
             .map_or(("", None), |sc| (&sc.name[..], Some(&sc.matches)))
     }
+
+    /// Returns the name and [`ArgMatches`] of every [``] invocation in the order they were used.
+    /// Unless [`AppSettings::AllowMultipleSubcommands`] is set this contains at most one
+    /// element, the same one returned by [`ArgMatches::subcommand`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let app_m = App::new("myprog")
+    ///     .setting(AppSettings::AllowMultipleSubcommands)
+    ///     .subcommand(App::new("push").arg(Arg::with_name("remote")))
+    ///     .get_matches_from(vec!["myprog", "push", "origin", "push", "upstream"]);
+    ///
+    /// let remotes: Vec<_> = app_m.subcommand_matches_all()
+    ///     .into_iter()
+    ///     .map(|(_, sub_m)| sub_m.value_of("remote").unwrap())
+    ///     .collect();
+    /// assert_eq!(remotes, ["origin", "upstream"]);
+    /// ```
+    /// [``]: ./struct.App.html
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`ArgMatches::subcommand`]: ./struct.ArgMatches.html#method.subcommand
+    /// [`AppSettings::AllowMultipleSubcommands`]: ./enum.AppSettings.html#variant.AllowMultipleSubcommands
+    pub fn subcommand_matches_all(&self) -> Vec<(&str, &ArgMatches)> {
+        self.subcommand
+            .iter()
+            .map(|sc| &**sc)
+            .chain(self.repeated_subcommands.iter())
+            .map(|sc| (&*sc.name, &sc.matches))
+            .collect()
+    }
+
+    /// Moves the name and [`ArgMatches`] of the [``] used out of these matches, so a subcommand
+    /// handler can own them without a clone. Afterwards these matches report no subcommand, or
+    /// with [`AppSettings::AllowMultipleSubcommands`] the next one used, so calling this
+    /// repeatedly takes every invocation in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app_m = App::new("git")
+    ///     .subcommand(App::new("clone").arg(Arg::with_name("repo")))
+    ///     .get_matches_from(vec!["git", "clone", "https://github.com/clap-rs/clap"]);
+    ///
+    /// let (name, sub_m) = app_m.remove_subcommand().unwrap();
+    /// assert_eq!(name, "clone");
+    /// assert_eq!(sub_m.value_of("repo"), Some("https://github.com/clap-rs/clap"));
+    /// assert_eq!(app_m.subcommand_name(), None);
+    /// ```
+    /// [``]: ./struct.App.html
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`AppSettings::AllowMultipleSubcommands`]: ./enum.AppSettings.html#variant.AllowMultipleSubcommands
+    pub fn remove_subcommand(&mut self) -> Option<(String, ArgMatches)> {
+        let sc = self.subcommand.take()?;
+        if !self.repeated_subcommands.is_empty() {
+            self.subcommand = Some(Box::new(self.repeated_subcommands.remove(0)));
+        }
+        let SubCommand { name, matches, .. } = *sc;
+        Some((name, matches))
+    }
+
+    /// Fills in every argument missing from these matches with the one from `lower`, including
+    /// its values and occurrence count. This layers matches with different precedences, i.e.
+    /// arguments parsed from a config file under the ones from the command line. An argument which
+    /// only has a [default value] or a value from an [environment variable] here is replaced as
+    /// well, if it was actually used in `lower`.
+    ///
+    /// An argument [overridden] here isn't filled in, it was replaced on purpose. Neither is one
+    /// which overrides or [conflicts with] an argument used here. One overridden in
+    /// `lower` is reported by [`ArgMatches::overridden_by`] unless it's present here. The
+    /// [unknown arguments] of `lower` are appended to the ones collected here.
+    ///
+    /// Subcommand matches are overlaid recursively by name. If no subcommand was used here, the
+    /// one from `lower` (if any) is taken as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::from("-j, --jobs [n] 'number of jobs'"))
+    ///     .arg(Arg::from("-o, --output [file] 'output file'").default_value("out.txt"))
+    ///     .arg(Arg::from("-v, --verbose... 'be verbose'"));
+    ///
+    /// let config = app.clone().get_matches_from(vec!["myprog", "-j", "4", "-o", "cfg.txt", "-vv"]);
+    /// let mut m = app.get_matches_from(vec!["myprog", "-j", "8"]);
+    /// m.overlay(config);
+    ///
+    /// assert_eq!(m.value_of("jobs"), Some("8"));
+    /// assert_eq!(m.value_of("output"), Some("cfg.txt"));
+    /// assert_eq!(m.occurrences_of("verbose"), 2);
+    /// ```
+    /// [default value]: ./struct.Arg.html#method.default_value
+    /// [environment variable]: ./struct.Arg.html#method.env
+    /// [overridden]: ./struct.Arg.html#method.overrides_with
+    /// [conflicts with]: ./struct.Arg.html#method.conflicts_with
+    /// [`ArgMatches::overridden_by`]: ./struct.ArgMatches.html#method.overridden_by
+    /// [unknown arguments]: ./struct.ArgMatches.html#method.unknown_args
+    pub fn overlay(&mut self, lower: ArgMatches) {
+        let ArgMatches {
+            args,
+            subcommand,
+            repeated_subcommands,
+            overridden,
+            group_members,
+            names,
+            declared,
+            declared_flags,
+            exclusive,
+            unknown,
+            ..
+        } = lower;
+
+        for &pair in &exclusive {
+            if !self.exclusive.contains(&pair) {
+                self.exclusive.push(pair);
+            }
+        }
+        for (id, ma) in args {
+            let fill = !self.overridden.contains_key(&id)
+                && self
+                    .args
+                    .get(&id)
+                    .map_or(true, |own| own.occurs == 0 && ma.occurs > 0)
+                && !self.args.iter().any(|(&own, own_ma)| {
+                    own_ma.occurs > 0 && self.exclusive.contains(&(own, id))
+                });
+            if fill {
+                self.args.insert(id, ma);
+            }
+        }
+        for (id, by) in overridden {
+            if !self.args.contains_key(&id) {
+                self.overridden.entry(id).or_insert(by);
+            }
+        }
+        self.unknown.extend(unknown);
+        for (id, name) in names {
+            self.names.entry(id).or_insert(name);
+        }
+        for (id, member) in group_members {
+            self.group_members.entry(id).or_insert(member);
+        }
+        for id in declared {
+            if !self.declared.contains(&id) {
+                self.declared.push(id);
+            }
+        }
+        for id in declared_flags {
+            if !self.declared_flags.contains(&id) {
+                self.declared_flags.push(id);
+            }
+        }
+
+        let mut lower_scs = subcommand
+            .map(|sc| *sc)
+            .into_iter()
+            .chain(repeated_subcommands)
+            .collect::<Vec<_>>();
+        if self.subcommand.is_none() {
+            if !lower_scs.is_empty() {
+                self.subcommand = Some(Box::new(lower_scs.remove(0)));
+                self.repeated_subcommands = lower_scs;
+            }
+            return;
+        }
+        for sc in self
+            .subcommand
+            .iter_mut()
+            .map(|sc| &mut **sc)
+            .chain(self.repeated_subcommands.iter_mut())
+        {
+            if let Some(i) = lower_scs.iter().position(|l| l.name == sc.name) {
+                sc.matches.overlay(lower_scs.remove(i).matches);
+            }
+        }
+    }
 }
 
 // The following were taken and adapated from vec_map source
@@ -899,6 +1538,48 @@ impl<'a> Default for Indices<'a> {
     }
 }
 
+/// An iterator over the values of several arguments in the order they were supplied, via the
+/// [`ArgMatches::occurrences_with_indices`] method.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg};
+/// let m = App::new("myapp")
+///     .arg(Arg::with_name("first")
+///         .short('f')
+///         .takes_value(true))
+///     .arg(Arg::with_name("second")
+///         .short('s')
+///         .takes_value(true))
+///     .get_matches_from(vec!["myapp", "-s", "val1", "-f", "val2"]);
+///
+/// let mut occurs = m.occurrences_with_indices(&["first", "second"]);
+///
+/// assert_eq!(occurs.next(), Some(("second", "val1", 2)));
+/// assert_eq!(occurs.next(), Some(("first", "val2", 4)));
+/// assert_eq!(occurs.next(), None);
+/// ```
+/// [`ArgMatches::occurrences_with_indices`]: ./struct.ArgMatches.html#method.occurrences_with_indices
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct Occurrences<'a, T> {
+    iter: IntoIter<(T, &'a str, usize)>,
+}
+
+impl<'a, T> Iterator for Occurrences<'a, T> {
+    type Item = (T, &'a str, usize);
+
+    fn next(&mut self) -> Option<(T, &'a str, usize)> { self.iter.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a, T> DoubleEndedIterator for Occurrences<'a, T> {
+    fn next_back(&mut self) -> Option<(T, &'a str, usize)> { self.iter.next_back() }
+}
+
+impl<'a, T> ExactSizeIterator for Occurrences<'a, T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
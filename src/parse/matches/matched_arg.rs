@@ -1,6 +1,9 @@
 // Std
 use std::ffi::{OsStr, OsString};
 
+// Internal
+use crate::parse::ValueSource;
+
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct MatchedArg {
@@ -10,6 +13,11 @@ pub struct MatchedArg {
     pub indices: Vec<usize>,
     #[doc(hidden)]
     pub vals: Vec<OsString>,
+    #[doc(hidden)]
+    pub source: ValueSource,
+    // A `-` value of this arg means stdin, see Arg::allow_stdin_dash
+    #[doc(hidden)]
+    pub stdin: bool,
 }
 
 impl Default for MatchedArg {
@@ -18,6 +26,8 @@ impl Default for MatchedArg {
             occurs: 1,
             indices: Vec::new(),
             vals: Vec::new(),
+            source: ValueSource::CommandLine,
+            stdin: false,
         }
     }
 }
@@ -1,7 +1,9 @@
 mod arg_matches;
 mod matched_arg;
 mod subcommand;
+mod value_source;
 
-pub use self::arg_matches::{ArgMatches, OsValues, Values};
+pub use self::arg_matches::{ArgMatches, Indices, Occurrences, OsValues, Values};
 pub use self::matched_arg::MatchedArg;
 pub use self::subcommand::SubCommand;
+pub use self::value_source::ValueSource;
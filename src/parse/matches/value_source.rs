@@ -0,0 +1,27 @@
+/// Where the values of an argument came from, see [`ArgMatches::value_source`].
+///
+/// [`ArgMatches::value_source`]: ./struct.ArgMatches.html#method.value_source
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    /// The argument was used on the command line, this includes values of
+    /// [`Arg::default_missing_value`] for an argument used without one
+    ///
+    /// [`Arg::default_missing_value`]: ./struct.Arg.html#method.default_missing_value
+    CommandLine,
+    /// The value was read from the argument's [environment variable]
+    ///
+    /// [environment variable]: ./struct.Arg.html#method.env
+    EnvVariable,
+    /// The value is the argument's [default value]
+    ///
+    /// [default value]: ./struct.Arg.html#method.default_value
+    DefaultValue,
+    /// The value is one of the argument's [conditional default values]
+    ///
+    /// [conditional default values]: ./struct.Arg.html#method.default_value_if
+    ConditionalDefaultValue,
+}
+
+impl Default for ValueSource {
+    fn default() -> Self { ValueSource::CommandLine }
+}
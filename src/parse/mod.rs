@@ -8,6 +8,8 @@ mod validator;
 
 pub use self::arg_matcher::ArgMatcher;
 pub use self::matches::ArgMatches;
-pub use self::matches::{MatchedArg, OsValues, SubCommand, Values};
+pub use self::matches::{
+    Indices, MatchedArg, Occurrences, OsValues, SubCommand, ValueSource, Values,
+};
 pub use self::parser::{ParseResult, Parser};
 pub use self::validator::Validator;
@@ -1,5 +1,5 @@
 // Std
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ffi::{OsStr, OsString};
 use std::io::{self, BufWriter, Write};
 use std::iter::Peekable;
@@ -13,7 +13,8 @@ use std::os::unix::ffi::OsStrExt;
 // Internal
 use crate::build::app::Propagation;
 use crate::build::AppSettings as AS;
-use crate::build::{App, Arg, ArgSettings};
+use crate::build::{App, Arg, ArgSettings, UnknownArgAction};
+use crate::complete;
 use crate::mkeymap::KeyType;
 use crate::output::Help;
 use crate::output::Usage;
@@ -22,7 +23,7 @@ use crate::parse::errors::ErrorKind;
 use crate::parse::errors::Result as ClapResult;
 use crate::parse::features::suggestions;
 use crate::parse::Validator;
-use crate::parse::{ArgMatcher, SubCommand};
+use crate::parse::{ArgMatcher, SubCommand, ValueSource};
 #[cfg(all(feature = "debug", any(target_os = "windows", target_arch = "wasm32")))]
 use crate::util::OsStrExt3;
 use crate::util::{self, ChildGraph, Key, OsStrExt2, EMPTY_HASH};
@@ -53,6 +54,8 @@ where
     pub overriden: Vec<Id>,
     seen: Vec<Id>,
     cur_idx: Cell<usize>,
+    sibling_scs: Vec<OsString>,
+    errors: RefCell<Vec<ClapError>>,
 }
 
 // Initializing Methods
@@ -78,6 +81,8 @@ where
             overriden: Vec::new(),
             seen: Vec::new(),
             cur_idx: Cell::new(0),
+            sibling_scs: Vec::new(),
+            errors: RefCell::new(Vec::new()),
         }
     }
 
@@ -383,8 +388,15 @@ where
         let mut subcmd_name: Option<String> = None;
         let mut needs_val_of: ParseResult = ParseResult::NotFound;
         let mut pos_counter = 1;
-        while let Some(arg) = it.next() {
-            let arg_os = arg.into();
+        loop {
+            if self.is_next_sibling_sc(it, needs_val_of) {
+                debugln!("Parser::get_matches_with: found next subcommand of parent, stopping");
+                break;
+            }
+            let arg_os: OsString = match it.next() {
+                Some(arg) => arg.into(),
+                None => break,
+            };
             debugln!(
                 "Parser::get_matches_with: Begin parsing '{:?}' ({:?})",
                 arg_os,
@@ -429,9 +441,10 @@ where
                     }
                 }
 
-                if starts_new_arg {
+                if starts_new_arg && !self.is_pos_hyphen_val(&arg_os, needs_val_of, pos_counter) {
                     if arg_os.starts_with(b"--") {
-                        needs_val_of = self.parse_long_arg(matcher, &arg_os)?;
+                        let res = self.parse_long_arg(matcher, &arg_os);
+                        needs_val_of = self.collect_err(res)?.unwrap_or(ParseResult::ValuesDone);
                         debugln!(
                             "Parser:get_matches_with: After parse_long_arg {:?}",
                             needs_val_of
@@ -444,7 +457,8 @@ where
                         // Try to parse short args like normal, if AllowLeadingHyphen or
                         // AllowNegativeNumbers is set, parse_short_arg will *not* throw
                         // an error, and instead return Ok(None)
-                        needs_val_of = self.parse_short_arg(matcher, &arg_os)?;
+                        let res = self.parse_short_arg(matcher, &arg_os);
+                        needs_val_of = self.collect_err(res)?.unwrap_or(ParseResult::ValuesDone);
                         // If it's None, we then check if one of those two AppSettings was set
                         debugln!(
                             "Parser:get_matches_with: After parse_short_arg {:?}",
@@ -455,12 +469,13 @@ where
                                 if !(arg_os.to_string_lossy().parse::<i64>().is_ok()
                                     || arg_os.to_string_lossy().parse::<f64>().is_ok())
                                 {
-                                    return Err(ClapError::unknown_argument(
+                                    self.collect(ClapError::unknown_argument(
                                         &*arg_os.to_string_lossy(),
                                         None,
-                                        &*Usage::new(self).create_usage_with_title(&[]),
-                                        self.app.color(),
-                                    ));
+                                        &*Usage::new(self).create_error_usage(&[]),
+                                        self.app.color_spec(),
+                                    ))?;
+                                    continue;
                                 }
                             }
                             ParseResult::Opt(..) | ParseResult::Flag | ParseResult::ValuesDone => continue,
@@ -480,16 +495,18 @@ where
             if !(self.is_set(AS::ArgsNegateSubcommands) && self.is_set(AS::ValidArgFound)
                 || self.is_set(AS::AllowExternalSubcommands)
                 || self.is_set(AS::InferSubcommands))
+                && self.is_set(AS::SuggestSubcommands)
             {
-                if let Some(cdate) =
-                    suggestions::did_you_mean(&*arg_os.to_string_lossy(), sc_names!(self.app))
-                {
+                if let Some(cdate) = suggestions::did_you_mean(
+                    &*arg_os.to_string_lossy(),
+                    visible_sc_names!(self.app),
+                ) {
                     return Err(ClapError::invalid_subcommand(
                         arg_os.to_string_lossy().into_owned(),
                         cdate,
                         self.app.bin_name.as_ref().unwrap_or(&self.app.name),
-                        &*Usage::new(self).create_usage_with_title(&[]),
-                        self.app.color(),
+                        &*Usage::new(self).create_error_usage(&[]),
+                        self.app.color_spec(),
                     ));
                 }
             }
@@ -575,21 +592,23 @@ where
                     return Err(ClapError::unknown_argument(
                         &*arg_os.to_string_lossy(),
                         None,
-                        &*Usage::new(self).create_usage_with_title(&[]),
-                        self.app.color(),
+                        &*Usage::new(self).create_error_usage(&[]),
+                        self.app.color_spec(),
                     ));
                 }
                 if !self.is_set(AS::TrailingValues)
-                    && (self.is_set(AS::TrailingVarArg)
-                        && pos_counter
-                            == self
-                                .app
-                                .args
-                                .keys
-                                .iter()
-                                .map(|x| &x.key)
-                                .filter(|x| x.is_position())
-                                .count())
+                    && (p.is_set(ArgSettings::Raw)
+                        || self.is_set(AS::PassthroughAfterPositional)
+                        || (self.is_set(AS::TrailingVarArg)
+                            && pos_counter
+                                == self
+                                    .app
+                                    .args
+                                    .keys
+                                    .iter()
+                                    .map(|x| &x.key)
+                                    .filter(|x| x.is_position())
+                                    .count()))
                 {
                     self.app.settings.set(AS::TrailingValues);
                 }
@@ -599,6 +618,7 @@ where
                 matcher.inc_occurrence_of(p.id);
                 for grp in groups_for_arg!(self.app, p.id) {
                     matcher.inc_occurrence_of(grp);
+                    matcher.add_group_member(grp, p.name);
                 }
 
                 self.app.settings.set(AS::ValidArgFound);
@@ -614,8 +634,8 @@ where
                     None => {
                         if !self.is_set(AS::StrictUtf8) {
                             return Err(ClapError::invalid_utf8(
-                                &*Usage::new(self).create_usage_with_title(&[]),
-                                self.app.color(),
+                                &*Usage::new(self).create_error_usage(&[]),
+                                self.app.color_spec(),
                             ));
                         }
                         arg_os.to_string_lossy().into_owned()
@@ -628,8 +648,8 @@ where
                     let a = v.into();
                     if a.to_str().is_none() && !self.is_set(AS::StrictUtf8) {
                         return Err(ClapError::invalid_utf8(
-                            &*Usage::new(self).create_usage_with_title(&[]),
-                            self.app.color(),
+                            &*Usage::new(self).create_error_usage(&[]),
+                            self.app.color_spec(),
                         ));
                     }
                     sc_m.add_val_to(EMPTY_HASH, &a);
@@ -646,37 +666,43 @@ where
                 && arg_os.starts_with(b"-"))
                 && !self.is_set(AS::InferSubcommands)
             {
-                return Err(ClapError::unknown_argument(
+                self.collect(ClapError::unknown_argument(
                     &*arg_os.to_string_lossy(),
                     None,
-                    &*Usage::new(self).create_usage_with_title(&[]),
-                    self.app.color(),
-                ));
+                    &*Usage::new(self).create_error_usage(&[]),
+                    self.app.color_spec(),
+                ))?;
             } else if !has_args || self.is_set(AS::InferSubcommands) && self.has_subcommands() {
-                if let Some(cdate) =
-                    suggestions::did_you_mean(&*arg_os.to_string_lossy(), sc_names!(self.app))
-                {
+                let cdate = if self.is_set(AS::SuggestSubcommands) {
+                    suggestions::did_you_mean(
+                        &*arg_os.to_string_lossy(),
+                        visible_sc_names!(self.app),
+                    )
+                } else {
+                    None
+                };
+                if let Some(cdate) = cdate {
                     return Err(ClapError::invalid_subcommand(
                         arg_os.to_string_lossy().into_owned(),
                         cdate,
                         self.app.bin_name.as_ref().unwrap_or(&self.app.name),
-                        &*Usage::new(self).create_usage_with_title(&[]),
-                        self.app.color(),
+                        &*Usage::new(self).create_error_usage(&[]),
+                        self.app.color_spec(),
                     ));
                 } else {
                     return Err(ClapError::unrecognized_subcommand(
                         arg_os.to_string_lossy().into_owned(),
                         self.app.bin_name.as_ref().unwrap_or(&self.app.name),
-                        self.app.color(),
+                        self.app.color_spec(),
                     ));
                 }
             } else {
-                return Err(ClapError::unknown_argument(
+                self.collect(ClapError::unknown_argument(
                     &*arg_os.to_string_lossy(),
                     None,
-                    &*Usage::new(self).create_usage_with_title(&[]),
-                    self.app.color(),
-                ));
+                    &*Usage::new(self).create_error_usage(&[]),
+                    self.app.color_spec(),
+                ))?;
             }
         }
 
@@ -687,44 +713,125 @@ where
                     .name
                     .clone()
             };
-            self.parse_subcommand(&*sc_name, matcher, it)?;
+            let res = self.parse_subcommand(&*sc_name, matcher, it);
+            self.collect_err(res)?;
+            if self.is_set(AS::AllowMultipleSubcommands) {
+                // Subcommands only stop parsing at the name of one of their siblings, so whatever
+                // is left starts the next invocation
+                while let Some(arg) = it.next() {
+                    let arg_os: OsString = arg.into();
+                    let sc_name = match find_subcmd!(self.app, &*arg_os.to_string_lossy()) {
+                        Some(sc) => sc.name.clone(),
+                        None => {
+                            // Only a subcommand which failed (see AppSettings::CollectErrors)
+                            // stops early, the rest of the line can't be made sense of then
+                            self.collect(ClapError::unknown_argument(
+                                &*arg_os.to_string_lossy(),
+                                None,
+                                &*Usage::new(self).create_error_usage(&[]),
+                                self.app.color_spec(),
+                            ))?;
+                            break;
+                        }
+                    };
+                    if sc_name == "help" && !self.is_set(AS::NoAutoHelp) {
+                        self.parse_help_subcommand(it)?;
+                    }
+                    let res = self.parse_subcommand(&*sc_name, matcher, it);
+                    self.collect_err(res)?;
+                }
+            }
+        } else if self.is_set(AS::SubcommandRequiredElseHelp) {
+            // Showing the help is what was asked for, so it's not an error
+            debugln!("Parser::get_matches_with: SubcommandRequiredElseHelp=true");
+            return Err(self.help_err(false));
         } else if self.is_set(AS::SubcommandRequired) {
             let bn = self.app.bin_name.as_ref().unwrap_or(&self.app.name);
             return Err(ClapError::missing_subcommand(
                 bn,
-                &Usage::new(self).create_usage_with_title(&[]),
-                self.app.color(),
+                &Usage::new(self).create_error_usage(&[]),
+                self.app.color_spec(),
             ));
-        } else if self.is_set(AS::SubcommandRequiredElseHelp) {
-            debugln!("Parser::get_matches_with: SubcommandRequiredElseHelp=true");
-            let mut out = vec![];
-            self.write_help_err(&mut out)?;
-            return Err(ClapError {
-                message: String::from_utf8_lossy(&*out).into_owned(),
-                kind: ErrorKind::MissingArgumentOrSubcommand,
-                info: None,
-            });
         }
 
         self.remove_overrides(matcher);
 
-        Validator::new(self).validate(needs_val_of, &subcmd_name, matcher)
+        // Globals may still be filled in from subcommands later on, so their names are needed too
+        for a in self.app.args.args.iter() {
+            if a.global || matcher.contains(a.id) {
+                matcher.add_name(a.id, a.name);
+            }
+        }
+
+        Validator::new(self).validate(needs_val_of, &subcmd_name, matcher)?;
+
+        let errors = self.errors.replace(Vec::new());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ClapError::multiple(errors))
+        }
+    }
+
+    // Whether `e` is kept for the error returned at the end with AppSettings::CollectErrors
+    // rather than ending the parse. Help and version messages are what was asked for, not errors.
+    fn collects(&self, e: &ClapError) -> bool {
+        self.is_set(AS::CollectErrors)
+            && match e.kind {
+                ErrorKind::HelpDisplayed
+                | ErrorKind::VersionDisplayed
+                | ErrorKind::CompletionsDisplayed
+                | ErrorKind::Io
+                | ErrorKind::Format => false,
+                _ => true,
+            }
+    }
+
+    // Keeps `e` if AppSettings::CollectErrors is set so parsing can carry on, otherwise returns it
+    pub(crate) fn collect(&self, e: ClapError) -> ClapResult<()> {
+        if !self.collects(&e) {
+            return Err(e);
+        }
+        debugln!("Parser::collect: kind={:?}", e.kind);
+        if e.kind == ErrorKind::MultipleErrors {
+            // From a subcommand
+            self.errors.borrow_mut().extend(e.errors);
+        } else {
+            self.errors.borrow_mut().push(e);
+        }
+        Ok(())
+    }
+
+    // Like Parser::collect for the result of a step which can fail, there's no value if the error
+    // was kept
+    pub(crate) fn collect_err<T>(&self, res: ClapResult<T>) -> ClapResult<Option<T>> {
+        match res {
+            Ok(v) => Ok(Some(v)),
+            Err(e) => self.collect(e).map(|_| None),
+        }
     }
 
     // Checks if the arg matches a subcommand name, or any of it's aliases (if defined)
     fn possible_subcommand(&self, arg_os: &OsStr) -> (bool, Option<&str>) {
         debugln!("Parser::possible_subcommand: arg={:?}", arg_os);
-        fn starts(h: &str, n: &OsStr) -> bool {
+        fn starts(h: &str, n: &OsStr, ignore_case: bool) -> bool {
             #[cfg(target_os = "windows")]
             use crate::util::OsStrExt3;
             #[cfg(not(target_os = "windows"))]
             use std::os::unix::ffi::OsStrExt;
 
+            if ignore_case {
+                return h
+                    .to_lowercase()
+                    .starts_with(&*n.to_string_lossy().to_lowercase());
+            }
+
             let n_bytes = n.as_bytes();
             let h_bytes = OsStr::new(h).as_bytes();
 
             h_bytes.starts_with(n_bytes)
         }
+        let ignore_case = self.is_set(AS::CaseInsensitiveSubcommands);
 
         if self.is_set(AS::ArgsNegateSubcommands) && self.is_set(AS::ValidArgFound) {
             return (false, None);
@@ -733,9 +840,14 @@ where
             if let Some(sc) = find_subcmd!(self.app, arg_os) {
                 return (true, Some(&sc.name));
             }
+            if ignore_case {
+                if let Some(sc) = find_subcmd_ignore_case!(self.app, arg_os.to_string_lossy()) {
+                    return (true, Some(&sc.name));
+                }
+            }
         } else {
             let v = sc_names!(self.app)
-                .filter(|s| starts(s, &*arg_os))
+                .filter(|s| starts(s, &*arg_os, ignore_case))
                 .collect::<Vec<_>>();
 
             if v.len() == 1 {
@@ -764,18 +876,28 @@ where
                     help_help = true;
                     break; // Maybe?
                 }
+                let cmd = if self.is_set(AS::CaseInsensitiveSubcommands)
+                    && find_subcmd!(sc, cmd).is_none()
+                {
+                    find_subcmd_ignore_case!(sc, cmd.to_string_lossy())
+                        .map_or_else(|| cmd.clone(), |c| OsString::from(&*c.name))
+                } else {
+                    cmd.clone()
+                };
                 if let Some(id) = find_subcmd!(sc, cmd).map(|x| x.id) {
                     sc._propagate(Propagation::To(id));
                 }
                 if let Some(mut c) = find_subcmd_cloned!(sc, cmd) {
                     c._build();
                     sc = c;
+                    bin_name = format!("{} {}", bin_name, &*sc.name);
                     if i == cmds.len() - 1 {
                         break;
                     }
                 } else if let Some(mut c) = find_subcmd_cloned!(sc, &*cmd.to_string_lossy()) {
                     c._build();
                     sc = c;
+                    bin_name = format!("{} {}", bin_name, &*sc.name);
                     if i == cmds.len() - 1 {
                         break;
                     }
@@ -783,10 +905,9 @@ where
                     return Err(ClapError::unrecognized_subcommand(
                         cmd.to_string_lossy().into_owned(),
                         self.app.bin_name.as_ref().unwrap_or(&self.app.name),
-                        self.app.color(),
+                        self.app.color_spec(),
                     ));
                 }
-                bin_name = format!("{} {}", bin_name, &*sc.name);
             }
             sc
         };
@@ -801,9 +922,8 @@ where
             parser.app.settings = parser.app.settings | self.app.g_settings;
             parser.app.g_settings = self.app.g_settings;
         }
-        if parser.app.bin_name != self.app.bin_name {
-            parser.app.bin_name = Some(format!("{} {}", bin_name, parser.app.name));
-        }
+        // bin_name holds the full path of every subcommand walked through, i.e. `git remote add`
+        parser.app.bin_name = Some(bin_name);
         Err(parser.help_err(false))
     }
 
@@ -827,6 +947,10 @@ where
         let arg_allows_tac = match needs_val_of {
             ParseResult::Opt(name) => {
                 let o = self.app.find(name).expect(INTERNAL_ERROR_MSG);
+                // The option's terminator (even `--`) is handled when adding values
+                if o.terminator.map_or(false, |t| OsStr::new(t) == arg_os) {
+                    return false;
+                }
                 (o.is_set(ArgSettings::AllowHyphenValues) || app_wide_settings)
             }
             ParseResult::Pos(name) => {
@@ -862,6 +986,63 @@ where
         ret
     }
 
+    // Checks if an arg that looks like a flag is actually a value for the next positional,
+    // which is only the case if that positional allows hyphen values and the arg doesn't match
+    // any known flag or option
+    fn is_pos_hyphen_val(
+        &self,
+        arg_os: &OsStr,
+        needs_val_of: ParseResult,
+        pos_counter: usize,
+    ) -> bool {
+        debugln!("Parser::is_pos_hyphen_val:{:?}:{:?}", arg_os, needs_val_of);
+        if let ParseResult::Opt(_) = needs_val_of {
+            return false;
+        }
+        let allows_tac = positionals!(self.app)
+            .find(|p| p.index == Some(pos_counter as u64))
+            .map_or(false, |p| p.is_set(ArgSettings::AllowHyphenValues));
+        if !allows_tac {
+            return false;
+        }
+        if arg_os.starts_with(b"--") {
+            let arg = arg_os.trim_start_matches(b'-');
+            let name = if arg.contains_byte(b'=') {
+                arg.split_at_byte(b'=').0
+            } else {
+                arg
+            };
+            self.app.args.get(&KeyType::Long(name.into())).is_none()
+        } else {
+            arg_os
+                .trim_start_matches(b'-')
+                .to_string_lossy()
+                .chars()
+                .any(|c| !self.contains_short(c))
+        }
+    }
+
+    // Checks if the next arg is the name of a sibling subcommand, meaning this subcommand is done
+    // and the parent should parse the next invocation (see AppSettings::AllowMultipleSubcommands)
+    fn is_next_sibling_sc<I, T>(&self, it: &mut Peekable<I>, needs_val_of: ParseResult) -> bool
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        if self.sibling_scs.is_empty() || self.is_set(AS::TrailingValues) {
+            return false;
+        }
+        if let ParseResult::Opt(_) = needs_val_of {
+            return false;
+        }
+        if let Some(n) = it.peek() {
+            let n: OsString = (*n).clone().into();
+            self.sibling_scs.contains(&n)
+        } else {
+            false
+        }
+    }
+
     fn parse_subcommand<I, T>(
         &mut self,
         sc_name: &str,
@@ -883,39 +1064,45 @@ where
             }
         }
         mid_string.push_str(" ");
+        // A repeated subcommand has already been propagated to and built
+        let repeated = matcher.has_subcommand(sc_name.key());
         if let Some(id) = find_subcmd!(self.app, sc_name).map(|x| x.id) {
-            self.app._propagate(Propagation::To(id));
+            if !repeated {
+                self.app._propagate(Propagation::To(id));
+            }
         }
+        let sibling_scs: Vec<OsString> = if self.is_set(AS::AllowMultipleSubcommands) {
+            sc_names!(self.app).map(OsString::from).collect()
+        } else {
+            Vec::new()
+        };
+        let mut completions = None;
         if let Some(sc) = subcommands_mut!(self.app).find(|s| s.name == sc_name) {
-            let mut sc_matcher = ArgMatcher::new();
             // bin_name should be parent's bin_name + [<reqs>] + the sc's name separated by
-            // a space
-            sc.usage = Some(format!(
-                "{}{}{}",
-                self.app.bin_name.as_ref().unwrap_or(&String::new()),
-                if self.app.bin_name.is_some() {
-                    &*mid_string
-                } else {
-                    ""
-                },
-                &*sc.name
-            ));
-            sc.bin_name = Some(format!(
-                "{}{}{}",
-                self.app.bin_name.as_ref().unwrap_or(&String::new()),
-                if self.app.bin_name.is_some() { " " } else { "" },
-                &*sc.name
-            ));
-
-            // Ensure all args are built and ready to parse
-            sc._build();
+            // a space. When the parent has no bin_name (i.e. NoBinaryName) fall back to its name
+            // so nested subcommands still show the full path
+            let parent_bin_name = self.app.bin_name.as_ref().unwrap_or(&self.app.name);
+            sc.usage = Some(format!("{}{}{}", parent_bin_name, &*mid_string, &*sc.name));
+            sc.bin_name = Some(format!("{} {}", parent_bin_name, &*sc.name));
+
+            // Ensure all args are built and ready to parse, a repeated subcommand or one from an
+            // earlier parse of the same App already is
+            if !sc.is_set(AS::Built) {
+                sc._build();
+            }
+            sc._reset_parse_state();
+            let mut sc_matcher = ArgMatcher::for_app(sc);
 
             debugln!("Parser::parse_subcommand: About to parse sc={}", sc.name);
 
             {
                 let mut p = Parser::new(sc);
+                p.sibling_scs = sibling_scs;
                 p.get_matches_with(&mut sc_matcher, it)?;
             }
+            if sc.is_set(AS::CompletionsSubcommand) {
+                completions = sc_matcher.0.value_of("shell").map(String::from);
+            }
             let name = sc.name.clone();
             let sc_id = name.key();
             matcher.subcommand(SubCommand {
@@ -924,25 +1111,37 @@ where
                 matches: sc_matcher.into_inner(),
             });
         }
+        if let Some(shell) = completions {
+            return Err(self.completions_err(&*shell));
+        }
         Ok(())
     }
 
     // Retrieves the names of all args the user has supplied thus far, except required ones
     // because those will be listed in self.required
-    fn check_for_help_and_version_str(&self, arg: &OsStr) -> ClapResult<()> {
+    fn check_for_help_and_version_str(
+        &self,
+        arg: &Arg<'b>,
+        val: Option<&OsStr>,
+    ) -> ClapResult<()> {
         debugln!("Parser::check_for_help_and_version_str;");
         debug!(
-            "Parser::check_for_help_and_version_str: Checking if --{} is help or version...",
-            arg.to_str().unwrap()
+            "Parser::check_for_help_and_version_str: Checking if {} is help or version...",
+            arg.name
         );
 
         // Needs to use app.settings.is_set instead of just is_set() because is_set() checks
         // both global and local settings, we only want to check local
-        if arg == "help" && !self.app.settings.is_set(AS::NoAutoHelp) {
+        //
+        // The arg was already resolved, so compare ids in order for aliases to count as well
+        if arg.id == util::HELP_HASH && !self.app.settings.is_set(AS::NoAutoHelp) {
             sdebugln!("Help");
+            if let Some(section) = val {
+                return Err(self.help_section_err(&*section.to_string_lossy()));
+            }
             return Err(self.help_err(true));
         }
-        if arg == "version" && !self.app.settings.is_set(AS::NoAutoVersion) {
+        if arg.id == util::VERSION_HASH && !self.app.settings.is_set(AS::NoAutoVersion) {
             sdebugln!("Version");
             return Err(self.version_err(true));
         }
@@ -989,9 +1188,12 @@ where
             v.long_help.is_some()
                 || v.is_set(ArgSettings::HiddenLongHelp)
                 || v.is_set(ArgSettings::HiddenShortHelp)
+                || v.possible_vals_cap.is_some()
         };
 
         self.app.long_about.is_some()
+            || self.app.long_pre_help.is_some()
+            || self.app.long_more_help.is_some()
             || self.app.args.args.iter().any(|f| should_long(&f))
             || subcommands!(self.app).any(|s| s.long_about.is_some())
     }
@@ -1030,7 +1232,7 @@ where
             if opt.is_set(ArgSettings::TakesValue) {
                 return Ok(self.parse_opt(val, opt, val.is_some(), matcher)?);
             }
-            self.check_for_help_and_version_str(arg)?;
+            self.check_for_help_and_version_str(opt, val)?;
             self.parse_flag(opt, matcher)?;
 
             return Ok(ParseResult::Flag);
@@ -1041,6 +1243,9 @@ where
         }
 
         debugln!("Parser::parse_long_arg: Didn't match anything");
+        if let Some(ret) = self.handle_unknown_arg(full_arg, matcher) {
+            return Ok(ret);
+        }
         self.did_you_mean_error(arg.to_str().expect(INVALID_UTF8), matcher)
             .map(|_| ParseResult::NotFound)
     }
@@ -1122,12 +1327,17 @@ where
 
                 return Ok(ret);
             } else {
+                if ret == ParseResult::NotFound {
+                    if let Some(ret) = self.handle_unknown_arg(full_arg, matcher) {
+                        return Ok(ret);
+                    }
+                }
                 let arg = format!("-{}", c);
                 return Err(ClapError::unknown_argument(
                     &*arg,
                     None,
-                    &*Usage::new(self).create_usage_with_title(&[]),
-                    self.app.color(),
+                    &*Usage::new(self).create_error_usage(&[]),
+                    self.app.color_spec(),
                 ));
             }
         }
@@ -1149,6 +1359,25 @@ where
         let min_vals_zero = opt.min_vals.unwrap_or(1) == 0;
         let needs_eq = opt.is_set(ArgSettings::RequireEquals);
 
+        // Each occurrence needs exactly `number_of_values` values, so a new occurrence can't be
+        // used to finish off the values of the previous one
+        if let Some(num) = opt.num_vals {
+            if let Some(ma) = matcher.get(opt.id) {
+                let curr = ma.vals.len() % num as usize;
+                if curr != 0 {
+                    debugln!("Parser::parse_opt: previous occurrence has {} values", curr);
+                    return Err(ClapError::wrong_number_of_values(
+                        opt,
+                        num,
+                        curr,
+                        if curr == 1 { "as" } else { "ere" },
+                        &*Usage::new(self).create_error_usage(&[]),
+                        self.app.color_spec(),
+                    ));
+                }
+            }
+        }
+
         debug!("Parser::parse_opt; Checking for val...");
         if let Some(fv) = val {
             has_eq = fv.starts_with(&[b'=']) || had_eq;
@@ -1157,8 +1386,8 @@ where
                 sdebugln!("Found Empty - Error");
                 return Err(ClapError::empty_value(
                     opt,
-                    &*Usage::new(self).create_usage_with_title(&[]),
-                    self.app.color(),
+                    &*Usage::new(self).create_error_usage(&[]),
+                    self.app.color_spec(),
                 ));
             }
             sdebugln!("Found - {:?}, len: {}", v, v.len());
@@ -1172,8 +1401,8 @@ where
             sdebugln!("None, but requires equals...Error");
             return Err(ClapError::empty_value(
                 opt,
-                &*Usage::new(self).create_usage_with_title(&[]),
-                self.app.color(),
+                &*Usage::new(self).create_error_usage(&[]),
+                self.app.color_spec(),
             ));
         } else {
             sdebugln!("None");
@@ -1183,6 +1412,7 @@ where
         // Increment or create the group "args"
         for grp in groups_for_arg!(self.app, opt.id) {
             matcher.inc_occurrence_of(grp);
+            matcher.add_group_member(grp, opt.name);
         }
 
         let needs_delim = opt.is_set(ArgSettings::RequireDelimiter);
@@ -1215,6 +1445,17 @@ where
             if let Some(delim) = arg.val_delim {
                 if val.is_empty() {
                     Ok(self.add_single_val_to_arg(arg, val, matcher)?)
+                } else if arg.is_set(ArgSettings::AllowDelimiterEscape) {
+                    let vals = val.split_escaped(delim as u32 as u8);
+                    let mut iret = ParseResult::ValuesDone;
+                    for v in &vals {
+                        iret = self.add_single_val_to_arg(arg, v, matcher)?;
+                    }
+                    // Only an unescaped delimiter means we're not looking for more values
+                    if vals.len() > 1 || arg.is_set(ArgSettings::RequireDelimiter) {
+                        iret = ParseResult::ValuesDone;
+                    }
+                    Ok(iret)
                 } else {
                     let mut iret = ParseResult::ValuesDone;
                     for v in val.split(delim as u32 as u8) {
@@ -1255,8 +1496,19 @@ where
             }
         }
 
+        let stdin = arg.is_set(ArgSettings::AllowStdinDash) && v == "-";
+        if let Some(ref vtor) = arg.validator_error {
+            if !stdin {
+                debugln!("Parser::add_single_val_to_arg: checking validator_error...");
+                vtor(v)?;
+            }
+        }
+
         matcher.add_val_to(arg.id, v);
         matcher.add_index_to(arg.id, self.cur_idx.get());
+        if stdin {
+            matcher.set_stdin(arg.id);
+        }
 
         // Increment or create the group "args"
         for grp in groups_for_arg!(self.app, arg.id) {
@@ -1277,6 +1529,7 @@ where
         // Increment or create the group "args"
         for grp in groups_for_arg!(self.app, flag.id) {
             matcher.inc_occurrence_of(grp);
+            matcher.add_group_member(grp, flag.name);
         }
 
         Ok(ParseResult::Flag)
@@ -1284,7 +1537,7 @@ where
 
     fn remove_overrides(&mut self, matcher: &mut ArgMatcher) {
         debugln!("Parser::remove_overrides;");
-        let mut to_rem: Vec<Id> = Vec::new();
+        let mut to_rem: Vec<(Id, Id)> = Vec::new();
         let mut self_override: Vec<Id> = Vec::new();
         let mut arg_overrides = Vec::new();
         for &name in matcher.arg_names() {
@@ -1324,11 +1577,28 @@ where
             }
         }
 
+        // args which override regardless of order win before the seen order is looked at, unless
+        // the other side does so too
+        let always = |id| {
+            self.app
+                .find(id)
+                .map_or(false, |a| a.is_set(ArgSettings::OverridesRegardlessOfOrder))
+        };
+        for &(a, overr) in &arg_overrides {
+            if always(a)
+                && !always(overr)
+                && matcher.contains(overr)
+                && !to_rem.contains(&(overr, a))
+            {
+                to_rem.push((overr, a));
+            }
+        }
+
         // remove future overrides in reverse seen order
         for &arg in self.seen.iter().rev() {
             for &(a, overr) in arg_overrides.iter().filter(|&&(a, _)| a == arg) {
-                if !to_rem.contains(&a) {
-                    to_rem.push(overr);
+                if !to_rem.iter().any(|&(rem, _)| rem == a || rem == overr) {
+                    to_rem.push((overr, a));
                 }
             }
         }
@@ -1350,8 +1620,13 @@ where
         }
 
         // Finally remove conflicts
-        for &name in &to_rem {
+        for &(name, by) in &to_rem {
             debugln!("Parser::remove_overrides:iter:{}: removing;", name);
+            if matcher.contains(name) {
+                if let Some(winner) = self.app.find(by) {
+                    matcher.add_override(name, winner.name);
+                }
+            }
             matcher.remove(name);
             self.overriden.push(name);
         }
@@ -1376,6 +1651,7 @@ where
                         for val in vals {
                             $_self.add_val_to_arg($a, val, $m)?;
                         }
+                        $m.set_source($a.id, ValueSource::DefaultValue);
                     } else if $m.get($a.id).is_some() {
                         debugln!(
                             "Parser::add_defaults:iter:{}: has user defined vals",
@@ -1387,6 +1663,7 @@ where
                         for val in vals {
                             $_self.add_val_to_arg($a, val, $m)?;
                         }
+                        $m.set_source($a.id, ValueSource::DefaultValue);
                     }
                 } else {
                     debugln!(
@@ -1412,6 +1689,7 @@ where
                             };
                             if add {
                                 $_self.add_val_to_arg($a, OsStr::new(default), $m)?;
+                                $m.set_source($a.id, ValueSource::ConditionalDefaultValue);
                                 done = true;
                                 break;
                             }
@@ -1428,6 +1706,23 @@ where
             };
         }
 
+        for o in opts!(self.app) {
+            if let Some(ref vals) = o.default_missing_vals {
+                if matcher
+                    .get(o.id)
+                    .map(|ma| ma.vals.is_empty() && ma.occurs > 0)
+                    .unwrap_or(false)
+                {
+                    debugln!(
+                        "Parser::add_defaults:iter:{}: used without a value, adding default missing vals",
+                        o.name
+                    );
+                    for val in vals {
+                        self.add_val_to_arg(o, val, matcher)?;
+                    }
+                }
+            }
+        }
         for o in opts!(self.app) {
             debug!("Parser::add_defaults:iter:{}:", o.name);
             add_val!(self, o, matcher);
@@ -1443,7 +1738,11 @@ where
         for a in self.app.args.args.iter() {
             if let Some(ref val) = a.env {
                 if let Some(ref val) = val.1 {
+                    let used = matcher.contains(a.id);
                     self.add_val_to_arg(a, OsStr::new(val), matcher)?;
+                    if !used {
+                        matcher.set_source(a.id, ValueSource::EnvVariable);
+                    }
                 }
             }
         }
@@ -1456,27 +1755,37 @@ impl<'b, 'c> Parser<'b, 'c>
 where
     'b: 'c,
 {
+    // Asks the App::on_unknown_arg callback what to do with `arg`, None means it's an error
+    fn handle_unknown_arg(&self, arg: &OsStr, matcher: &mut ArgMatcher) -> Option<ParseResult> {
+        let action = self.app.unknown_arg_handler.as_ref()?.call(arg);
+        debugln!("Parser::handle_unknown_arg: arg={:?}, action={:?}", arg, action);
+        match action {
+            UnknownArgAction::Error => None,
+            UnknownArgAction::Collect => {
+                matcher.add_unknown(arg);
+                Some(ParseResult::ValuesDone)
+            }
+            UnknownArgAction::Ignore => Some(ParseResult::ValuesDone),
+        }
+    }
+
     fn did_you_mean_error(&mut self, arg: &str, matcher: &mut ArgMatcher) -> ClapResult<()> {
         debugln!("Parser::did_you_mean_error: arg={}", arg);
         // Didn't match a flag or option
-        let longs = self
-            .app
-            .args
-            .keys
-            .iter()
-            .map(|x| &x.key)
-            .filter_map(|x| match x {
-                KeyType::Long(l) => Some(l.to_string_lossy().into_owned()),
-                _ => None,
-            })
-            .collect::<Vec<_>>();
-        debugln!("Parser::did_you_mean_error: longs={:?}", longs);
+        let suffix = if self.is_set(AS::SuggestFlags) {
+            let longs = visible_longs!(self.app)
+                .map(|l| l.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            debugln!("Parser::did_you_mean_error: longs={:?}", longs);
 
-        let suffix = suggestions::did_you_mean_flag_suffix(
-            arg,
-            longs.iter().map(|ref x| &x[..]),
-            self.app.subcommands.as_mut_slice(),
-        );
+            suggestions::did_you_mean_flag_suffix(
+                arg,
+                longs.iter().map(|ref x| &x[..]),
+                self.app.subcommands.as_mut_slice(),
+            )
+        } else {
+            (String::new(), None)
+        };
 
         // Add the arg to the matches to build a proper usage string
         if let Some(ref name) = suffix.1 {
@@ -1509,8 +1818,8 @@ where
         Err(ClapError::unknown_argument(
             &*format!("--{}", arg),
             did_you_mean_msg,
-            &*Usage::new(self).create_usage_with_title(&*used),
-            self.app.color(),
+            &*Usage::new(self).create_error_usage(&*used),
+            self.app.color_spec(),
         ))
     }
 
@@ -1537,20 +1846,69 @@ where
                 message: String::from_utf8(buf).unwrap_or_default(),
                 kind: ErrorKind::HelpDisplayed,
                 info: None,
+                errors: vec![],
+            },
+        }
+    }
+
+    fn help_section_err(&self, section: &str) -> ClapError {
+        debugln!("Parser::help_section_err: section={}", section);
+        let use_long = self.use_long_help();
+        let mut buf = vec![];
+        match Help::new(&mut buf, self, use_long, false).write_help_section(section) {
+            Err(e) => e,
+            _ => ClapError {
+                message: String::from_utf8(buf).unwrap_or_default(),
+                kind: ErrorKind::HelpDisplayed,
+                info: None,
+                errors: vec![],
+            },
+        }
+    }
+
+    fn completions_err(&self, shell: &str) -> ClapError {
+        debugln!("Parser::completions_err: shell={}", shell);
+        let bin_name = self.app.bin_name.as_ref().unwrap_or(&self.app.name);
+        let mut buf = vec![];
+        let res = match shell {
+            "bash" => complete::bash(&*self.app, bin_name, &mut buf),
+            "elvish" => complete::elvish(&*self.app, bin_name, &mut buf),
+            "fish" => complete::fish(&*self.app, bin_name, &mut buf),
+            "powershell" => complete::powershell(&*self.app, bin_name, &mut buf),
+            _ => complete::zsh(&*self.app, bin_name, &mut buf),
+        };
+        match res {
+            Err(e) => e,
+            _ => ClapError {
+                message: String::from_utf8(buf).unwrap_or_default(),
+                kind: ErrorKind::CompletionsDisplayed,
+                info: None,
+                errors: vec![],
             },
         }
     }
 
     fn version_err(&self, use_long: bool) -> ClapError {
         debugln!("Parser::version_err: ");
-        let out = io::stdout();
-        let mut buf_w = BufWriter::new(out.lock());
-        match self.print_version(&mut buf_w, use_long) {
+        // The version is printed straight away, unless AppSettings::NoAutoError leaves printing it
+        // to the caller
+        let res = if self.is_set(AS::NoAutoError) {
+            let mut buf = vec![];
+            self.print_version(&mut buf, use_long)
+                .map(|_| String::from_utf8(buf).unwrap_or_default())
+        } else {
+            let out = io::stdout();
+            let mut buf_w = BufWriter::new(out.lock());
+            self.print_version(&mut buf_w, use_long)
+                .map(|_| String::new())
+        };
+        match res {
             Err(e) => e,
-            _ => ClapError {
-                message: String::new(),
+            Ok(message) => ClapError {
+                message,
                 kind: ErrorKind::VersionDisplayed,
                 info: None,
+                errors: vec![],
             },
         }
     }
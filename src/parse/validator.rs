@@ -42,7 +42,8 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
         if let ParseResult::Opt(a) = needs_val_of {
             debugln!("Validator::validate: needs_val_of={:?}", a);
             {
-                self.validate_required(matcher)?;
+                let res = self.validate_required(matcher);
+                self.p.collect_err(res)?;
             }
             let o = self.p.app.find(a).expect(INTERNAL_ERROR_MSG);
             reqs_validated = true;
@@ -52,11 +53,11 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 true
             };
             if should_err {
-                return Err(Error::empty_value(
+                self.p.collect(Error::empty_value(
                     o,
-                    &*Usage::new(self.p).create_usage_with_title(&[]),
-                    self.p.app.color(),
-                ));
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
+                ))?;
             }
         }
 
@@ -70,12 +71,16 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 message: String::from_utf8_lossy(&*out).into_owned(),
                 kind: ErrorKind::MissingArgumentOrSubcommand,
                 info: None,
+                errors: vec![],
             });
         }
-        self.validate_conflicts(matcher)?;
+        let res = self.validate_conflicts(matcher);
+        self.p.collect_err(res)?;
         if !(self.p.is_set(AS::SubcommandsNegateReqs) && subcmd_name.is_some() || reqs_validated) {
-            self.validate_required(matcher)?;
-            self.validate_required_unless(matcher)?;
+            let res = self.validate_required(matcher);
+            self.p.collect_err(res)?;
+            let res = self.validate_required_unless(matcher);
+            self.p.collect_err(res)?;
         }
         self.validate_matched_args(matcher)?;
 
@@ -96,19 +101,30 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                     val
                 );
                 return Err(Error::invalid_utf8(
-                    &*Usage::new(self.p).create_usage_with_title(&[]),
-                    self.p.app.color(),
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
                 ));
             }
-            if let Some(ref p_vals) = arg.possible_vals {
+            if ma.stdin && val == "-" {
+                debugln!("Validator::validate_arg_values: '-' reads from stdin, skipping checks");
+                continue;
+            }
+            if let Some(p_vals) = arg.possible_vals_lossy() {
                 debugln!("Validator::validate_arg_values: possible_vals={:?}", p_vals);
                 let val_str = val.to_string_lossy();
-                let ok = if arg.is_set(ArgSettings::IgnoreCase) {
-                    p_vals.iter().any(|pv| pv.eq_ignore_ascii_case(&*val_str))
-                } else {
-                    p_vals.contains(&&*val_str)
-                };
-                if !ok {
+                let ok_str = arg.possible_vals.as_ref().map_or(false, |pvs| {
+                    if arg.is_set(ArgSettings::IgnoreCase) {
+                        pvs.iter().any(|pv| pv.eq_ignore_ascii_case(&*val_str))
+                    } else {
+                        pvs.contains(&&*val_str)
+                    }
+                });
+                // Non UTF-8 values are compared before any lossy conversion
+                let ok_os = arg
+                    .possible_vals_os
+                    .as_ref()
+                    .map_or(false, |pvs| pvs.iter().any(|&pv| pv == val.as_os_str()));
+                if !(ok_str || ok_os) {
                     let used: Vec<Id> = matcher
                         .arg_names()
                         .filter(|&&n| {
@@ -122,10 +138,10 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                         .collect();
                     return Err(Error::invalid_value(
                         val_str,
-                        p_vals,
+                        &*p_vals,
                         arg,
-                        &*Usage::new(self.p).create_usage_with_title(&*used),
-                        self.p.app.color(),
+                        &*Usage::new(self.p).create_error_usage(&*used),
+                        self.p.app.color_spec(),
                     ));
                 }
             }
@@ -136,15 +152,15 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 debugln!("Validator::validate_arg_values: illegal empty val found");
                 return Err(Error::empty_value(
                     arg,
-                    &*Usage::new(self.p).create_usage_with_title(&[]),
-                    self.p.app.color(),
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
                 ));
             }
             if let Some(ref vtor) = arg.validator {
                 debug!("Validator::validate_arg_values: checking validator...");
                 if let Err(e) = vtor(val.to_string_lossy().into_owned()) {
                     sdebugln!("error");
-                    return Err(Error::value_validation(Some(arg), &e, self.p.app.color()));
+                    return Err(Error::value_validation(Some(arg), &e, self.p.app.color_spec()));
                 } else {
                     sdebugln!("good");
                 }
@@ -156,7 +172,7 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                     return Err(Error::value_validation(
                         Some(arg),
                         &(*e).to_string(),
-                        self.p.app.color(),
+                        self.p.app.color_spec(),
                     ));
                 } else {
                     sdebugln!("good");
@@ -168,17 +184,17 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
 
     fn build_conflict_err(&self, name: Id, matcher: &ArgMatcher) -> ClapResult<()> {
         debugln!("build_err!: name={}", name);
-        let usg = Usage::new(self.p).create_usage_with_title(&[]);
-        if self.p.app.find(name).is_some() {
+        let usg = Usage::new(self.p).create_error_usage(&[]);
+        if let Some(c) = self.p.app.find(name) {
             for &k in matcher.arg_names() {
                 if let Some(a) = self.p.app.find(k) {
                     if let Some(ref v) = a.blacklist {
                         if v.contains(&name) {
                             return Err(Error::argument_conflict(
                                 a,
-                                Some(a.to_string()),
+                                Some(c.to_string()),
                                 &*usg,
-                                self.p.app.color(),
+                                self.p.app.color_spec(),
                             ));
                         }
                     }
@@ -199,7 +215,7 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 self.p.app.find(*first).expect(INTERNAL_ERROR_MSG),
                 c_with,
                 &*usg,
-                self.p.app.color(),
+                self.p.app.color_spec(),
             ));
         }
 
@@ -263,6 +279,10 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
         for &name in matcher.arg_names() {
             debugln!("Validator::gather_conflicts:iter:{};", name);
             if let Some(arg) = self.p.app.find(name) {
+                // Default values don't count as using the arg, so they can't cause conflicts
+                if matcher.get(name).map_or(false, |ma| ma.occurs == 0) {
+                    continue;
+                }
                 // Since an arg was used, every arg it conflicts with is added to the conflicts
                 if let Some(ref bl) = arg.blacklist {
                     for &conf in bl {
@@ -340,10 +360,10 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 ma.vals
             );
             if let Some(arg) = self.p.app.find(name) {
-                self.validate_arg_num_vals(arg, ma)?;
-                self.validate_arg_values(arg, ma, matcher)?;
-                self.validate_arg_requires(arg, ma, matcher)?;
-                self.validate_arg_num_occurs(arg, ma)?;
+                self.p.collect_err(self.validate_arg_num_vals(arg, ma))?;
+                self.p.collect_err(self.validate_arg_values(arg, ma, matcher))?;
+                self.p.collect_err(self.validate_arg_requires(arg, ma, matcher))?;
+                self.p.collect_err(self.validate_arg_num_occurs(arg, ma))?;
             } else {
                 let grp = self
                     .p
@@ -354,7 +374,7 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                     .expect(INTERNAL_ERROR_MSG);
                 if let Some(ref g_reqs) = grp.requires {
                     if g_reqs.iter().any(|&n| !matcher.contains(n)) {
-                        return self.missing_required_error(matcher, Some(name));
+                        self.p.collect_err(self.missing_required_error(matcher, Some(name)))?;
                     }
                 }
             }
@@ -368,14 +388,43 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
             a.name,
             ma.occurs
         );
-        if ma.occurs > 1 && !a.is_set(ArgSettings::MultipleOccurrences) {
+        // Positional args occur once per value, so allowing multiple values is enough for them
+        if ma.occurs > 1
+            && !a.is_set(ArgSettings::MultipleOccurrences)
+            && (a.has_switch() || !a.is_set(ArgSettings::MultipleValues))
+        {
             // Not the first time, and we don't allow multiples
             return Err(Error::unexpected_multiple_usage(
                 a,
-                &*Usage::new(self.p).create_usage_with_title(&[]),
-                self.p.app.color(),
+                &*Usage::new(self.p).create_error_usage(&[]),
+                self.p.app.color_spec(),
             ));
         }
+        if let Some(num) = a.max_occurs {
+            debugln!("Validator::validate_arg_num_occurs: max_occurs set...{}", num);
+            if ma.occurs > num {
+                return Err(Error::too_many_occurrences(
+                    a,
+                    num,
+                    ma.occurs,
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
+                ));
+            }
+        }
+        if let Some(num) = a.min_occurs {
+            debugln!("Validator::validate_arg_num_occurs: min_occurs set...{}", num);
+            // Values from a default or the environment don't count as using the argument
+            if ma.occurs > 0 && ma.occurs < num {
+                return Err(Error::too_few_occurrences(
+                    a,
+                    num,
+                    ma.occurs,
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -383,7 +432,9 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
         debugln!("Validator::validate_arg_num_vals;");
         if let Some(num) = a.num_vals {
             debugln!("Validator::validate_arg_num_vals: num_vals set...{}", num);
-            let should_err = if a.is_set(ArgSettings::MultipleValues) {
+            let mult = a.is_set(ArgSettings::MultipleValues)
+                || a.is_set(ArgSettings::MultipleOccurrences);
+            let should_err = if mult {
                 ((ma.vals.len() as u64) % num) != 0
             } else {
                 num != (ma.vals.len() as u64)
@@ -393,21 +444,19 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 return Err(Error::wrong_number_of_values(
                     a,
                     num,
-                    if a.is_set(ArgSettings::MultipleValues) {
+                    if mult {
                         (ma.vals.len() % num as usize)
                     } else {
                         ma.vals.len()
                     },
-                    if ma.vals.len() == 1
-                        || (a.is_set(ArgSettings::MultipleValues)
-                            && (ma.vals.len() % num as usize) == 1)
+                    if ma.vals.len() == 1 || (mult && (ma.vals.len() % num as usize) == 1)
                     {
                         "as"
                     } else {
                         "ere"
                     },
-                    &*Usage::new(self.p).create_usage_with_title(&[]),
-                    self.p.app.color(),
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
                 ));
             }
         }
@@ -423,8 +472,8 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                         .to_str()
                         .expect(INVALID_UTF8),
                     a,
-                    &*Usage::new(self.p).create_usage_with_title(&[]),
-                    self.p.app.color(),
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
                 ));
             }
         }
@@ -436,8 +485,8 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                     a,
                     num,
                     ma.vals.len(),
-                    &*Usage::new(self.p).create_usage_with_title(&[]),
-                    self.p.app.color(),
+                    &*Usage::new(self.p).create_error_usage(&[]),
+                    self.p.app.color_spec(),
                 ));
             }
             num == 0
@@ -449,8 +498,8 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
         if a.is_set(ArgSettings::TakesValue) && !min_vals_zero && ma.vals.is_empty() {
             return Err(Error::empty_value(
                 a,
-                &*Usage::new(self.p).create_usage_with_title(&[]),
-                self.p.app.color(),
+                &*Usage::new(self.p).create_error_usage(&[]),
+                self.p.app.color_spec(),
             ));
         }
         Ok(())
@@ -468,7 +517,7 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
                 let missing_req =
                     |v| v == val.expect(INTERNAL_ERROR_MSG) && !matcher.contains(name);
                 if ma.vals.iter().any(missing_req) {
-                    return self.missing_required_error(matcher, Some(a.id));
+                    return self.missing_required_error(matcher, Some(name));
                 }
             }
             for &(_, name) in a_reqs.iter().filter(|&&(val, _)| val.is_none()) {
@@ -594,10 +643,7 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
     // `incl`: an arg to include in the error even if not used
     fn missing_required_error(&self, matcher: &ArgMatcher, incl: Option<Id>) -> ClapResult<()> {
         debugln!("Validator::missing_required_error; incl={:?}", incl);
-        let c = Colorizer::new(&ColorizerOption {
-            use_stderr: true,
-            when: self.p.app.color(),
-        });
+        let c = Colorizer::new(&ColorizerOption::from(self.p.app.color_spec()));
         debugln!(
             "Validator::missing_required_error: reqs={:?}",
             self.p.required
@@ -634,8 +680,8 @@ impl<'b, 'c, 'z> Validator<'b, 'c, 'z> {
             .collect();
         Err(Error::missing_required_argument(
             &*req_args,
-            &*usg.create_usage_with_title(&*used),
-            self.p.app.color(),
+            &*usg.create_error_usage(&*used),
+            self.p.app.color_spec(),
         ))
     }
 }
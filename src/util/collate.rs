@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+
+// Compares `a` and `b` the way people expect a listing to be sorted: ignoring case and the accents
+// of Latin letters, so "Éclair" lands among the "e"s. Strings which only differ in those are
+// ordered by their bytes, so the order is still total.
+//
+// This is a small hand made table rather than the Unicode Collation Algorithm, which would need a
+// dependency and locale data. Only Latin-1 and Latin Extended-A letters have their accents folded,
+// every other char (Latin Extended-B, Greek, Cyrillic, CJK, ...) is compared by its lowercase code
+// point, and locale rules such as "ä" sorting after "z" in Swedish aren't followed.
+pub fn collate(a: &str, b: &str) -> Ordering {
+    collation_key(a)
+        .cmp(&collation_key(b))
+        .then_with(|| a.cmp(b))
+}
+
+fn collation_key(s: &str) -> String {
+    let mut key = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        match fold_accent(c) {
+            Some(folded) => key.push_str(folded),
+            None => key.push(c),
+        }
+    }
+    key
+}
+
+// The unaccented spelling of the lowercase Latin-1 and Latin Extended-A letters, i.e. 'é' => "e"
+fn fold_accent(c: char) -> Option<&'static str> {
+    let folded = match c as u32 {
+        0xDF => "ss",
+        0xE0..=0xE5 | 0x100..=0x105 => "a",
+        0xE6 => "ae",
+        0xE7 | 0x106..=0x10D => "c",
+        0xE8..=0xEB | 0x112..=0x11B => "e",
+        0xEC..=0xEF | 0x128..=0x131 => "i",
+        0xF0 | 0x10E..=0x111 => "d",
+        0xF1 | 0x143..=0x14B => "n",
+        0xF2..=0xF6 | 0xF8 | 0x14C..=0x151 => "o",
+        0xF9..=0xFC | 0x168..=0x173 => "u",
+        0xFD | 0xFF | 0x176..=0x178 => "y",
+        0xFE => "th",
+        0x11C..=0x123 => "g",
+        0x124..=0x127 => "h",
+        0x132..=0x133 => "ij",
+        0x134..=0x135 => "j",
+        0x136..=0x138 => "k",
+        0x139..=0x142 => "l",
+        0x152..=0x153 => "oe",
+        0x154..=0x159 => "r",
+        0x15A..=0x161 | 0x17F => "s",
+        0x162..=0x167 => "t",
+        0x174..=0x175 => "w",
+        0x179..=0x17E => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::collate;
+
+    #[test]
+    fn collate_ignores_case_and_accents() {
+        let mut words = vec!["slow", "Éclair", "fast", "medium", "eclair", "Zebra", "ångström"];
+        words.sort_by(|a, b| collate(a, b));
+        assert_eq!(
+            words,
+            ["ångström", "eclair", "Éclair", "fast", "medium", "slow", "Zebra"]
+        );
+    }
+
+    #[test]
+    fn collate_folds_to_several_letters() {
+        let mut words = vec!["strat", "Straße", "strass", "af", "æble"];
+        words.sort_by(|a, b| collate(a, b));
+        assert_eq!(words, ["æble", "af", "strass", "Straße", "strat"]);
+    }
+}
@@ -1,9 +1,11 @@
+mod collate;
 mod fnv;
 mod graph;
 mod map;
 mod osstringext;
 mod strext;
 
+pub use self::collate::collate;
 pub use self::fnv::{Key, EMPTY_HASH, HELP_HASH, VERSION_HASH};
 pub use self::graph::ChildGraph;
 pub use self::map::{Values, VecMap};
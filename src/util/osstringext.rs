@@ -1,4 +1,4 @@
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 #[cfg(not(any(target_os = "windows", target_arch = "wasm32")))]
 use std::os::unix::ffi::OsStrExt;
 #[cfg(any(target_os = "windows", target_arch = "wasm32"))]
@@ -18,6 +18,7 @@ pub trait OsStrExt2 {
     fn trim_start_matches(&self, b: u8) -> &OsStr;
     fn contains_byte(&self, b: u8) -> bool;
     fn split(&self, b: u8) -> OsSplit;
+    fn split_escaped(&self, b: u8) -> Vec<OsString>;
 }
 
 #[cfg(target_os = "windows")]
@@ -85,6 +86,33 @@ impl OsStrExt2 for OsStr {
             pos: 0,
         }
     }
+
+    // Like split(), but a separator preceded by a backslash is kept (without the backslash)
+    fn split_escaped(&self, b: u8) -> Vec<OsString> {
+        let bytes = self.as_bytes();
+        let mut vals = Vec::new();
+        let mut cur = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b) {
+                cur.push(b);
+                i += 2;
+                continue;
+            }
+            if bytes[i] == b {
+                vals.push(OsStr::from_bytes(&cur).to_os_string());
+                cur.clear();
+            } else {
+                cur.push(bytes[i]);
+            }
+            i += 1;
+        }
+        // Mirrors split() which doesn't yield an empty value after a trailing separator
+        if !cur.is_empty() || bytes.last() != Some(&b) {
+            vals.push(OsStr::from_bytes(&cur).to_os_string());
+        }
+        vals
+    }
 }
 
 #[doc(hidden)]
@@ -1,7 +1,7 @@
 extern crate clap;
 extern crate regex;
 
-use clap::{App, AppSettings, Arg, ErrorKind, Propagation};
+use clap::{App, AppSettings, Arg, ErrorKind, Propagation, UnknownArgAction};
 
 include!("../clap-test.rs");
 
@@ -56,6 +56,18 @@ OPTIONS:
         --option <opt>    some option
     -V, --version         Prints version information";
 
+static HIDE_USAGE: &str = "test 1.3
+Kevin K.
+tests stuff
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+SUBCOMMANDS:
+    help    Prints this message or the help of the given subcommand(s)
+    sub     does things";
+
 static SKIP_POS_VALS: &str = "test 1.3
 Kevin K.
 tests stuff
@@ -92,6 +104,26 @@ fn global_version() {
     assert_eq!(app.subcommands[0].version, Some("1.1"));
 }
 
+#[test]
+fn global_version_long_and_explicit() {
+    let mut app = App::new("global_version")
+        .version("1.1")
+        .long_version("1.1 (abc123)")
+        .setting(AppSettings::GlobalVersion)
+        .subcommand(App::new("sub1").subcommand(App::new("nested")))
+        .subcommand(App::new("sub2").version("2.0"));
+    app._propagate(Propagation::Full);
+    assert_eq!(app.subcommands[0].version, Some("1.1"));
+    assert_eq!(app.subcommands[0].long_version, Some("1.1 (abc123)"));
+    assert_eq!(app.subcommands[0].subcommands[0].long_version, Some("1.1 (abc123)"));
+    assert_eq!(app.subcommands[1].version, Some("2.0"));
+    assert_eq!(app.subcommands[1].long_version, None);
+
+    let mut ver = vec![];
+    app.subcommands[0].write_long_version(&mut ver).unwrap();
+    assert_eq!(String::from_utf8(ver).unwrap(), "sub1 1.1 (abc123)");
+}
+
 #[test]
 fn sub_command_negate_required_2() {
     let result = App::new("sub_command_negate")
@@ -279,6 +311,33 @@ fn skip_possible_values() {
     ));
 }
 
+#[test]
+fn hide_usage() {
+    let app = App::new("test")
+        .author("Kevin K.")
+        .about("tests stuff")
+        .version("1.3")
+        .setting(AppSettings::HideUsage)
+        .subcommand(App::new("sub").about("does things"));
+
+    assert!(test::compare_output(
+        app,
+        "test --help",
+        HIDE_USAGE,
+        false
+    ));
+}
+
+#[test]
+fn hide_usage_template() {
+    let app = App::new("test")
+        .version("1.3")
+        .setting(AppSettings::HideUsage)
+        .help_template("{bin}: {usage}");
+
+    assert!(test::compare_output(app, "test --help", "test:", false));
+}
+
 #[test]
 fn global_setting() {
     let mut app = App::new("test")
@@ -492,6 +551,35 @@ fn leading_double_hyphen_trailingvararg() {
     );
 }
 
+#[test]
+fn passthrough_after_positional() {
+    let m = App::new("wrapper")
+        .setting(AppSettings::PassthroughAfterPositional)
+        .arg(Arg::from("-v 'verbose'"))
+        .arg(Arg::from("<cmd> 'command to run'"))
+        .arg(Arg::from("[args]... 'arguments for the command'"))
+        .get_matches_from(vec!["wrapper", "-v", "ls", "-v", "--all", "--", "-l"]);
+    assert_eq!(m.occurrences_of("v"), 1);
+    assert_eq!(m.value_of("cmd"), Some("ls"));
+    assert_eq!(
+        m.values_of("args").unwrap().collect::<Vec<_>>(),
+        &["-v", "--all", "--", "-l"]
+    );
+}
+
+#[test]
+fn passthrough_after_positional_double_hyphen() {
+    let m = App::new("wrapper")
+        .setting(AppSettings::PassthroughAfterPositional)
+        .arg(Arg::from("-v 'verbose'"))
+        .arg(Arg::from("<cmd> 'command to run'"))
+        .arg(Arg::from("[args]... 'arguments for the command'"))
+        .get_matches_from(vec!["wrapper", "--", "-v", "-l"]);
+    assert!(!m.is_present("v"));
+    assert_eq!(m.value_of("cmd"), Some("-v"));
+    assert_eq!(m.values_of("args").unwrap().collect::<Vec<_>>(), &["-l"]);
+}
+
 #[test]
 fn unset_setting() {
     let m = App::new("unset_setting").setting(AppSettings::AllArgsOverrideSelf);
@@ -959,3 +1047,353 @@ fn aaos_option_use_delim_false() {
         &["one,two"]
     );
 }
+
+#[test]
+fn color_never_plain_error_usage() {
+    let res = App::new("clap-test")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::from("-o, --opt [opt] 'some option'"))
+        .try_get_matches_from(vec!["clap-test", "--unknown"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert!(err.message.starts_with("error:"));
+    assert!(err
+        .message
+        .contains("USAGE:\n    clap-test [OPTIONS]"));
+}
+
+#[test]
+fn reparse_does_not_keep_trailing_values() {
+    let mut app = App::new("prog")
+        .arg(Arg::from("-f, --flag 'some flag'"))
+        .arg(Arg::from("[arg] 'some arg'"));
+
+    let m = app.try_get_matches_from_mut(vec!["prog", "--", "-f"]).unwrap();
+    assert!(!m.is_present("flag"));
+    assert_eq!(m.value_of("arg"), Some("-f"));
+
+    let m = app.try_get_matches_from_mut(vec!["prog", "-f"]).unwrap();
+    assert!(m.is_present("flag"));
+    assert!(!m.is_present("arg"));
+}
+
+#[test]
+fn reparse_subcommand_does_not_keep_trailing_values() {
+    let mut app = App::new("prog").subcommand(
+        App::new("sub")
+            .arg(Arg::from("-f, --flag 'some flag'"))
+            .arg(Arg::from("[arg] 'some arg'")),
+    );
+
+    let m = app
+        .try_get_matches_from_mut(vec!["prog", "sub", "--", "-f"])
+        .unwrap();
+    assert_eq!(
+        m.subcommand_matches("sub").unwrap().value_of("arg"),
+        Some("-f")
+    );
+
+    let m = app.try_get_matches_from_mut(vec!["prog", "sub", "-f"]).unwrap();
+    let sub_m = m.subcommand_matches("sub").unwrap();
+    assert!(sub_m.is_present("flag"));
+    assert!(!sub_m.is_present("arg"));
+}
+
+#[test]
+fn disable_colored_help_beats_color_always() {
+    let mut app = App::new("prog")
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::ColorAlways)
+        .setting(AppSettings::DisableColoredHelp)
+        .arg(Arg::from("-f, --flag 'some flag'"));
+
+    let mut help = Vec::new();
+    app.write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("-f, --flag"));
+    assert!(!help.contains('\u{1b}'));
+}
+
+#[test]
+fn args_pulled_lazily() {
+    let args = vec!["prog", "--help", "boom"]
+        .into_iter()
+        .map(|a| if a == "boom" { panic!("read past --help") } else { a });
+    let res = App::new("prog").try_get_matches_from(args);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::HelpDisplayed);
+}
+
+// A file unique to the calling test, which is removed again when dropped
+struct ArgsFile(String);
+
+impl ArgsFile {
+    fn new(name: &str, contents: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("clap-{}-{}", std::process::id(), name));
+        let file = ArgsFile(path.to_str().unwrap().to_owned());
+        file.write(contents);
+        file
+    }
+
+    fn write(&self, contents: &str) { std::fs::write(&self.0, contents).unwrap(); }
+}
+
+impl Drop for ArgsFile {
+    fn drop(&mut self) { let _ = std::fs::remove_file(&self.0); }
+}
+
+#[test]
+fn expand_args_from_file() {
+    let inner_file = ArgsFile::new("expand-inner.txt", "--opt 'two words'\n");
+    let inner = &inner_file.0;
+    let outer_file = ArgsFile::new("expand-outer.txt", &format!("first\n@{}\nsecond", inner));
+    let outer = &outer_file.0;
+    let m = App::new("prog")
+        .setting(AppSettings::ExpandArgsFromFile)
+        .arg(Arg::from("--opt [opt] 'some option'"))
+        .arg(Arg::from("[args]... 'some args'"))
+        .try_get_matches_from(vec![
+            "prog".to_owned(),
+            format!("@{}", outer),
+            "--".to_owned(),
+            format!("@{}", inner),
+        ])
+        .unwrap();
+    assert_eq!(m.value_of("opt"), Some("two words"));
+    assert_eq!(
+        m.values_of("args").unwrap().collect::<Vec<_>>(),
+        vec!["first", "second", &*format!("@{}", inner)]
+    );
+}
+
+#[test]
+fn expand_args_from_missing_file() {
+    let res = App::new("prog")
+        .setting(AppSettings::ExpandArgsFromFile)
+        .arg(Arg::from("[args]... 'some args'"))
+        .try_get_matches_from(vec!["prog", "@/this/file/does/not/exist"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::Io);
+}
+
+#[test]
+fn expand_args_from_file_cycle() {
+    let cycle = ArgsFile::new("expand-cycle.txt", "");
+    let file = &cycle.0;
+    cycle.write(&format!("a @{}", file));
+    let res = App::new("prog")
+        .setting(AppSettings::ExpandArgsFromFile)
+        .arg(Arg::from("[args]... 'some args'"))
+        .try_get_matches_from(vec!["prog".to_owned(), format!("@{}", file)]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::Format);
+}
+
+#[test]
+fn args_file_not_expanded_by_default() {
+    let m = App::new("prog")
+        .arg(Arg::from("[args]... 'some args'"))
+        .try_get_matches_from(vec!["prog", "@args.txt"])
+        .unwrap();
+    assert_eq!(m.value_of("args"), Some("@args.txt"));
+}
+
+#[test]
+fn unknown_args_collected() {
+    let m = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .arg(Arg::from("[target] 'the target'"))
+        .on_unknown_arg(|_| UnknownArgAction::Collect)
+        .try_get_matches_from(vec!["proxy", "--jobs=4", "-v", "-xz", "host", "--fast"])
+        .unwrap();
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.value_of("target"), Some("host"));
+    assert_eq!(
+        m.unknown_args().collect::<Vec<_>>(),
+        ["--jobs=4", "-xz", "--fast"]
+    );
+}
+
+#[test]
+fn overlay_keeps_overrides_and_unknown_args() {
+    let app = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'").overrides_with("quiet"))
+        .arg(Arg::from("-q, --quiet 'be quiet'").overrides_with("verbose"))
+        .arg(Arg::from("-d, --debug 'debug output'").overrides_with("color"))
+        .arg(Arg::from("-c, --color 'color output'"))
+        .on_unknown_arg(|_| UnknownArgAction::Collect);
+    let lower = app
+        .clone()
+        .get_matches_from(vec!["proxy", "-v", "-d", "-c", "--jobs=4"]);
+    let mut m = app.get_matches_from(vec!["proxy", "-v", "-q", "--fast"]);
+    m.overlay(lower);
+
+    // Overridden by `quiet` here, so `verbose` from `lower` doesn't come back
+    assert!(!m.is_present("verbose"));
+    assert_eq!(m.overridden_by("verbose"), Some("quiet"));
+    assert!(m.is_present("quiet"));
+    // Overridden in `lower` and not used here
+    assert!(!m.is_present("debug"));
+    assert_eq!(m.overridden_by("debug"), Some("color"));
+    assert!(m.is_present("color"));
+    assert_eq!(m.unknown_args().collect::<Vec<_>>(), ["--fast", "--jobs=4"]);
+}
+
+#[test]
+fn overlay_skips_overridden_and_conflicting_args() {
+    let app = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'").overrides_with("quiet"))
+        .arg(Arg::from("-q, --quiet 'be quiet'").overrides_with("verbose"))
+        .arg(Arg::from("-d, --debug 'debug output'").conflicts_with("color"))
+        .arg(Arg::from("-c, --color 'color output'"));
+    let lower = app.clone().get_matches_from(vec!["proxy", "-v", "-c"]);
+    let mut m = app.get_matches_from(vec!["proxy", "-q", "-d"]);
+    m.overlay(lower);
+
+    assert!(m.is_present("quiet"));
+    assert!(!m.is_present("verbose"));
+    assert!(m.is_present("debug"));
+    assert!(!m.is_present("color"));
+}
+
+#[test]
+fn unknown_args_ignored() {
+    let m = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .on_unknown_arg(|_| UnknownArgAction::Ignore)
+        .try_get_matches_from(vec!["proxy", "--jobs", "-v"])
+        .unwrap();
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.unknown_args().count(), 0);
+}
+
+#[test]
+fn unknown_args_error() {
+    let mut seen = Vec::new();
+    let res = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .on_unknown_arg(|arg| {
+            seen.push(arg.to_os_string());
+            if arg == "--jobs" {
+                UnknownArgAction::Collect
+            } else {
+                UnknownArgAction::Error
+            }
+        })
+        .try_get_matches_from(vec!["proxy", "--jobs", "--bogus", "-v"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+    assert_eq!(seen, ["--jobs", "--bogus"]);
+}
+
+#[test]
+fn unknown_args_without_callback() {
+    let m = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .try_get_matches_from(vec!["proxy", "-v"])
+        .unwrap();
+    assert_eq!(m.unknown_args().count(), 0);
+
+    let res = App::new("proxy")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .try_get_matches_from(vec!["proxy", "--jobs"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+#[should_panic(expected = "argument `undocumented` has no help text")]
+fn help_expected_missing_help() {
+    App::new("myprog")
+        .setting(AppSettings::HelpExpected)
+        .arg(Arg::with_name("documented").long("documented").help("Has help"))
+        .arg(Arg::with_name("undocumented").long("undocumented"))
+        .get_matches_from(vec!["myprog"]);
+}
+
+#[test]
+fn help_expected_hidden_and_long_help() {
+    let res = App::new("myprog")
+        .setting(AppSettings::HelpExpected)
+        .arg(Arg::with_name("long").long("long").long_help("Only long help"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .arg(Arg::with_name("input").help("The input"))
+        .try_get_matches_from(vec!["myprog"]);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn no_auto_error_version() {
+    let err = App::new("myprog")
+        .version("1.0")
+        .setting(AppSettings::NoAutoError)
+        .try_get_matches_from(vec!["myprog", "-V"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    assert_eq!(err.message, "myprog 1.0");
+}
+
+#[test]
+fn version_printed_without_no_auto_error() {
+    let err = App::new("myprog")
+        .version("1.0")
+        .try_get_matches_from(vec!["myprog", "-V"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    assert_eq!(err.message, "");
+}
+
+#[test]
+fn no_auto_error_subcommand_version() {
+    let err = App::new("myprog")
+        .global_setting(AppSettings::NoAutoError)
+        .subcommand(App::new("sub").version("2.0"))
+        .try_get_matches_from(vec!["myprog", "sub", "--version"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    assert_eq!(err.message, "myprog-sub 2.0");
+}
+
+fn collect_errors_app() -> App<'static> {
+    App::new("myprog")
+        .setting(AppSettings::CollectErrors)
+        .arg(Arg::from("-f, --flag 'some flag'"))
+        .arg(Arg::from("--mode [MODE] 'the mode'").possible_values(&["fast", "slow"]))
+        .arg(Arg::from("<input> 'the input'"))
+}
+
+#[test]
+fn collect_errors_reports_all() {
+    let err = collect_errors_app()
+        .try_get_matches_from(vec!["myprog", "--unknown", "-x", "--mode", "medium"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    let kinds = err.errors.iter().map(|e| e.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::UnknownArgument,
+            ErrorKind::UnknownArgument,
+            ErrorKind::MissingRequiredArgument,
+            ErrorKind::InvalidValue,
+        ]
+    );
+    for e in &err.errors {
+        assert!(err.message.contains(&*e.message));
+    }
+    assert_eq!(err.exit_code(), 1);
+}
+
+#[test]
+fn collect_errors_success() {
+    let m = collect_errors_app()
+        .try_get_matches_from(vec!["myprog", "-f", "file"])
+        .unwrap();
+    assert!(m.is_present("flag"));
+    assert_eq!(m.value_of("input"), Some("file"));
+}
+
+#[test]
+fn collect_errors_help_still_exits() {
+    let err = collect_errors_app()
+        .try_get_matches_from(vec!["myprog", "--unknown", "--help"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+}
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg};
+use clap::{App, Arg, ErrorKind};
 
 static SC_VISIBLE_ALIAS_HELP: &str = "ct-test 1.2
 Some help
@@ -212,3 +212,153 @@ fn visible_arg_aliases_help_output() {
         false
     ));
 }
+
+#[test]
+fn alias_of_option_with_equals() {
+    let a = App::new("equals").arg(
+        Arg::with_name("config")
+            .long("config")
+            .takes_value(true)
+            .alias("conf")
+            .visible_alias("cfg"),
+    );
+
+    for args in &[
+        ["", "--conf=foo.toml"],
+        ["", "--cfg=foo.toml"],
+        ["", "--config=foo.toml"],
+    ] {
+        let m = a.clone().try_get_matches_from(args.iter());
+        assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+        assert_eq!(m.unwrap().value_of("config"), Some("foo.toml"));
+    }
+}
+
+#[test]
+fn alias_of_option_with_space() {
+    let a = App::new("space").arg(
+        Arg::with_name("config")
+            .long("config")
+            .takes_value(true)
+            .alias("conf")
+            .visible_alias("cfg"),
+    );
+
+    for args in &[["", "--conf", "foo.toml"], ["", "--cfg", "foo.toml"]] {
+        let m = a.clone().try_get_matches_from(args.iter());
+        assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+        assert_eq!(m.unwrap().value_of("config"), Some("foo.toml"));
+    }
+}
+
+#[test]
+fn alias_of_help_flag() {
+    let m = App::new("help_alias")
+        .mut_arg("help", |h| h.short('h').long("help").alias("usage"))
+        .try_get_matches_from(vec!["", "--usage"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::HelpDisplayed);
+}
+
+#[test]
+fn single_short_alias_of_option() {
+    let a = App::new("single_alias")
+        .arg(
+            Arg::with_name("alias")
+                .short('a')
+                .takes_value(true)
+                .help("single short alias")
+                .short_alias('n'),
+        )
+        .try_get_matches_from(vec!["", "-n", "cool"]);
+    assert!(a.is_ok());
+    let a = a.unwrap();
+    assert!(a.is_present("alias"));
+    assert_eq!(a.value_of("alias").unwrap(), "cool");
+}
+
+#[test]
+fn short_alias_of_option_with_attached_value() {
+    let m = App::new("single_alias")
+        .arg(
+            Arg::with_name("alias")
+                .short('a')
+                .takes_value(true)
+                .short_alias('n'),
+        )
+        .arg(Arg::with_name("flag").short('f'))
+        .try_get_matches_from(vec!["", "-fncool"])
+        .unwrap();
+    assert!(m.is_present("flag"));
+    assert_eq!(m.value_of("alias").unwrap(), "cool");
+}
+
+#[test]
+fn short_aliases_stacked_flags() {
+    let m = App::new("stacked")
+        .arg(
+            Arg::with_name("verbose")
+                .short('v')
+                .multiple_occurrences(true)
+                .short_aliases(&['x', 'y']),
+        )
+        .arg(Arg::with_name("quiet").short('q').visible_short_alias('s'))
+        .try_get_matches_from(vec!["", "-xsy"])
+        .unwrap();
+    assert_eq!(m.occurrences_of("verbose"), 2);
+    assert!(m.is_present("quiet"));
+}
+
+#[test]
+fn short_alias_unknown_without_alias() {
+    let res = App::new("single_alias")
+        .arg(Arg::with_name("flag").short('f').short_alias('g'))
+        .try_get_matches_from(vec!["", "-z"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn short_aliases_take_auto_help_and_version_shorts() {
+    let m = App::new("aliased")
+        .version("1.0")
+        .arg(Arg::with_name("human").long("human").short_alias('h'))
+        .arg(Arg::with_name("verify").long("verify").short_alias('V'))
+        .try_get_matches_from(vec!["", "-h", "-V"])
+        .unwrap();
+    assert!(m.is_present("human"));
+    assert!(m.is_present("verify"));
+}
+
+#[test]
+fn visible_short_aliases_help_output() {
+    let app = App::new("ct")
+        .arg(
+            Arg::with_name("flag")
+                .short('f')
+                .long("flag")
+                .visible_short_aliases(&['x', 'y'])
+                .short_alias('z'),
+        )
+        .arg(
+            Arg::with_name("opt")
+                .long("opt")
+                .short('o')
+                .takes_value(true)
+                .short_alias('p'),
+        );
+    let err = app
+        .try_get_matches_from(vec!["ct", "--help"])
+        .unwrap_err();
+    assert!(err.message.contains("[short aliases: -x, -y]"));
+    assert!(!err.message.contains("-z"));
+    assert!(!err.message.contains("-p"));
+}
+
+#[test]
+#[should_panic(expected = "Argument short must be unique")]
+fn short_alias_conflicts_with_short() {
+    let _ = App::new("conflict")
+        .arg(Arg::with_name("one").short('a'))
+        .arg(Arg::with_name("two").short('b').short_alias('a'))
+        .try_get_matches_from(vec![""]);
+}
@@ -0,0 +1,368 @@
+extern crate clap;
+
+use std::ffi::OsStr;
+
+use clap::{complete, App, AppSettings, Arg, ErrorKind, ValueHint};
+
+static BASH: &str = include_str!("complete_bash.txt");
+
+static ELVISH: &str = include_str!("complete_elvish.txt");
+
+static ZSH: &str = include_str!("complete_zsh.txt");
+
+static FISH: &str = r#"complete -c myprog -n "__fish_use_subcommand" -s c -l config -d 'Sets a custom config file' -r
+complete -c myprog -n "__fish_use_subcommand" -l color -d 'Doesn\'t always use colors' -r -f -a "always never auto"
+complete -c myprog -n "__fish_use_subcommand" -s v -l verbose -d 'Be verbose'
+complete -c myprog -n "__fish_use_subcommand" -s h -l help -d 'Prints help information'
+complete -c myprog -n "__fish_use_subcommand" -s V -l version -d 'Prints version information'
+complete -c myprog -n "__fish_use_subcommand" -f -a "json yaml" -d 'The output format'
+complete -c myprog -n "__fish_use_subcommand" -f -a "test" -d 'Tests things'
+complete -c myprog -n "__fish_use_subcommand" -f -a "help" -d 'Prints this message or the help of the given subcommand(s)'
+complete -c myprog -n "__fish_seen_subcommand_from test" -l case -d 'The case to test' -r
+complete -c myprog -n "__fish_seen_subcommand_from test" -s h -l help -d 'Prints help information'
+complete -c myprog -n "__fish_seen_subcommand_from test" -s V -l version -d 'Prints version information'
+complete -c myprog -n "__fish_seen_subcommand_from help" -s h -l help -d 'Prints help information'
+complete -c myprog -n "__fish_seen_subcommand_from help" -s V -l version -d 'Prints version information'
+"#;
+
+static POWERSHELL: &str = r#"using namespace System.Management.Automation
+using namespace System.Management.Automation.Language
+
+Register-ArgumentCompleter -Native -CommandName 'myprog' -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $commandElements = $commandAst.CommandElements
+    $command = @(
+        'myprog'
+        for ($i = 1; $i -lt $commandElements.Count; $i++) {
+            $element = $commandElements[$i]
+            if ($element -isnot [StringConstantExpressionAst] -or
+                $element.StringConstantType -ne [StringConstantType]::BareWord -or
+                $element.Value.StartsWith('-')) {
+                break
+            }
+            $element.Value
+        }) -join ';'
+
+    $completions = @(switch ($command) {
+        'myprog' {
+            [CompletionResult]::new('-c', 'c', [CompletionResultType]::ParameterName, 'Sets a custom config file')
+            [CompletionResult]::new('--config', 'config', [CompletionResultType]::ParameterName, 'Sets a custom config file')
+            [CompletionResult]::new('--color', 'color', [CompletionResultType]::ParameterName, 'Doesn''t always use colors')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'Be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'Be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Prints help information')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Prints help information')
+            [CompletionResult]::new('-V', 'V', [CompletionResultType]::ParameterName, 'Prints version information')
+            [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'Prints version information')
+            [CompletionResult]::new('test', 'test', [CompletionResultType]::ParameterValue, 'Tests things')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Prints this message or the help of the given subcommand(s)')
+            break
+        }
+        'myprog;test' {
+            [CompletionResult]::new('--case', 'case', [CompletionResultType]::ParameterName, 'The case to test')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Prints help information')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Prints help information')
+            [CompletionResult]::new('-V', 'V', [CompletionResultType]::ParameterName, 'Prints version information')
+            [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'Prints version information')
+            break
+        }
+        'myprog;help' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Prints help information')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Prints help information')
+            [CompletionResult]::new('-V', 'V', [CompletionResultType]::ParameterName, 'Prints version information')
+            [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'Prints version information')
+            break
+        }
+    })
+
+    $completions.Where{ $_.CompletionText -like "$wordToComplete*" } |
+        Sort-Object -Property ListItemText
+}
+"#;
+
+fn build_app() -> App<'static> {
+    App::new("myprog")
+        .version("1.0")
+        .arg(
+            Arg::with_name("config")
+                .short('c')
+                .long("config")
+                .takes_value(true)
+                .help("Sets a custom config file"),
+        )
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .help("Doesn't always use colors")
+                .possible_values(&["always", "never", "auto"]),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Be verbose"),
+        )
+        .arg(
+            Arg::with_name("secret")
+                .long("secret")
+                .hidden(true),
+        )
+        .arg(
+            Arg::with_name("format")
+                .help("The output format")
+                .possible_values(&["json", "yaml"]),
+        )
+        .subcommand(
+            App::new("test")
+                .about("Tests things")
+                .arg(
+                    Arg::with_name("case")
+                        .long("case")
+                        .takes_value(true)
+                        .help("The case to test"),
+                ),
+        )
+        .subcommand(App::new("internal").setting(AppSettings::Hidden))
+}
+
+#[test]
+fn bash() {
+    let app = build_app();
+    let mut buf = Vec::new();
+    complete::bash(&app, "myprog", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), BASH);
+}
+
+#[test]
+fn bash_value_hints() {
+    let app = App::new("my-prog")
+        .arg(Arg::with_name("dir").long("dir").value_hint(ValueHint::DirPath))
+        .arg(Arg::with_name("host").short('H').value_hint(ValueHint::Hostname))
+        .arg(Arg::with_name("name").long("name").value_hint(ValueHint::Other))
+        .arg(
+            Arg::with_name("price")
+                .long("price")
+                .possible_values(&["$5", "two words"]),
+        );
+    let mut buf = Vec::new();
+    complete::bash(&app, "my-prog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.starts_with("_my_prog() {\n"));
+    assert!(script.contains("'--dir')\n                    COMPREPLY=($(compgen -d -- "));
+    assert!(script.contains("'-H')\n                    COMPREPLY=($(compgen -A hostname -- "));
+    assert!(script.contains("'--name')\n                    COMPREPLY=()\n"));
+    assert!(script.contains("COMPREPLY=($(compgen -W '\\$5 two\\ words' -- "));
+}
+
+#[test]
+fn fish() {
+    let app = build_app();
+    let mut buf = Vec::new();
+    complete::fish(&app, "myprog", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), FISH);
+}
+
+#[test]
+fn fish_value_hints() {
+    let app = App::new("myprog")
+        .arg(Arg::with_name("dir").long("dir").value_hint(ValueHint::DirPath))
+        .arg(Arg::with_name("file").long("file").value_hint(ValueHint::FilePath))
+        .arg(Arg::with_name("host").long("host").value_hint(ValueHint::Hostname))
+        .arg(Arg::with_name("name").long("name").value_hint(ValueHint::Other))
+        .arg(Arg::with_name("any").long("any").takes_value(true))
+        .arg(Arg::with_name("cmd").value_hint(ValueHint::CommandName));
+    let mut buf = Vec::new();
+    complete::fish(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("-l dir -r -f -a \"(__fish_complete_directories)\"\n"));
+    assert!(script.contains("-l file -r -F\n"));
+    assert!(script.contains("-l host -r -f -a \"(__fish_print_hostnames)\"\n"));
+    assert!(script.contains("-l name -r -f\n"));
+    assert!(script.contains("-l any -r\n"));
+    assert!(script.contains("__fish_use_subcommand\" -f -a \"(__fish_complete_command)\"\n"));
+}
+
+#[test]
+fn fish_sorted_possible_values() {
+    let app = App::new("myprog").arg(
+        Arg::with_name("color")
+            .long("color")
+            .possible_values(&["never", "auto", "always"])
+            .sort_possible_values(true),
+    );
+    let mut buf = Vec::new();
+    complete::fish(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("-l color -r -f -a \"always auto never\"\n"));
+}
+
+#[test]
+fn fish_possible_values_os() {
+    let app = App::new("myprog").arg(
+        Arg::with_name("color")
+            .long("color")
+            .possible_values(&["never", "auto"])
+            .possible_values_os(&[OsStr::new("always")])
+            .sort_possible_values(true),
+    );
+    let mut buf = Vec::new();
+    complete::fish(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("-l color -r -f -a \"always auto never\"\n"));
+}
+
+#[test]
+fn fish_headings_and_aliases() {
+    let app = App::new("myprog")
+        .help_heading("NETWORK")
+        .arg(
+            Arg::with_name("port")
+                .short('p')
+                .long("port")
+                .visible_short_alias('P')
+                .visible_alias("listen")
+                .alias("secret-port")
+                .takes_value(true),
+        )
+        .arg(Arg::with_name("offline").long("offline"));
+    let mut buf = Vec::new();
+    complete::fish(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("-s p -s P -l port -l listen -r\n"));
+    assert!(script.contains("-l offline\n"));
+    assert!(!script.contains("secret-port"));
+}
+
+#[test]
+fn fish_escaped_possible_values() {
+    let app = App::new("myprog").arg(
+        Arg::with_name("price")
+            .long("price")
+            .possible_values(&["$5", "two words", "\"quoted\""]),
+    );
+    let mut buf = Vec::new();
+    complete::fish(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains(r#"-l price -r -f -a "\\\$5 two\\ words \\\"quoted\\\"""#));
+}
+
+#[test]
+fn powershell() {
+    let app = build_app();
+    let mut buf = Vec::new();
+    complete::powershell(&app, "myprog", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), POWERSHELL);
+}
+
+#[test]
+fn powershell_headings_and_aliases() {
+    let app = App::new("myprog")
+        .help_heading("NETWORK")
+        .arg(
+            Arg::with_name("port")
+                .short('p')
+                .long("port")
+                .visible_short_alias('P')
+                .visible_alias("listen")
+                .alias("secret-port")
+                .takes_value(true)
+                .help("The port"),
+        );
+    let mut buf = Vec::new();
+    complete::powershell(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    for &(text, list_text) in &[
+        ("-p", "p"),
+        ("-P", "P"),
+        ("--port", "port"),
+        ("--listen", "listen"),
+    ] {
+        assert!(script.contains(&format!(
+            "[CompletionResult]::new('{}', '{}', [CompletionResultType]::ParameterName, 'The port')",
+            text, list_text
+        )));
+    }
+    assert!(!script.contains("secret-port"));
+}
+
+#[test]
+fn elvish() {
+    let app = build_app();
+    let mut buf = Vec::new();
+    complete::elvish(&app, "myprog", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), ELVISH);
+}
+
+#[test]
+fn elvish_headings_and_aliases() {
+    let app = App::new("myprog")
+        .help_heading("NETWORK")
+        .arg(
+            Arg::with_name("port")
+                .short('p')
+                .long("port")
+                .visible_short_alias('P')
+                .visible_alias("listen")
+                .alias("secret-port")
+                .takes_value(true)
+                .help("The port"),
+        );
+    let mut buf = Vec::new();
+    complete::elvish(&app, "myprog", &mut buf).unwrap();
+    let script = String::from_utf8(buf).unwrap();
+
+    for cand in &["-p", "-P", "--port", "--listen"] {
+        assert!(script.contains(&format!("cand '{}' 'The port'", cand)));
+    }
+    assert!(!script.contains("secret-port"));
+}
+
+#[test]
+fn zsh() {
+    let app = build_app();
+    let mut buf = Vec::new();
+    complete::zsh(&app, "myprog", &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), ZSH);
+}
+
+#[test]
+fn completions_subcommand() {
+    let err = build_app()
+        .gen_completions_subcommand()
+        .try_get_matches_from(vec!["/usr/bin/myprog", "completions", "fish"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    assert!(!err.use_stderr());
+    assert_eq!(err.message, FISH);
+
+    let err = build_app()
+        .gen_completions_subcommand()
+        .try_get_matches_from(vec!["myprog", "completions", "powershell"])
+        .unwrap_err();
+    assert_eq!(err.message, POWERSHELL);
+
+    for &(shell, script) in &[("bash", BASH), ("elvish", ELVISH), ("zsh", ZSH)] {
+        let err = build_app()
+            .gen_completions_subcommand()
+            .try_get_matches_from(vec!["myprog", "completions", shell])
+            .unwrap_err();
+        assert_eq!(err.message, script);
+    }
+}
+
+#[test]
+fn completions_subcommand_unknown_shell() {
+    let err = build_app()
+        .gen_completions_subcommand()
+        .try_get_matches_from(vec!["myprog", "completions", "tcsh"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+}
@@ -108,3 +108,33 @@ fn conflict_with_unused_default_value() {
     assert_eq!(m.value_of("opt"), Some("default"));
     assert!(m.is_present("flag"));
 }
+
+fn two_way_app() -> App<'static> {
+    App::new("prog")
+        .arg(Arg::from("-f, --flag 'some flag'").conflicts_with_all(&["other", "input"]))
+        .arg(Arg::from("-o, --other 'some other flag'"))
+        .arg(Arg::from("<input> 'some input'"))
+}
+
+#[test]
+fn conflict_either_order_names_both() {
+    for args in &[["prog", "-f", "-o", "in"], ["prog", "-o", "-f", "in"]] {
+        let err = two_way_app().try_get_matches_from(args.to_vec()).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+        let info = err.info.unwrap();
+        assert!(
+            info.iter().any(|i| i.contains("flag")) && info.iter().any(|i| i.contains("other")),
+            "{:?}",
+            info
+        );
+    }
+}
+
+#[test]
+fn conflict_makes_other_required_optional() {
+    let m = two_way_app()
+        .try_get_matches_from(vec!["prog", "--flag"])
+        .unwrap();
+    assert!(m.is_present("flag"));
+    assert!(!m.is_present("input"));
+}
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind};
+use clap::{App, Arg, ErrorKind, ValueSource};
 
 #[test]
 fn opts() {
@@ -495,3 +495,160 @@ fn issue_1050_num_vals_and_defaults() {
     let m = res.unwrap();
     assert_eq!(m.value_of("exit-code"), Some("1"));
 }
+
+static DEFAULT_IF_HELP: &str = "prog 
+
+USAGE:
+    prog [FLAGS] [OPTIONS]
+
+FLAGS:
+        --api        use the api
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --output <output>    output format [default: text]";
+
+#[test]
+fn default_if_not_shown_in_help() {
+    let app = App::new("prog")
+        .arg(Arg::from("--api 'use the api'"))
+        .arg(
+            Arg::from("--output [output] 'output format'")
+                .default_value("text")
+                .default_value_if("api", None, "json"),
+        );
+    assert!(test::compare_output(
+        app,
+        "prog --help",
+        DEFAULT_IF_HELP,
+        false
+    ));
+}
+
+#[test]
+fn args_present_skips_defaults() {
+    let m = App::new("prog")
+        .arg(Arg::from("-f, --flag 'some flag'"))
+        .arg(Arg::from("-o, --opt [opt] 'some opt'").default_value("default"))
+        .arg(Arg::from("-c, --color [color] 'some color'").default_value("auto"))
+        .arg(Arg::from("[arg] 'some arg'").default_value("default"))
+        .get_matches_from(vec!["prog", "--color", "never", "-f"]);
+
+    assert!(m.is_present("opt"));
+    assert!(m.is_present("arg"));
+    assert_eq!(m.args_present(), ["color", "flag"]);
+}
+
+#[test]
+fn overlay_replaces_default_vals() {
+    let app = App::new("df")
+        .arg(Arg::from("-o, --opt [FILE] 'some arg'").default_value("default"))
+        .arg(Arg::from("-c, --color [WHEN] 'some arg'").default_value("auto"));
+    let lower = app.clone().get_matches_from(vec!["", "--opt", "config"]);
+    let mut m = app.get_matches_from(vec![""]);
+    m.overlay(lower);
+    assert_eq!(m.value_of("opt"), Some("config"));
+    assert_eq!(m.occurrences_of("opt"), 1);
+    // Defaults on both sides stay defaults
+    assert_eq!(m.value_of("color"), Some("auto"));
+    assert_eq!(m.occurrences_of("color"), 0);
+}
+
+fn color_app() -> App<'static> {
+    App::new("prog")
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .value_name("WHEN")
+                .help("when to color output")
+                .possible_values(&["always", "auto", "never"])
+                .default_value("never")
+                .default_missing_value("auto"),
+        )
+        .arg(Arg::from("-f, --flag 'some flag'"))
+}
+
+#[test]
+fn default_missing_value_used_without_value() {
+    let m = color_app()
+        .try_get_matches_from(vec!["prog", "--color"])
+        .unwrap();
+    assert_eq!(m.value_of("color"), Some("auto"));
+    assert_eq!(m.occurrences_of("color"), 1);
+}
+
+#[test]
+fn default_missing_value_user_override() {
+    let m = color_app()
+        .try_get_matches_from(vec!["prog", "--color=always"])
+        .unwrap();
+    assert_eq!(m.value_of("color"), Some("always"));
+}
+
+#[test]
+fn default_missing_value_not_used() {
+    let m = color_app().try_get_matches_from(vec!["prog"]).unwrap();
+    assert_eq!(m.value_of("color"), Some("never"));
+    assert_eq!(m.occurrences_of("color"), 0);
+}
+
+#[test]
+fn default_missing_value_followed_by_flag() {
+    let m = color_app()
+        .try_get_matches_from(vec!["prog", "--color", "-f"])
+        .unwrap();
+    assert_eq!(m.value_of("color"), Some("auto"));
+    assert!(m.is_present("flag"));
+}
+
+#[test]
+fn default_missing_value_help() {
+    let err = color_app()
+        .try_get_matches_from(vec!["prog", "--help"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("--color[=<WHEN>]"));
+}
+
+#[test]
+fn value_source_of_defaults() {
+    let app = App::new("df")
+        .arg(Arg::from("-f, --flag 'some flag'"))
+        .arg(Arg::from("-o, --opt [FILE] 'some arg'").default_value("default"))
+        .arg(
+            Arg::from("-c, --color [WHEN] 'some arg'")
+                .default_value("never")
+                .default_value_if("flag", None, "always"),
+        );
+
+    let m = app.clone().try_get_matches_from(vec![""]).unwrap();
+    assert_eq!(m.value_source("opt"), Some(ValueSource::DefaultValue));
+    assert_eq!(m.value_source("color"), Some(ValueSource::DefaultValue));
+    assert_eq!(m.value_source("flag"), None);
+
+    let m = app.try_get_matches_from(vec!["", "-f", "-o", "file"]).unwrap();
+    assert_eq!(m.value_source("opt"), Some(ValueSource::CommandLine));
+    assert_eq!(m.value_of("color"), Some("always"));
+    assert_eq!(m.value_source("color"), Some(ValueSource::ConditionalDefaultValue));
+    assert_eq!(m.value_source("flag"), Some(ValueSource::CommandLine));
+}
+
+#[test]
+fn default_value_hidden() {
+    let mut app = App::new("df")
+        .arg(Arg::from("-t, --token [FILE] 'the token'").default_value_hidden("/etc/df/token"));
+
+    let help = app.write_long_help_to_string().unwrap();
+    assert!(help.contains("the token"), "{:?}", help);
+    assert!(!help.contains("/etc/df/token"), "{:?}", help);
+
+    let m = app.clone().try_get_matches_from(vec![""]).unwrap();
+    assert_eq!(m.value_of("token"), Some("/etc/df/token"));
+    assert_eq!(m.value_source("token"), Some(ValueSource::DefaultValue));
+    assert_eq!(m.occurrences_of("token"), 0);
+
+    let m = app.try_get_matches_from(vec!["", "-t", "mine"]).unwrap();
+    assert_eq!(m.value_of("token"), Some("mine"));
+    assert_eq!(m.value_source("token"), Some(ValueSource::CommandLine));
+}
@@ -132,3 +132,75 @@ fn opt_eq_mult_def_delim() {
         &["val1", "val2", "val3"]
     );
 }
+
+#[test]
+fn opt_custom_delim_empty_val() {
+    let m = App::new("delim")
+        .arg(
+            Arg::with_name("option")
+                .long("opt")
+                .multiple(true)
+                .value_delimiter(":")
+                .setting(ArgSettings::AllowEmptyValues),
+        )
+        .try_get_matches_from(vec!["", "--opt=val1::val3"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+
+    assert_eq!(
+        m.values_of("option").unwrap().collect::<Vec<_>>(),
+        &["val1", "", "val3"]
+    );
+}
+
+#[test]
+fn opt_delim_escaped() {
+    let m = App::new("delim")
+        .arg(
+            Arg::with_name("option")
+                .long("opt")
+                .multiple(true)
+                .value_delimiter(":")
+                .allow_delimiter_escape(true),
+        )
+        .try_get_matches_from(vec!["", r"--opt=C\:\dir:val2\:", r"--opt=a\b"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+
+    assert_eq!(
+        m.values_of("option").unwrap().collect::<Vec<_>>(),
+        &[r"C:\dir", "val2:", r"a\b"]
+    );
+}
+
+#[test]
+fn opt_delim_escape_not_allowed() {
+    let m = App::new("delim")
+        .arg(
+            Arg::with_name("option")
+                .long("opt")
+                .multiple(true)
+                .value_delimiter(":"),
+        )
+        .try_get_matches_from(vec!["", r"--opt=val1\:val2"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+
+    assert_eq!(
+        m.values_of("option").unwrap().collect::<Vec<_>>(),
+        &[r"val1\", "val2"]
+    );
+}
+
+#[test]
+fn delim_escape_unset_keeps_takes_value() {
+    let a = Arg::with_name("option")
+        .long("opt")
+        .allow_delimiter_escape(true)
+        .allow_delimiter_escape(false);
+    assert!(a.is_set(ArgSettings::TakesValue));
+    assert!(!a.is_set(ArgSettings::AllowDelimiterEscape));
+}
@@ -117,6 +117,32 @@ OPTIONS:
     -h, --help                   Prints help information
     -V, --version                Prints version information";
 
+static SORT_POSITIONALS: &str = "test 1.2
+
+USAGE:
+    test [ARGS]
+
+ARGS:
+    <first>     first pos
+    <second>    second pos
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information";
+
+static NO_SORT_POSITIONALS: &str = "test 1.2
+
+USAGE:
+    test [ARGS]
+
+ARGS:
+    <second>    second pos
+    <first>     first pos
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information";
+
 #[test]
 fn no_derive_order() {
     let app = App::new("test").version("1.2").args(&[
@@ -332,3 +358,48 @@ fn unified_help_and_derive_order_subcommand_propagate_with_explicit_display_orde
         false
     ));
 }
+
+#[test]
+fn sort_positionals() {
+    let app = App::new("test")
+        .setting(AppSettings::SortPositionals)
+        .version("1.2")
+        .args(&[
+            Arg::with_name("second")
+                .index(1)
+                .display_order(2)
+                .help("second pos"),
+            Arg::with_name("first")
+                .index(2)
+                .display_order(1)
+                .help("first pos"),
+        ]);
+
+    assert!(test::compare_output(
+        app,
+        "test --help",
+        SORT_POSITIONALS,
+        false
+    ));
+}
+
+#[test]
+fn no_sort_positionals() {
+    let app = App::new("test").version("1.2").args(&[
+        Arg::with_name("second")
+            .index(1)
+            .display_order(2)
+            .help("second pos"),
+        Arg::with_name("first")
+            .index(2)
+            .display_order(1)
+            .help("first pos"),
+    ]);
+
+    assert!(test::compare_output(
+        app,
+        "test --help",
+        NO_SORT_POSITIONALS,
+        false
+    ));
+}
@@ -3,7 +3,7 @@ extern crate clap;
 use std::env;
 use std::ffi::OsStr;
 
-use clap::{App, Arg};
+use clap::{App, Arg, ValueSource};
 
 #[test]
 fn env() {
@@ -275,3 +275,39 @@ fn validator_invalid() {
 
     assert!(r.is_err());
 }
+
+#[test]
+fn hide_env_still_read() {
+    env::set_var("CLP_TEST_ENV_HIDDEN", "env");
+
+    let r = App::new("df")
+        .arg(
+            Arg::from("[arg] 'some opt'")
+                .env("CLP_TEST_ENV_HIDDEN")
+                .hide_env(true),
+        )
+        .try_get_matches_from(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn env_value_source() {
+    env::set_var("CLP_TEST_ENV_SOURCE", "env");
+
+    let app = App::new("df").arg(
+        Arg::from("[arg] 'some opt'")
+            .env("CLP_TEST_ENV_SOURCE")
+            .default_value("default"),
+    );
+
+    let m = app.clone().try_get_matches_from(vec![""]).unwrap();
+    assert_eq!(m.value_of("arg"), Some("env"));
+    assert_eq!(m.value_source("arg"), Some(ValueSource::EnvVariable));
+
+    let m = app.try_get_matches_from(vec!["", "user"]).unwrap();
+    assert_eq!(m.value_of("arg"), Some("user"));
+    assert_eq!(m.value_source("arg"), Some(ValueSource::CommandLine));
+}
@@ -136,3 +136,25 @@ fn short_flag_name_missing() {
     assert!(a.val_names.is_none());
     assert!(a.num_vals.is_none());
 }
+
+#[test]
+fn get_flag() {
+    let m = App::new("flag")
+        .arg(Arg::from("-f, --flag 'some flag'"))
+        .arg(Arg::from("-c, --color 'some other flag'"))
+        .subcommand(App::new("sub").arg(Arg::from("-v, --verbose 'be verbose'")))
+        .get_matches_from(vec!["", "-f", "sub", "-v"]);
+    assert!(m.get_flag("flag"));
+    assert!(!m.get_flag("color"));
+    assert!(m.subcommand_matches("sub").unwrap().get_flag("verbose"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the argument \"option\" isn't a flag declared on this App")]
+fn get_flag_of_option() {
+    let m = App::new("flag")
+        .arg(Arg::from("-o, --option [opt] 'some option'"))
+        .get_matches_from(vec!["", "-o", "val"]);
+    m.get_flag("option");
+}
@@ -229,3 +229,96 @@ fn group_acts_like_arg() {
         .get_matches_from(vec!["prog", "--debug"]);
     assert!(m.is_present("mode"));
 }
+
+#[test]
+fn group_value_names_chosen_arg() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("debug").long("debug"))
+        .arg(Arg::with_name("level").long("level").takes_value(true))
+        .arg(Arg::with_name("file"))
+        .group(
+            ArgGroup::with_name("mode")
+                .args(&["debug", "level", "file"])
+                .required(true),
+        )
+        .get_matches_from(vec!["prog", "--level", "3"]);
+    assert_eq!(m.group_value("mode"), Some("level"));
+
+    let m = App::new("prog")
+        .arg(Arg::with_name("debug").long("debug"))
+        .arg(Arg::with_name("file"))
+        .group(ArgGroup::with_name("mode").args(&["debug", "file"]))
+        .get_matches_from(vec!["prog", "input.txt"]);
+    assert_eq!(m.group_value("mode"), Some("file"));
+}
+
+#[test]
+fn group_value_not_present() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("debug").long("debug"))
+        .arg(
+            Arg::with_name("level")
+                .long("level")
+                .default_value("1"),
+        )
+        .group(ArgGroup::with_name("mode").args(&["debug", "level"]))
+        .get_matches_from(vec!["prog"]);
+    assert_eq!(m.group_value("mode"), None);
+}
+
+static GROUP_HELP: &str = "prog 1.0
+
+USAGE:
+    prog [FLAGS]
+
+FLAGS:
+        --json|--yaml|--toml    Output format
+    -h, --help                  Prints help information
+    -v, --verbose               be loud
+    -V, --version               Prints version information";
+
+#[test]
+fn group_help_single_entry() {
+    let app = App::new("prog")
+        .version("1.0")
+        .arg("--json 'json output'")
+        .arg("--yaml 'yaml output'")
+        .arg("--toml 'toml output'")
+        .arg("-v, --verbose 'be loud'")
+        .group(
+            ArgGroup::with_name("format")
+                .args(&["json", "yaml", "toml"])
+                .help("Output format"),
+        );
+    assert!(test::compare_output(app, "prog --help", GROUP_HELP, false));
+}
+
+static GROUP_HELP_HIDDEN: &str = "prog 1.0
+
+USAGE:
+    prog [FLAGS]
+
+FLAGS:
+        --json|--toml    Output format
+    -h, --help           Prints help information
+    -V, --version        Prints version information";
+
+#[test]
+fn group_help_skips_hidden_args() {
+    let app = App::new("prog")
+        .version("1.0")
+        .arg("--json 'json output'")
+        .arg(Arg::from("--yaml 'yaml output'").hidden(true))
+        .arg("--toml 'toml output'")
+        .group(
+            ArgGroup::with_name("format")
+                .args(&["json", "yaml", "toml"])
+                .help("Output format"),
+        );
+    assert!(test::compare_output(
+        app,
+        "prog --help",
+        GROUP_HELP_HIDDEN,
+        false
+    ));
+}
@@ -4,7 +4,10 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, AppSettings, Arg, ArgGroup, ArgSettings, ErrorKind};
+use clap::{
+    App, AppSettings, Arg, ArgGroup, ArgSettings, ColorChoice, DefaultUsageRenderer, ErrorKind,
+    TermColor, UsageParts, UsageRenderer,
+};
 
 static REQUIRE_DELIM_HELP: &str = "test 1.3
 Kevin K.
@@ -502,6 +505,18 @@ OPTIONS:
     -c, --cafe <FILE>    A coffeehouse, coffee shop, or café. [env: ENVVAR]
     -p, --pos <VAL>      Some vals [possible values: fast, slow]";
 
+static HIDE_ENV: &str = "ctest 0.1
+
+USAGE:
+    ctest [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+    -c, --cafe <FILE>    A coffeehouse, coffee shop, or café.";
+
 static SHOW_ENV_VALS: &str = "ctest 0.1
 
 USAGE:
@@ -705,6 +720,82 @@ fn after_and_before_help_output() {
     ));
 }
 
+#[test]
+fn after_and_before_long_help_output() {
+    let app = App::new("clap-test")
+        .before_help("short before")
+        .after_help("short after")
+        .before_long_help("long before")
+        .after_long_help("long after");
+
+    let short = app
+        .clone()
+        .try_get_matches_from(vec!["clap-test", "-h"])
+        .unwrap_err()
+        .message;
+    assert!(short.starts_with("short before\n\n"));
+    assert!(short.ends_with("short after\n"));
+    assert!(!short.contains("long"));
+
+    let long = app
+        .try_get_matches_from(vec!["clap-test", "--help"])
+        .unwrap_err()
+        .message;
+    assert!(long.starts_with("long before\n\n"));
+    assert!(long.ends_with("long after\n"));
+    assert!(!long.contains("short"));
+}
+
+#[test]
+fn long_help_falls_back_to_short_sections() {
+    let app = App::new("clap-test")
+        .before_help("short before")
+        .after_long_help("long after");
+    let long = app
+        .try_get_matches_from(vec!["clap-test", "--help"])
+        .unwrap_err()
+        .message;
+    assert!(long.starts_with("short before\n\n"));
+    assert!(long.ends_with("long after\n"));
+}
+
+#[test]
+fn long_help_template_tags() {
+    let app = App::new("clap-test")
+        .after_help("short after")
+        .before_long_help("long before")
+        .help_template("{before-long-help}|{after-long-help}");
+    let long = app
+        .try_get_matches_from(vec!["clap-test", "--help"])
+        .unwrap_err()
+        .message;
+    assert_eq!(long, "long before|short after\n");
+}
+
+#[test]
+fn no_wrap_keeps_preformatted_help() {
+    let example = "  myprog --example    # prints a rather long example comment";
+    let app = App::new("myprog").set_term_width(40).arg(
+        Arg::with_name("example")
+            .long("example")
+            .help("Example:\n  myprog --example    # prints a rather long example comment")
+            .setting(ArgSettings::NoWrap),
+    );
+    let help = app
+        .try_get_matches_from(vec!["myprog", "-h"])
+        .unwrap_err()
+        .message;
+    let lines = help.lines().collect::<Vec<_>>();
+    let first = lines.iter().position(|l| l.ends_with("Example:")).unwrap();
+    let next = lines[first + 1];
+    assert!(next.ends_with(example), "{:?}", next);
+    // The continuation line is lined up under the first line of the help
+    assert_eq!(
+        next.len() - example.len(),
+        lines[first].len() - "Example:".len()
+    );
+}
+
 #[test]
 fn multi_level_sc_help() {
     let app = App::new("ctest").subcommand(
@@ -884,6 +975,416 @@ fn wrapping_newline_chars() {
     ));
 }
 
+static BREAK_LONG_WORDS: &str = "ctest 0.1
+
+USAGE:
+    ctest [FLAGS]
+
+FLAGS:
+        --docs
+            see
+            https://example.com/a/very/long/path/t
+            o/the/docs
+    -h, --help       Prints help information
+    -V, --version    Prints version information";
+
+#[test]
+fn break_long_words() {
+    let app = App::new("ctest")
+        .version("0.1")
+        .set_term_width(50)
+        .arg("--docs 'see https://example.com/a/very/long/path/to/the/docs'");
+    assert!(test::compare_output(
+        app.clone().setting(AppSettings::BreakLongWords),
+        "ctest --help",
+        BREAK_LONG_WORDS,
+        false
+    ));
+    // By default the URL overflows instead
+    assert!(test::compare_output(
+        app,
+        "ctest --help",
+        &BREAK_LONG_WORDS.replace("path/t\n            o/", "path/to/"),
+        false
+    ));
+}
+
+#[test]
+fn spec_vals_hint_color() {
+    let app = App::new("ctest")
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::ColorAlways)
+        .arg(
+            Arg::from("--mode [MODE] 'the mode'")
+                .default_value("fast")
+                .possible_values(&["fast", "safe"])
+                .visible_alias("m"),
+        );
+    let help = app
+        .try_get_matches_from(vec!["ctest", "--help"])
+        .unwrap_err()
+        .message;
+    // The name stays green while the values and aliases are cyan
+    assert!(help.contains("\x1b[32m--mode\x1b[0m"), "{:?}", help);
+    assert!(
+        help.contains(
+            "[default: \x1b[36mfast\x1b[0m]  [aliases: \x1b[36mm\x1b[0m]  \
+             [possible values: \x1b[36mfast\x1b[0m, \x1b[36msafe\x1b[0m]"
+        ),
+        "{:?}",
+        help
+    );
+}
+
+#[test]
+fn write_help_to_string() {
+    let mut app = App::new("ctest").version("0.1").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .help("the mode")
+            .long_help("the mode, in detail"),
+    );
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.starts_with("ctest 0.1\n"), "{:?}", help);
+    assert!(help.contains("--mode"), "{:?}", help);
+    assert!(!help.contains("in detail"), "{:?}", help);
+    let long_help = app.write_long_help_to_string().unwrap();
+    assert!(long_help.contains("the mode, in detail"), "{:?}", long_help);
+}
+
+#[test]
+fn write_help_to_string_colored() {
+    let mut app = App::new("ctest")
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::ColorAlways)
+        .arg(Arg::from("--mode 'the mode'"));
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("\x1b[32m--mode\x1b[0m"), "{:?}", help);
+}
+
+#[test]
+fn write_help_to_string_auto_color() {
+    // Whether or not stdout is a terminal, the string isn't one
+    let mut app = App::new("ctest")
+        .setting(AppSettings::ColoredHelp)
+        .arg(Arg::from("--mode 'the mode'"));
+    let help = app.write_help_to_string().unwrap();
+    assert!(!help.contains('\x1b'), "{:?}", help);
+}
+
+#[test]
+fn write_help_repeatedly() {
+    let mut app = App::new("ctest")
+        .arg(Arg::from("--json 'json output'"))
+        .arg(Arg::from("--yaml 'yaml output'"))
+        .arg(Arg::from("<target> 'the target'"))
+        .arg(Arg::from("[files]... 'the files'"))
+        .group(ArgGroup::with_name("format").args(&["json", "yaml"]).required(true));
+    let first = app.write_help_to_string().unwrap();
+    assert!(first.contains("<--json|--yaml>"), "{:?}", first);
+    assert!(!first.contains("--json|--yaml|--json"), "{:?}", first);
+    assert_eq!(app.write_help_to_string().unwrap(), first);
+
+    let m = app.try_get_matches_from_mut(vec!["ctest", "--json", "a", "b"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(app.write_help_to_string().unwrap(), first);
+}
+
+#[test]
+#[cfg(all(feature = "color", not(target_os = "windows")))]
+fn color_choice_always() {
+    let mut app = App::new("ctest")
+        .color(ColorChoice::Always)
+        .arg(Arg::from("--mode 'the mode'"));
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("\x1b[32m--mode\x1b[0m"), "{:?}", help);
+}
+
+#[test]
+#[cfg(all(feature = "color", not(target_os = "windows")))]
+fn color_choice_always_errors() {
+    // Colored whether or not stderr is a terminal
+    let m = App::new("ctest")
+        .color(ColorChoice::Always)
+        .arg(Arg::from("--mode 'the mode'"))
+        .try_get_matches_from(vec!["ctest", "--mdoe"]);
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(err.message.contains('\x1b'), "{:?}", err.message);
+}
+
+#[test]
+fn color_choice_overrides_settings() {
+    let mut app = App::new("ctest")
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::ColorAlways)
+        .color(ColorChoice::Never)
+        .arg(Arg::from("--mode 'the mode'"));
+    let help = app.write_help_to_string().unwrap();
+    assert!(!help.contains('\x1b'), "{:?}", help);
+}
+
+#[test]
+fn color_choice_from_str() {
+    assert_eq!("always".parse::<ColorChoice>().unwrap(), ColorChoice::Always);
+    assert_eq!("Never".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+    assert!("sometimes".parse::<ColorChoice>().is_err());
+}
+
+#[test]
+#[cfg(all(feature = "color", not(target_os = "windows")))]
+fn custom_colors_in_help() {
+    let mut app = App::new("ctest")
+        .color(ColorChoice::Always)
+        .good_color(TermColor::Rgb(1, 2, 3))
+        .warning_color(TermColor::Ansi256(208))
+        .arg(Arg::from("--mode 'the mode'"));
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("\x1b[38;2;1;2;3m--mode\x1b[0m"), "{:?}", help);
+    assert!(help.contains("\x1b[38;5;208m\nUSAGE:\x1b[0m"), "{:?}", help);
+}
+
+#[test]
+#[cfg(all(feature = "color", not(target_os = "windows")))]
+fn custom_colors_in_errors() {
+    let m = App::new("ctest")
+        .color(ColorChoice::Always)
+        .error_color(TermColor::Ansi256(208))
+        .subcommand(App::new("sub").arg(Arg::from("--mode 'the mode'")))
+        .try_get_matches_from(vec!["ctest", "sub", "--mdoe"]);
+    let err = m.unwrap_err();
+    assert!(err.message.starts_with("\x1b[1;38;5;208merror:\x1b[0m"), "{:?}", err.message);
+}
+
+static HELP_GUTTER: &str = "ctest 0.1
+
+USAGE:
+    ctest [OPTIONS] <input>
+
+ARGS:
+    <input>  the input
+
+FLAGS:
+    -h, --help  Prints help information
+    -V, --version  Prints version information
+
+OPTIONS:
+    -c, --config <FILE>  the config";
+
+#[test]
+fn help_gutter() {
+    let app = App::new("ctest")
+        .version("0.1")
+        .set_term_width(120)
+        .help_gutter(2)
+        .arg("-c, --config [FILE] 'the config'")
+        .arg("<input> 'the input'");
+    assert!(test::compare_output(app, "ctest --help", HELP_GUTTER, false));
+}
+
+static HELP_GUTTER_OVERFLOW: &str = "    -c, --config <FILE>
+            the config";
+
+#[test]
+fn help_gutter_overflow() {
+    let app = App::new("ctest")
+        .set_term_width(24)
+        .help_gutter(4)
+        .arg("-c, --config [FILE] 'the config'")
+        .help_template("{options}");
+    assert!(test::compare_output(
+        app,
+        "ctest --help",
+        HELP_GUTTER_OVERFLOW,
+        false
+    ));
+}
+
+static VALUE_NAME_ALIGN: &str = "ctest 0.1
+
+USAGE:
+    ctest [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --config <FILE>...    the config
+    -o, --output <PATH>       the output";
+
+#[test]
+fn value_name_mult_occurrences_aligned() {
+    let app = App::new("ctest")
+        .version("0.1")
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .multiple_occurrences(true)
+                .help("the config"),
+        )
+        .arg(
+            Arg::with_name("out")
+                .short('o')
+                .long("output")
+                .value_name("PATH")
+                .help("the output"),
+        );
+    assert!(test::compare_output(
+        app,
+        "ctest --help",
+        VALUE_NAME_ALIGN,
+        false
+    ));
+}
+
+fn section_app() -> App<'static> {
+    App::new("ctest")
+        .version("0.1")
+        .arg("-c, --config [FILE] 'the config'")
+        .arg("[input] 'the input'")
+        .subcommand(App::new("sub").about("does things"))
+}
+
+#[test]
+fn help_section_options() {
+    assert!(test::compare_output(
+        section_app(),
+        "ctest --help=options",
+        "    -c, --config <FILE>    the config",
+        false
+    ));
+}
+
+#[test]
+fn help_section_positionals() {
+    assert!(test::compare_output(
+        section_app(),
+        "ctest --help=positionals",
+        "    <input>    the input",
+        false
+    ));
+}
+
+#[test]
+fn help_section_usage() {
+    assert!(test::compare_output(
+        section_app(),
+        "ctest --help=usage",
+        "ctest [OPTIONS] [input] [SUBCOMMAND]",
+        false
+    ));
+}
+
+#[test]
+fn help_section_unknown() {
+    let res = section_app().try_get_matches_from(vec!["ctest", "--help=nope"]);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("USAGE:"), "{:?}", err.message);
+    assert!(err.message.contains("SUBCOMMANDS:"), "{:?}", err.message);
+}
+
+#[test]
+fn usage_spec() {
+    let mut app = App::new("ctest")
+        .arg("-v 'be verbose'")
+        .arg("-o, --out <FILE> 'the output'")
+        .arg("-c, --config [FILE] 'the config'")
+        .arg("<first> 'the first'")
+        .arg("[second] 'the second'")
+        .arg(Arg::from("[secret] 'not shown'").hidden(true))
+        .arg(Arg::from("--token <TOKEN> 'not shown'").hidden(true))
+        .subcommand(App::new("sub"));
+    let spec = app.generate_usage_spec();
+    assert_eq!(spec.required, ["out", "first"]);
+    assert_eq!(spec.positionals, ["first", "second"]);
+    assert!(spec.flags.contains(&"v"), "{:?}", spec.flags);
+    assert!(!spec.flags.contains(&"secret"), "{:?}", spec.flags);
+    assert_eq!(spec.options, ["config"]);
+    assert!(spec.has_subcommands);
+}
+
+#[test]
+fn usage_spec_external_subcommands() {
+    let mut app = App::new("ctest").setting(AppSettings::AllowExternalSubcommands);
+    assert!(app.generate_usage_spec().has_subcommands);
+}
+
+struct LegacyUsage;
+
+impl UsageRenderer for LegacyUsage {
+    fn render(&self, parts: &UsageParts) -> String {
+        let mut usage = format!("{} {{", parts.name);
+        for f in &parts.flags {
+            usage.push_str(&format!(" {}", f.name));
+        }
+        usage.push_str(" }");
+        for r in &parts.required {
+            usage.push_str(&format!(" {}", r));
+        }
+        if parts.has_subcommands {
+            usage.push_str(" command ...");
+        }
+        usage
+    }
+}
+
+fn usage_renderer_app() -> App<'static> {
+    App::new("legacy")
+        .arg("-v, --verbose 'be verbose'")
+        .arg("<input> 'the input'")
+        .subcommand(App::new("sub"))
+}
+
+#[test]
+fn usage_renderer_help_and_errors() {
+    let app = usage_renderer_app().usage_renderer(Box::new(LegacyUsage));
+
+    let err = app
+        .clone()
+        .try_get_matches_from(vec!["legacy", "--help"])
+        .unwrap_err();
+    assert!(
+        err.message
+            .contains("USAGE:\n    legacy { verbose help version } <input> command ...\n"),
+        "{}",
+        err.message
+    );
+
+    let err = app.try_get_matches_from(vec!["legacy", "-v"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(
+        err.message.contains("legacy { verbose help version } <input>"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn default_usage_renderer_matches_builtin() {
+    let help = |app: App<'static>| {
+        app.try_get_matches_from(vec!["legacy", "--help"])
+            .unwrap_err()
+            .message
+    };
+    assert_eq!(
+        help(usage_renderer_app().usage_renderer(Box::new(DefaultUsageRenderer))),
+        help(usage_renderer_app())
+    );
+}
+
+#[test]
+fn override_usage_beats_usage_renderer() {
+    let err = usage_renderer_app()
+        .usage_renderer(Box::new(LegacyUsage))
+        .override_usage("legacy [-v] <input>")
+        .try_get_matches_from(vec!["legacy", "--help"])
+        .unwrap_err();
+    assert!(err.message.contains("USAGE:\n    legacy [-v] <input>\n"));
+}
+
 #[test]
 fn old_newline_chars() {
     let app = App::new("ctest").version("0.1").arg(
@@ -1343,6 +1844,24 @@ fn hide_env_vals() {
     ));
 }
 
+#[test]
+fn hide_env() {
+    use std::env;
+
+    env::set_var("ENVVAR", "MYVAL");
+    let app = App::new("ctest").version("0.1").arg(
+        Arg::with_name("cafe")
+            .short('c')
+            .long("cafe")
+            .value_name("FILE")
+            .hide_env(true)
+            .env("ENVVAR")
+            .help("A coffeehouse, coffee shop, or café.")
+            .takes_value(true),
+    );
+    assert!(test::compare_output(app, "ctest --help", HIDE_ENV, false));
+}
+
 #[test]
 fn show_env_vals() {
     use std::env;
@@ -1404,6 +1923,66 @@ fn custom_headers_headers() {
     ));
 }
 
+#[test]
+fn custom_headers_on_arg() {
+    let app = App::new("blorp")
+        .author("Will M.")
+        .about("does stuff")
+        .version("1.4")
+        .arg(
+            Arg::from("-f, --fake <some> <val> 'some help'")
+                .require_delimiter(true)
+                .value_delimiter(":"),
+        )
+        .arg(
+            Arg::with_name("no-proxy")
+                .short('n')
+                .long("no-proxy")
+                .help("Do not use system proxy settings")
+                .help_heading("NETWORKING"),
+        );
+
+    assert!(test::compare_output(
+        app,
+        "test --help",
+        CUSTOM_HELP_SECTION,
+        false
+    ));
+}
+
+static CUSTOM_HEADING_POS: &str = "blorp 1.4
+
+USAGE:
+    test [FLAGS] <file> <host>
+
+ARGS:
+    <file>    the file
+
+FLAGS:
+    -n, --dry-run    Don't do anything
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+NETWORKING:
+    <host>    the host";
+
+#[test]
+fn custom_heading_pos() {
+    let app = App::new("blorp")
+        .version("1.4")
+        .arg(Arg::from("<file> 'the file'"))
+        .arg(Arg::from("-n, --dry-run 'Don't do anything'"))
+        .help_heading("NETWORKING")
+        .arg(Arg::from("<host> 'the host'"));
+
+    assert!(test::compare_output(
+        app,
+        "test --help",
+        CUSTOM_HEADING_POS,
+        false
+    ));
+}
+
 static MULTIPLE_CUSTOM_HELP_SECTIONS: &str = "blorp 1.4
 Will M.
 does stuff
@@ -1533,3 +2112,224 @@ fn issue_1487() {
             .required(true));
     assert!(test::compare_output(app, "ctest -h", ISSUE_1487, false));
 } 
+
+#[test]
+fn disable_help_flags() {
+    let app = App::new("prog")
+        .setting(AppSettings::DisableHelpFlags)
+        .arg(Arg::with_name("host").short('h').takes_value(true));
+
+    let m = app.clone().try_get_matches_from(vec!["prog", "-h", "localhost"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().value_of("host"), Some("localhost"));
+
+    let m = app.try_get_matches_from(vec!["prog", "--help"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn disable_help_flags_own_help_arg() {
+    let m = App::new("prog")
+        .setting(AppSettings::DisableHelpFlags)
+        .arg(Arg::with_name("manual").long("help"))
+        .try_get_matches_from(vec!["prog", "--help"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert!(m.unwrap().is_present("manual"));
+}
+
+#[test]
+fn renamed_help_long() {
+    let app = App::new("prog").mut_arg("help", |h| {
+        h.short('?').long("usage").help("Print help information")
+    });
+
+    let m = app.clone().try_get_matches_from(vec!["prog", "--usage"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::HelpDisplayed);
+
+    let m = app.try_get_matches_from(vec!["prog", "--help"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn requested_help_uses_stdout() {
+    let app = || App::new("prog").subcommand(App::new("sub"));
+
+    let err = app().try_get_matches_from(vec!["prog", "--help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(!err.use_stderr());
+
+    let err = app().try_get_matches_from(vec!["prog", "help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(!err.use_stderr());
+
+    let err = app().try_get_matches_from(vec!["prog", "help", "sub"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(!err.use_stderr());
+}
+
+#[test]
+fn help_on_error_uses_stderr() {
+    let err = App::new("prog")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(Arg::with_name("input"))
+        .try_get_matches_from(vec!["prog"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingArgumentOrSubcommand);
+    assert!(err.use_stderr());
+}
+
+#[test]
+fn subcommand_required_else_help_uses_stdout() {
+    let app = App::new("prog")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(App::new("sub").about("some subcommand"));
+
+    let err = app.clone().try_get_matches_from(vec!["prog"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(!err.use_stderr());
+    assert_eq!(err.exit_code(), 0);
+    assert!(err.message.contains("SUBCOMMANDS:"));
+    assert!(err.message.contains("some subcommand"));
+
+    let m = app.try_get_matches_from(vec!["prog", "sub"]).unwrap();
+    assert_eq!(m.subcommand_name(), Some("sub"));
+}
+
+#[test]
+fn error_kinds_and_exit_codes() {
+    let app = || {
+        App::new("prog")
+            .version("1.0")
+            .arg(Arg::from("<input> 'some input'"))
+            .arg(Arg::from("--color [color] 'a color'").possible_values(&["red", "blue"]))
+    };
+    let kind_and_code = |args: Vec<&str>| {
+        let err = app().try_get_matches_from(args).unwrap_err();
+        (err.kind, err.exit_code())
+    };
+
+    assert_eq!(
+        kind_and_code(vec!["prog"]),
+        (ErrorKind::MissingRequiredArgument, 1)
+    );
+    assert_eq!(
+        kind_and_code(vec!["prog", "in", "--unknown"]),
+        (ErrorKind::UnknownArgument, 1)
+    );
+    assert_eq!(
+        kind_and_code(vec!["prog", "in", "--color", "green"]),
+        (ErrorKind::InvalidValue, 1)
+    );
+    assert_eq!(kind_and_code(vec!["prog", "--help"]), (ErrorKind::HelpDisplayed, 0));
+    assert_eq!(
+        kind_and_code(vec!["prog", "--version"]),
+        (ErrorKind::VersionDisplayed, 0)
+    );
+}
+
+#[test]
+fn hyperlinks_with_colored_output() {
+    let mut app = App::new("myprog")
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::ColorAlways)
+        .setting(AppSettings::HelpHyperlinks)
+        .arg(Arg::from("--docs 'see https://example.com/docs.'"))
+        .after_help("Report bugs at https://example.com/issues");
+    let help = app.write_help_to_string().unwrap();
+    assert!(
+        help.contains(
+            "see \x1b]8;;https://example.com/docs\x1b\\https://example.com/docs\x1b]8;;\x1b\\."
+        ),
+        "{:?}",
+        help
+    );
+    assert!(
+        help.contains(
+            "Report bugs at \x1b]8;;https://example.com/issues\x1b\\https://example.com/issues\x1b]8;;\x1b\\"
+        ),
+        "{:?}",
+        help
+    );
+}
+
+#[test]
+fn hyperlinks_need_colored_output() {
+    // A string isn't a terminal, so Auto writes no colors and no links
+    let mut app = App::new("myprog")
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::HelpHyperlinks)
+        .arg(Arg::from("--docs 'see https://example.com/docs.'"))
+        .after_help("Report bugs at https://example.com/issues");
+    let help = app.write_help_to_string().unwrap();
+    assert!(!help.contains('\x1b'));
+    assert!(help.contains("see https://example.com/docs."));
+    assert!(help.contains("Report bugs at https://example.com/issues"));
+
+    let mut app = app.setting(AppSettings::DisableColoredHelp).setting(AppSettings::ColorAlways);
+    let help = app.write_help_to_string().unwrap();
+    assert!(!help.contains('\x1b'), "{:?}", help);
+}
+
+#[test]
+fn term_width_raised_to_minimum() {
+    let mut app = App::new("ctest")
+        .set_term_width(3)
+        .after_help("one two three four five six seven");
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("one two three four\nfive six seven"));
+
+    let mut app = App::new("ctest")
+        .set_term_width(3)
+        .min_term_width(0)
+        .after_help("one two three four five six seven");
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("one\ntwo\nthree\nfour"));
+}
+
+#[test]
+fn help_tree_sections() {
+    let mut app = App::new("myprog")
+        .version("1.0")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .arg(Arg::from("--color [WHEN] 'when to color'").default_value("auto"))
+        .arg(Arg::from("<input> 'the input'"))
+        .arg(
+            Arg::from("--level [LEVEL] 'the level'")
+                .help_heading(Some("TUNING"))
+                .long_help("the level, from 1 to 3"),
+        )
+        .subcommand(App::new("test").about("tests things").visible_alias("t"));
+    let tree = app.generate_help_tree();
+    assert_eq!(tree.name, "myprog");
+    assert_eq!(tree.version, Some("1.0"));
+    let titles: Vec<_> = tree.sections.iter().map(|s| &*s.title).collect();
+    assert_eq!(titles, ["ARGS", "FLAGS", "OPTIONS", "TUNING"]);
+    assert_eq!(tree.sections[0].entries[0].name, "<input>");
+    let flags: Vec<_> = tree.sections[1].entries.iter().map(|e| &*e.name).collect();
+    assert_eq!(flags, ["-h, --help", "-v, --verbose", "-V, --version"]);
+    assert_eq!(tree.sections[2].entries[0].name, "--color <WHEN>");
+    assert_eq!(tree.sections[2].entries[0].spec_vals, "[default: auto]");
+    assert_eq!(tree.sections[3].entries[0].help, Some("the level"));
+    let test = tree.subcommands.iter().find(|e| e.name == "test").unwrap();
+    assert_eq!(test.help, Some("tests things"));
+    assert_eq!(test.spec_vals, "[aliases: t]");
+}
+
+#[test]
+fn long_help_tree_uses_long_help() {
+    let mut app = App::new("myprog").arg(
+        Arg::from("--level [LEVEL] 'the level'").long_help("the level, from 1 to 3"),
+    );
+    let tree = app.generate_long_help_tree();
+    let level = tree.sections[1]
+        .entries
+        .iter()
+        .find(|e| e.name == "--level <LEVEL>")
+        .unwrap();
+    assert_eq!(level.help, Some("the level, from 1 to 3"));
+}
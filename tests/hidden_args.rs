@@ -202,3 +202,39 @@ fn hidden_long_args_short_help() {
         false
     ));
 }
+
+static HIDDEN_SHORT_OPTS: &str = "test 2.31.2
+Steve P.
+hides short opts
+
+USAGE:
+    test [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information";
+
+/// Ensure a section whose only args are hidden isn't written at all
+#[test]
+fn hidden_short_opts_no_section() {
+    let app = App::new("test")
+        .about("hides short opts")
+        .author("Steve P.")
+        .version("2.31.2")
+        .arg(
+            Arg::with_name("cfg")
+                .short('c')
+                .long("config")
+                .takes_value(true)
+                .next_line_help(true)
+                .hidden_short_help(true)
+                .help("Some help text describing the --config arg"),
+        );
+
+    assert!(test::compare_output(
+        app,
+        "test -h",
+        HIDDEN_SHORT_OPTS,
+        false
+    ));
+}
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg};
+use clap::{App, Arg, Indices};
 
 #[test]
 fn indices_mult_opts() {
@@ -232,3 +232,63 @@ fn indices_mult_opt_mult_flag() {
     assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[2, 5]);
     assert_eq!(m.indices_of("flag").unwrap().collect::<Vec<_>>(), &[3, 6]);
 }
+
+#[test]
+fn occurrences_with_indices_interleaved() {
+    let m = App::new("myapp")
+        .arg(
+            Arg::with_name("include")
+                .long("include")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .arg(
+            Arg::with_name("exclude")
+                .long("exclude")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .arg(Arg::with_name("flag").short('f'))
+        .get_matches_from(vec![
+            "myapp", "--exclude", "a", "-f", "--include", "b", "--exclude", "c",
+        ]);
+
+    assert_eq!(
+        m.occurrences_with_indices(&["include", "exclude", "flag"])
+            .collect::<Vec<_>>(),
+        &[("exclude", "a", 2), ("include", "b", 5), ("exclude", "c", 7)]
+    );
+}
+
+#[test]
+fn occurrences_with_indices_not_present() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("option").short('o').takes_value(true))
+        .get_matches_from(vec!["myapp"]);
+
+    assert_eq!(m.occurrences_with_indices(&["option"]).next(), None);
+}
+
+#[test]
+fn indices_zip_values() {
+    fn positions<'a>(m: &'a clap::ArgMatches, name: &str) -> Option<Indices<'a>> {
+        m.indices_of(name)
+    }
+
+    let m = App::new("myapp")
+        .arg(
+            Arg::with_name("file")
+                .short('f')
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .get_matches_from(vec!["myapp", "-f", "a.rs", "-f", "b.rs"]);
+
+    assert_eq!(
+        m.values_of("file")
+            .unwrap()
+            .zip(positions(&m, "file").unwrap())
+            .collect::<Vec<_>>(),
+        &[("a.rs", 2), ("b.rs", 4)]
+    );
+}
@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg, ArgSettings};
+use clap::{App, Arg, ArgSettings, ErrorKind};
 
 #[test]
 fn multiple_occurrences_of_flags_long() {
@@ -76,3 +76,60 @@ fn multiple_occurrences_of_flags_large_quantity() {
     assert!(m.is_present("multflag"));
     assert_eq!(m.occurrences_of("multflag"), 1024);
 }
+
+#[test]
+fn max_occurrences_of_flags() {
+    let app = App::new("mo_max").arg(Arg::with_name("verbose").short('v').max_occurrences(3));
+
+    let m = app.clone().try_get_matches_from(vec!["", "-vvv"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().occurrences_of("verbose"), 3);
+
+    let m = app.try_get_matches_from(vec!["", "-vv", "-vv"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::TooManyOccurrences);
+}
+
+#[test]
+fn min_occurrences_of_flags() {
+    let app = App::new("mo_min").arg(Arg::with_name("verbose").short('v').min_occurrences(2));
+
+    let m = app.clone().try_get_matches_from(vec!["", "-v", "-v"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().occurrences_of("verbose"), 2);
+
+    let m = app.clone().try_get_matches_from(vec!["", "-v"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::TooFewOccurrences);
+
+    let m = app.try_get_matches_from(vec![""]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert!(!m.unwrap().is_present("verbose"));
+}
+
+#[test]
+fn min_occurrences_with_default_value() {
+    let app = App::new("mo_min_default").arg(
+        Arg::with_name("opt")
+            .long("opt")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .default_value("x")
+            .min_occurrences(2),
+    );
+
+    let m = app.clone().try_get_matches_from(vec![""]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+    assert_eq!(m.value_of("opt"), Some("x"));
+    assert_eq!(m.occurrences_of("opt"), 0);
+
+    let m = app.clone().try_get_matches_from(vec!["", "--opt", "a"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::TooFewOccurrences);
+
+    let m = app.try_get_matches_from(vec!["", "--opt", "a", "--opt", "b"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().occurrences_of("opt"), 2);
+}
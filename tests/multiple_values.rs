@@ -89,15 +89,9 @@ fn option_exact_exact() {
         )
         .try_get_matches_from(vec!["", "-o", "val1", "-o", "val2", "-o", "val3"]);
 
-    assert!(m.is_ok());
-    let m = m.unwrap();
-
-    assert!(m.is_present("option"));
-    assert_eq!(m.occurrences_of("option"), 3);
-    assert_eq!(
-        m.values_of("option").unwrap().collect::<Vec<_>>(),
-        ["val1", "val2", "val3"]
-    );
+    // The count applies to each occurrence, not to the total
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::WrongNumberOfValues);
 }
 
 #[test]
@@ -1187,6 +1181,67 @@ fn multiple_value_terminator_option_other_arg() {
     assert!(m.is_present("flag"));
 }
 
+#[test]
+fn multiple_value_terminator_double_dash() {
+    let m = App::new("lip")
+        .arg(
+            Arg::with_name("files")
+                .long("files")
+                .value_terminator("--")
+                .multiple(true),
+        )
+        .arg(Arg::with_name("flag").short('F'))
+        .arg(Arg::with_name("other"))
+        .try_get_matches_from(vec!["lip", "--files", "a", "b", "c", "--", "-F", "rest"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+
+    assert_eq!(
+        m.values_of("files").unwrap().collect::<Vec<_>>(),
+        ["a", "b", "c"]
+    );
+    assert!(m.is_present("flag"));
+    assert_eq!(m.value_of("other"), Some("rest"));
+}
+
+#[test]
+fn multiple_value_terminator_max_values() {
+    let m = App::new("lip")
+        .arg(
+            Arg::with_name("files")
+                .short('f')
+                .value_terminator(";")
+                .max_values(2),
+        )
+        .arg(Arg::with_name("other"))
+        .try_get_matches_from(vec!["lip", "-f", "val1", "val2", ";", "otherval"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+
+    assert_eq!(
+        m.values_of("files").unwrap().collect::<Vec<_>>(),
+        ["val1", "val2"]
+    );
+    assert_eq!(m.value_of("other"), Some("otherval"));
+}
+
+#[test]
+fn multiple_value_terminator_max_values_exceeded() {
+    let m = App::new("lip")
+        .arg(
+            Arg::with_name("files")
+                .short('f')
+                .value_terminator(";")
+                .max_values(2),
+        )
+        .try_get_matches_from(vec!["lip", "-f", "val1", "val2", "val3"]);
+
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::TooManyValues);
+}
+
 #[test]
 fn multiple_vals_with_hyphen() {
     let res = App::new("do")
@@ -1214,3 +1269,41 @@ fn multiple_vals_with_hyphen() {
     assert_eq!(&cmds, &["find", "-type", "f", "-name", "special"]);
     assert_eq!(m.value_of("location"), Some("/home/clap"));
 }
+
+#[test]
+fn option_exact_per_occurrence() {
+    let m = App::new("multiple_values")
+        .arg(
+            Arg::with_name("point")
+                .long("point")
+                .value_names(&["x", "y"])
+                .multiple(true)
+                .number_of_values(2),
+        )
+        .try_get_matches_from(vec!["", "--point", "1", "--point", "2", "3", "4"]);
+
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::WrongNumberOfValues);
+}
+
+#[test]
+fn option_exact_multiple_occurrences() {
+    let m = App::new("multiple_values")
+        .arg(
+            Arg::with_name("point")
+                .long("point")
+                .value_names(&["x", "y"])
+                .multiple_occurrences(true)
+                .number_of_values(2),
+        )
+        .try_get_matches_from(vec!["", "--point", "1", "2", "--point", "3", "4"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+
+    assert_eq!(m.occurrences_of("point"), 2);
+    assert_eq!(
+        m.values_of("point").unwrap().collect::<Vec<_>>(),
+        ["1", "2", "3", "4"]
+    );
+}
@@ -3,12 +3,25 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ArgMatches, ArgSettings, ErrorKind};
+use clap::{App, AppSettings, Arg, ArgMatches, ArgSettings, Error, ErrorKind};
+use std::ffi::OsStr;
+use std::io::Read;
+
+#[cfg(feature = "suggestions")]
+static DYM_ALIAS: &str =
+    "error: Found argument '--colo' which wasn't expected, or isn't valid in this context
+\tDid you mean `--color`?
+If you tried to supply `--colo` as a PATTERN use `-- --colo`
+
+USAGE:
+    prog --colour <when>
+
+For more information try --help";
 
 #[cfg(feature = "suggestions")]
 static DYM: &str =
     "error: Found argument '--optio' which wasn't expected, or isn't valid in this context
-\tDid you mean --option?
+\tDid you mean `--option`?
 If you tried to supply `--optio` as a PATTERN use `-- --optio`
 
 USAGE:
@@ -369,6 +382,22 @@ fn did_you_mean() {
     ));
 }
 
+#[test]
+#[cfg(feature = "suggestions")]
+fn did_you_mean_alias_not_hidden() {
+    let app = App::new("prog")
+        .arg(Arg::from("--colour [when] 'when to color'").alias("color"))
+        .arg(Arg::from("--colr 'secret'").hidden(true));
+    assert!(test::compare_output(app.clone(), "prog --colo", DYM_ALIAS, true));
+
+    let err = app
+        .unset_setting(AppSettings::SuggestFlags)
+        .try_get_matches_from(vec!["prog", "--colo"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(!err.message.contains("Did you mean"));
+}
+
 #[test]
 fn issue_665() {
     let res = App::new("tester")
@@ -457,3 +486,184 @@ fn issue_1105_empty_value_short_explicit_no_space() {
     let m = r.unwrap();
     assert_eq!(m.value_of("option"), Some(""));
 }
+
+fn not_zero(v: &OsStr) -> Result<(), Error> {
+    if v == "0" {
+        return Err(Error::with_description(
+            "count must not be zero",
+            ErrorKind::InvalidValue,
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn validator_error_keeps_kind() {
+    let app = App::new("prog").arg(
+        Arg::with_name("count")
+            .long("count")
+            .takes_value(true)
+            .validator_error(not_zero),
+    );
+
+    let m = app.clone().try_get_matches_from(vec!["prog", "--count", "3"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().value_of("count"), Some("3"));
+
+    let err = app
+        .try_get_matches_from(vec!["prog", "--count", "0"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert!(err.message.contains("count must not be zero"));
+}
+
+#[test]
+fn validator_error_checks_default() {
+    let res = App::new("prog")
+        .arg(
+            Arg::with_name("count")
+                .long("count")
+                .default_value("0")
+                .validator_error(not_zero),
+        )
+        .try_get_matches_from(vec!["prog"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn validator_error_debug() {
+    let a = Arg::with_name("count").validator_error(not_zero);
+    assert!(format!("{:?}", a).contains("validator_error: Some(Fn)"));
+    let a = Arg::with_name("count");
+    assert!(format!("{:?}", a).contains("validator_error: None"));
+}
+
+#[test]
+fn value_of_t_parses() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("port").long("port").takes_value(true))
+        .arg(Arg::with_name("seq").multiple(true))
+        .get_matches_from(vec!["prog", "--port", "8080", "1", "2", "3"]);
+
+    assert_eq!(m.value_of_t::<u32>("port").unwrap(), 8080);
+    assert_eq!(m.values_of_t::<u8>("seq").unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn value_of_t_errors() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("port").long("port").takes_value(true))
+        .arg(Arg::with_name("seq").multiple(true))
+        .get_matches_from(vec!["prog", "--port", "http", "1", "two"]);
+
+    let err = m.value_of_t::<u32>("port").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("'http'"));
+    assert!(err.message.contains("'port'"));
+
+    let err = m.values_of_t::<u32>("seq").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("'two'"));
+
+    let err = m.value_of_t::<u32>("missing").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentNotFound);
+}
+
+#[test]
+fn short_opt_attached_value() {
+    let app = App::new("prog")
+        .arg(Arg::from("-a 'flag a'"))
+        .arg(Arg::from("-b 'flag b'"))
+        .arg(Arg::from("-o [out] 'output'"));
+
+    let m = app
+        .clone()
+        .try_get_matches_from(vec!["prog", "-oout.txt"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().value_of("o"), Some("out.txt"));
+
+    let m = app
+        .clone()
+        .try_get_matches_from(vec!["prog", "-aboab"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+    assert!(m.is_present("a"));
+    assert!(m.is_present("b"));
+    assert_eq!(m.value_of("o"), Some("ab"));
+
+    let m = app.try_get_matches_from(vec!["prog", "-ab"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+    assert!(m.is_present("a"));
+    assert!(m.is_present("b"));
+    assert!(!m.is_present("o"));
+}
+
+#[test]
+fn stdin_dash_skips_value_checks() {
+    let app = App::new("prog").arg(
+        Arg::from("-f, --file [file] 'the input'")
+            .possible_values(&["a.txt", "b.txt"])
+            .allow_stdin_dash(true),
+    );
+    let m = app.clone().try_get_matches_from(vec!["prog", "-f", "-"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().value_of("file"), Some("-"));
+
+    let m = app.try_get_matches_from(vec!["prog", "-f", "c.txt"]);
+    assert_eq!(m.unwrap_err().kind, ErrorKind::InvalidValue);
+
+    let m = App::new("prog")
+        .arg(Arg::from("-f, --file [file] 'the input'").possible_values(&["a.txt", "b.txt"]))
+        .try_get_matches_from(vec!["prog", "-f", "-"]);
+    assert_eq!(m.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn stdin_dash_value_reader() {
+    let app = App::new("prog").arg(
+        Arg::from("-f, --file [file] 'the input'")
+            .validator(|f| {
+                if f.ends_with(".toml") {
+                    Ok(())
+                } else {
+                    Err(String::from("not a toml file"))
+                }
+            })
+            .allow_stdin_dash(true),
+    );
+    let m = app
+        .clone()
+        .try_get_matches_from(vec!["prog", "-f", "-"])
+        .unwrap();
+    assert!(m.value_reader("file").unwrap().is_ok());
+
+    let m = app
+        .clone()
+        .try_get_matches_from(vec!["prog", "-f", "Cargo.toml"])
+        .unwrap();
+    let mut manifest = String::new();
+    m.value_reader("file")
+        .unwrap()
+        .unwrap()
+        .read_to_string(&mut manifest)
+        .unwrap();
+    assert!(manifest.contains("[package]"));
+
+    let m = app.try_get_matches_from(vec!["prog"]).unwrap();
+    assert!(m.value_reader("file").is_none());
+}
+
+#[test]
+fn stdin_dash_unset_keeps_takes_value() {
+    let m = App::new("prog")
+        .arg(
+            Arg::from("-f, --file [file] 'the input'")
+                .allow_stdin_dash(true)
+                .allow_stdin_dash(false),
+        )
+        .try_get_matches_from(vec!["prog", "-f", "-"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().value_of("file"), Some("-"));
+}
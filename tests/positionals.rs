@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg, ErrorKind};
+use clap::{App, Arg, ArgSettings, ErrorKind};
 
 #[test]
 fn only_pos_follow() {
@@ -218,6 +218,15 @@ fn single_positional_required_usage_string() {
     assert_eq!(app.generate_usage(), "USAGE:\n    test <FILE>");
 }
 
+#[test]
+fn usage_string_after_parse() {
+    let mut app = App::new("test")
+        .arg("-v 'be verbose'")
+        .arg("<FILE> 'some file'");
+    assert!(app.try_get_matches_from_mut(vec!["test", "-v"]).is_err());
+    assert_eq!(app.generate_usage(), "USAGE:\n    test [FLAGS] <FILE>");
+}
+
 // This tests a programmer error and will only succeed with debug_assertions
 #[cfg(debug_assertions)]
 #[test]
@@ -272,3 +281,134 @@ fn last_positional_second_to_last_mult() {
         .try_get_matches_from(vec!["test", "tgt", "crp1", "crp2", "--", "arg"]);
     assert!(r.is_ok(), "{:?}", r.unwrap_err().kind);
 }
+
+#[test]
+fn positional_hyphen_value() {
+    let r = App::new("seek")
+        .arg("-v, --verbose 'more output'")
+        .arg(Arg::from("<OFFSET> 'offset to seek to'").allow_hyphen_values(true))
+        .try_get_matches_from(vec!["seek", "-v", "-5"]);
+    assert!(r.is_ok(), "{:?}", r.unwrap_err().kind);
+    let m = r.unwrap();
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.value_of("OFFSET"), Some("-5"));
+}
+
+#[test]
+fn positional_hyphen_value_known_flag() {
+    let r = App::new("seek")
+        .arg("-v, --verbose 'more output'")
+        .arg(Arg::from("[OFFSET] 'offset to seek to'").allow_hyphen_values(true))
+        .try_get_matches_from(vec!["seek", "--verbose"]);
+    assert!(r.is_ok(), "{:?}", r.unwrap_err().kind);
+    let m = r.unwrap();
+    assert!(m.is_present("verbose"));
+    assert!(!m.is_present("OFFSET"));
+}
+
+#[test]
+fn positional_hyphen_value_unknown_flag_elsewhere() {
+    let r = App::new("seek")
+        .arg("-v, --verbose 'more output'")
+        .arg(Arg::from("<OFFSET> 'offset to seek to'").allow_hyphen_values(true))
+        .try_get_matches_from(vec!["seek", "-5", "--unknown"]);
+    assert!(r.is_err());
+    assert_eq!(r.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+// This tests a programmer error and will only succeed with debug_assertions
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "positional index 1 is used by both `foo` and `bar`")]
+fn duplicate_index() {
+    let _ = App::new("test")
+        .arg(Arg::with_name("foo").index(1))
+        .arg(Arg::with_name("bar").index(1))
+        .try_get_matches_from(vec![""]);
+}
+
+// This tests a programmer error and will only succeed with debug_assertions
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "index 2 missing; indices must be contiguous starting at 1")]
+fn gapped_index() {
+    let _ = App::new("test")
+        .arg(Arg::with_name("foo").index(1))
+        .arg(Arg::with_name("bar").index(3))
+        .try_get_matches_from(vec![""]);
+}
+
+#[test]
+fn auto_index_skips_explicit() {
+    let m = App::new("test")
+        .arg(Arg::with_name("first"))
+        .arg(Arg::with_name("second").index(1))
+        .arg(Arg::with_name("third"))
+        .try_get_matches_from(vec!["test", "a", "b", "c"])
+        .unwrap();
+    assert_eq!(m.value_of("second"), Some("a"));
+    assert_eq!(m.value_of("first"), Some("b"));
+    assert_eq!(m.value_of("third"), Some("c"));
+}
+
+#[test]
+fn multiple_positional_occurrences() {
+    let m = App::new("cp")
+        .arg(
+            Arg::with_name("src")
+                .required(true)
+                .setting(ArgSettings::MultipleValues),
+        )
+        .arg(Arg::with_name("dst").required(true))
+        .try_get_matches_from(vec!["cp", "a.txt", "b.txt", "c.txt", "out"])
+        .unwrap();
+    assert_eq!(m.occurrences_of("src"), 3);
+    assert_eq!(
+        m.values_of("src").unwrap().collect::<Vec<_>>(),
+        ["a.txt", "b.txt", "c.txt"]
+    );
+    assert_eq!(m.occurrences_of("dst"), 1);
+    assert_eq!(m.value_of("dst"), Some("out"));
+}
+
+fn raw_app() -> App<'static> {
+    App::new("run")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .arg(Arg::with_name("image").required(true).help("the image"))
+        .arg(
+            Arg::with_name("cmd")
+                .value_name("COMMAND")
+                .raw(true)
+                .help("the command"),
+        )
+}
+
+#[test]
+fn raw_positional_takes_everything() {
+    let m = raw_app()
+        .try_get_matches_from(vec!["run", "-v", "alpine", "ls", "-v", "--", "--help", "x"])
+        .unwrap();
+    assert_eq!(m.occurrences_of("verbose"), 1);
+    assert_eq!(m.value_of("image"), Some("alpine"));
+    assert_eq!(
+        m.values_of("cmd").unwrap().collect::<Vec<_>>(),
+        ["ls", "-v", "--", "--help", "x"]
+    );
+}
+
+#[test]
+fn raw_positional_first_value_hyphen() {
+    let m = raw_app()
+        .try_get_matches_from(vec!["run", "alpine", "-la"])
+        .unwrap();
+    assert_eq!(m.values_of("cmd").unwrap().collect::<Vec<_>>(), ["-la"]);
+}
+
+#[test]
+fn raw_positional_help() {
+    let err = raw_app()
+        .try_get_matches_from(vec!["run", "--help"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("<COMMAND>..."), "{}", err.message);
+}
@@ -298,3 +298,119 @@ fn require_overriden_4() {
     let err = result.err().unwrap();
     assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
 }
+
+#[test]
+fn overridden_by() {
+    let m = App::new("posix")
+        .arg(Arg::from("--verbose 'be verbose'").overrides_with("quiet"))
+        .arg(Arg::from("--quiet 'be quiet'"))
+        .get_matches_from(vec!["", "--verbose", "--quiet"]);
+    assert!(!m.is_present("verbose"));
+    assert!(m.is_present("quiet"));
+    assert_eq!(m.overridden_by("verbose"), Some("quiet"));
+    assert_eq!(m.overridden_by("quiet"), None);
+}
+
+#[test]
+fn overridden_by_rev() {
+    let m = App::new("posix")
+        .arg(Arg::from("--verbose 'be verbose'").overrides_with("quiet"))
+        .arg(Arg::from("--quiet 'be quiet'"))
+        .get_matches_from(vec!["", "--quiet", "--verbose"]);
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.overridden_by("quiet"), Some("verbose"));
+    assert_eq!(m.overridden_by("verbose"), None);
+}
+
+#[test]
+fn overridden_by_not_used() {
+    let m = App::new("posix")
+        .arg(Arg::from("--verbose 'be verbose'").overrides_with("quiet"))
+        .arg(Arg::from("--quiet 'be quiet'"))
+        .get_matches_from(vec!["", "--verbose"]);
+    assert_eq!(m.overridden_by("quiet"), None);
+}
+
+fn pos_opt_app() -> App<'static> {
+    App::new("posix")
+        .arg(Arg::with_name("config").index(1))
+        .arg(
+            Arg::with_name("set")
+                .long("set")
+                .takes_value(true)
+                .overrides_with("config"),
+        )
+}
+
+#[test]
+fn opt_overrides_pos() {
+    let m = pos_opt_app().get_matches_from(vec!["", "app.conf", "--set", "k=v"]);
+    assert!(!m.is_present("config"));
+    assert_eq!(m.value_of("set"), Some("k=v"));
+    assert_eq!(m.overridden_by("config"), Some("set"));
+}
+
+#[test]
+fn pos_overrides_opt() {
+    let m = pos_opt_app().get_matches_from(vec!["", "--set", "k=v", "app.conf"]);
+    assert!(!m.is_present("set"));
+    assert_eq!(m.value_of("config"), Some("app.conf"));
+    assert_eq!(m.overridden_by("set"), Some("config"));
+}
+
+#[test]
+fn pos_overrides_opt_declared_on_pos() {
+    let m = App::new("posix")
+        .arg(Arg::with_name("config").index(1).overrides_with("set"))
+        .arg(Arg::from("--set [val] 'sets a value'"))
+        .get_matches_from(vec!["", "--set", "k=v", "app.conf"]);
+    assert!(!m.is_present("set"));
+    assert_eq!(m.value_of("config"), Some("app.conf"));
+}
+
+#[test]
+fn mult_pos_overrides_opt_between_vals() {
+    let m = App::new("posix")
+        .arg(Arg::from("[files]... 'some files'").overrides_with("all"))
+        .arg(Arg::from("--all 'every file'"))
+        .get_matches_from(vec!["", "a", "--all", "b"]);
+    assert!(!m.is_present("all"));
+    assert_eq!(m.values_of("files").unwrap().collect::<Vec<_>>(), &["a", "b"]);
+}
+
+#[test]
+fn required_pos_overridden_by_opt() {
+    let res = App::new("posix")
+        .arg(Arg::with_name("config").index(1).required(true))
+        .arg(Arg::from("--set [val] 'sets a value'").overrides_with("config"))
+        .try_get_matches_from(vec!["", "app.conf", "--set", "k=v"]);
+    assert!(res.is_ok(), "{:?}", res.unwrap_err());
+    let m = res.unwrap();
+    assert!(!m.is_present("config"));
+    assert!(m.is_present("set"));
+}
+
+#[test]
+fn opt_overrides_pos_regardless_of_order() {
+    let app = pos_opt_app().mut_arg("set", |a| a.overrides_regardless_of_order(true));
+    for args in &[["", "app.conf", "--set", "k=v"], ["", "--set", "k=v", "app.conf"]] {
+        let m = app.clone().get_matches_from(args.iter());
+        assert!(!m.is_present("config"));
+        assert_eq!(m.value_of("set"), Some("k=v"));
+        assert_eq!(m.overridden_by("config"), Some("set"));
+    }
+}
+
+#[test]
+fn both_regardless_of_order_last_wins() {
+    let m = App::new("posix")
+        .arg(
+            Arg::from("--flag 'some flag'")
+                .overrides_with("color")
+                .overrides_regardless_of_order(true),
+        )
+        .arg(Arg::from("--color 'some other flag'").overrides_regardless_of_order(true))
+        .get_matches_from(vec!["", "--flag", "--color"]);
+    assert!(m.is_present("color"));
+    assert!(!m.is_present("flag"));
+}
@@ -3,7 +3,9 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind};
+use std::ffi::OsStr;
+
+use clap::{App, AppSettings, Arg, ArgSettings, ErrorKind};
 
 #[cfg(feature = "suggestions")]
 static PV_ERROR: &str = "error: 'slo' isn't a valid value for '--Option <option3>'
@@ -260,3 +262,148 @@ fn case_insensitive_multiple_fail() {
     assert!(m.is_err());
     assert_eq!(m.unwrap_err().kind, ErrorKind::InvalidValue);
 }
+
+#[test]
+fn possible_values_cap() {
+    let mut app = App::new("prog").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .possible_values(&["fast", "slow", "medium", "turbo"])
+            .possible_values_cap(2),
+    );
+
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: fast, slow, ... (see --help for all)]"));
+
+    let help = app.write_long_help_to_string().unwrap();
+    assert!(help.contains("[possible values: fast, slow, medium, turbo]"));
+}
+
+#[test]
+fn possible_values_cap_not_reached() {
+    let mut app = App::new("prog").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .possible_values(&["fast", "slow"])
+            .possible_values_cap(2),
+    );
+
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: fast, slow]"));
+}
+
+#[test]
+fn possible_values_cap_zero() {
+    let app = App::new("prog").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .possible_values(&["fast", "slow"])
+            .possible_values_cap(0),
+    );
+
+    let help = app.clone().write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: see --help]"), "{:?}", help);
+
+    let err = app.try_get_matches_from(vec!["prog", "--help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("[possible values: fast, slow]"), "{:?}", err.message);
+}
+
+#[test]
+fn possible_values_cap_renamed_help() {
+    let mut app = App::new("prog")
+        .mut_arg("help", |h| h.long("usage"))
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "slow", "medium"])
+                .possible_values_cap(1),
+        );
+
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: fast, ... (see --usage for all)]"), "{:?}", help);
+}
+
+#[test]
+fn possible_values_cap_without_help_flag() {
+    let mut app = App::new("prog")
+        .setting(AppSettings::DisableHelpFlags)
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "slow", "medium"])
+                .possible_values_cap(1),
+        );
+
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: fast, slow, medium]"), "{:?}", help);
+}
+
+#[test]
+fn sort_possible_values() {
+    let app = App::new("prog").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .possible_values(&["slow", "fast", "Éclair", "medium"]),
+    );
+
+    let help = app.clone().write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: slow, fast, Éclair, medium]"));
+
+    let mut app = app.mut_arg("mode", |a| a.sort_possible_values(true));
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: Éclair, fast, medium, slow]"));
+}
+
+static PV_ERROR_COLLATED: &str = "error: 'x' isn't a valid value for '--mode <mode>'
+\t[possible values: Éclair, fast, medium, slow]
+
+
+USAGE:
+    prog --mode <mode>
+
+For more information try --help";
+
+#[test]
+fn possible_values_error_collated() {
+    let app = App::new("prog").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .possible_values(&["slow", "fast", "Éclair", "medium"]),
+    );
+    assert!(test::compare_output(
+        app,
+        "prog --mode x",
+        PV_ERROR_COLLATED,
+        true
+    ));
+}
+
+#[test]
+fn sort_possible_values_os() {
+    let mut app = App::new("prog").arg(
+        Arg::with_name("mode")
+            .long("mode")
+            .possible_values(&["slow", "fast"])
+            .possible_values_os(&[OsStr::new("medium")])
+            .sort_possible_values(true),
+    );
+
+    let help = app.write_help_to_string().unwrap();
+    assert!(help.contains("[possible values: fast, medium, slow]"));
+}
+
+#[test]
+fn sort_possible_values_unset_keeps_takes_value() {
+    let a = Arg::with_name("mode")
+        .long("mode")
+        .possible_values(&["slow", "fast"])
+        .sort_possible_values(true)
+        .sort_possible_values(false);
+    assert!(a.is_set(ArgSettings::TakesValue));
+}
@@ -4,7 +4,7 @@ extern crate regex;
 #[cfg(test)]
 mod tests {
     include!("../clap-test.rs");
-    use clap::{App, Arg, ArgMatches, ArgSettings};
+    use clap::{App, AppSettings, Arg, ArgMatches, ArgSettings};
 
     fn get_app() -> App<'static> {
         App::new("myprog")
@@ -147,4 +147,48 @@ mod tests {
         assert!(inner_can_access_flag(&m, true, 2));
         assert!(outer_can_access_flag(&m, true, 2));
     }
+
+    fn get_deep_app() -> App<'static> {
+        get_app().subcommand(
+            App::new("first").subcommand(App::new("second").subcommand(App::new("third"))),
+        )
+    }
+
+    fn get_third_matches(m: &ArgMatches) -> &ArgMatches {
+        m.subcommand_matches("first")
+            .and_then(|m| m.subcommand_matches("second"))
+            .and_then(|m| m.subcommand_matches("third"))
+            .expect("could not access third subcommand")
+    }
+
+    #[test]
+    fn global_flag_used_three_levels_deep() {
+        let m = get_matches(get_deep_app(), "myprog first second third --global-flag");
+
+        assert!(top_can_access_flag(&m, true, 1));
+        let third = get_third_matches(&m);
+        assert!(third.is_present("GLOBAL_FLAG"));
+        assert_eq!(third.occurrences_of("GLOBAL_FLAG"), 1);
+    }
+
+    #[test]
+    fn global_arg_used_top_level_three_levels_deep() {
+        let m = get_matches(get_deep_app(), "myprog --global-arg=some_value first second third");
+
+        assert!(top_can_access_arg(&m, "some_value"));
+        assert_eq!(
+            get_third_matches(&m).value_of("GLOBAL_ARG"),
+            Some("some_value")
+        );
+    }
+
+    #[test]
+    fn global_flag_used_in_repeated_subcommand() {
+        let app = get_app()
+            .setting(AppSettings::AllowMultipleSubcommands)
+            .subcommand(App::new("other"));
+        let m = get_matches(app, "myprog outer other --global-flag");
+
+        assert!(top_can_access_flag(&m, true, 1));
+    }
 }
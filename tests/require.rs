@@ -451,6 +451,23 @@ fn requires_if_present_val() {
     assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
 }
 
+#[test]
+fn requires_if_present_val_names_missing_arg() {
+    let res = App::new("unlessone")
+        .arg(
+            Arg::with_name("cfg")
+                .requires_if("my.cfg", "extra")
+                .takes_value(true)
+                .long("config"),
+        )
+        .arg(Arg::with_name("extra").long("extra"))
+        .try_get_matches_from(vec!["unlessone", "--config=my.cfg"]);
+
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("--extra"), "{:?}", err.message);
+}
+
 #[test]
 fn requires_if_present_mult() {
     let res = App::new("unlessone")
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind};
+use clap::{App, AppSettings, Arg, ArgGroup, ErrorKind};
 
 static VISIBLE_ALIAS_HELP: &str = "clap-test 2.6
 
@@ -247,3 +247,387 @@ fn issue_1161_multiple_hyphen_hyphen() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn multiple_subcommands() {
+    let m = App::new("myprog")
+        .setting(AppSettings::AllowMultipleSubcommands)
+        .subcommand(App::new("log").arg(Arg::from("-n [num] 'number of entries'")))
+        .subcommand(App::new("grep").arg(Arg::from("<pattern> 'pattern to find'")))
+        .try_get_matches_from(vec![
+            "myprog", "log", "-n", "5", "grep", "fix", "log", "-n", "3",
+        ]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+    let all = m.subcommand_matches_all();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].0, "log");
+    assert_eq!(all[0].1.value_of("n"), Some("5"));
+    assert_eq!(all[1].0, "grep");
+    assert_eq!(all[1].1.value_of("pattern"), Some("fix"));
+    assert_eq!(all[2].0, "log");
+    assert_eq!(all[2].1.value_of("n"), Some("3"));
+    assert_eq!(m.subcommand_name(), Some("log"));
+    assert_eq!(m.subcommand_matches("log").unwrap().value_of("n"), Some("5"));
+}
+
+#[test]
+fn multiple_subcommands_value_not_split() {
+    let m = App::new("myprog")
+        .setting(AppSettings::AllowMultipleSubcommands)
+        .subcommand(App::new("log").arg(Arg::from("-n [num] 'number of entries'")))
+        .subcommand(App::new("grep").arg(Arg::from("<pattern> 'pattern to find'")))
+        .try_get_matches_from(vec!["myprog", "log", "-n", "grep", "grep", "fix"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    let m = m.unwrap();
+    let all = m.subcommand_matches_all();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].0, "log");
+    assert_eq!(all[0].1.value_of("n"), Some("grep"));
+    assert_eq!(all[1].0, "grep");
+    assert_eq!(all[1].1.value_of("pattern"), Some("fix"));
+}
+
+#[test]
+fn multiple_subcommands_collect_errors() {
+    let err = App::new("myprog")
+        .setting(AppSettings::AllowMultipleSubcommands)
+        .setting(AppSettings::CollectErrors)
+        .subcommand(App::new("remote").subcommand(App::new("add")))
+        .subcommand(App::new("log"))
+        .try_get_matches_from(vec!["myprog", "remote", "ad", "origin", "log"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    assert!(err.errors.len() > 1, "{:?}", err.errors);
+}
+
+#[test]
+fn multiple_subcommands_not_allowed() {
+    let m = App::new("myprog")
+        .subcommand(App::new("log"))
+        .try_get_matches_from(vec!["myprog", "log", "log"]);
+
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn case_insensitive_subcommand() {
+    let m = App::new("myprog")
+        .setting(AppSettings::CaseInsensitiveSubcommands)
+        .subcommand(App::new("build").visible_alias("compile"))
+        .try_get_matches_from(vec!["myprog", "BUILD"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().subcommand_name(), Some("build"));
+}
+
+#[test]
+fn case_insensitive_subcommand_alias() {
+    let m = App::new("myprog")
+        .setting(AppSettings::CaseInsensitiveSubcommands)
+        .subcommand(App::new("build").visible_alias("compile"))
+        .try_get_matches_from(vec!["myprog", "Compile"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().subcommand_name(), Some("build"));
+}
+
+#[test]
+fn case_insensitive_subcommand_visible_aliases_help_output() {
+    let app = App::new("clap-test")
+        .version("2.6")
+        .setting(AppSettings::CaseInsensitiveSubcommands)
+        .subcommand(
+            App::new("test")
+                .about("Some help")
+                .alias("invisible")
+                .visible_alias("dongle")
+                .visible_alias("done"),
+        );
+    assert!(test::compare_output(
+        app,
+        "clap-test HELP",
+        VISIBLE_ALIAS_HELP,
+        false
+    ));
+}
+
+#[test]
+fn case_sensitive_subcommand_by_default() {
+    let m = App::new("myprog")
+        .subcommand(App::new("build"))
+        .try_get_matches_from(vec!["myprog", "BUILD"]);
+
+    assert!(m.is_err());
+}
+
+#[test]
+#[cfg(feature = "suggestions")]
+fn subcmd_did_you_mean_visible_alias() {
+    let m = App::new("dym")
+        .subcommand(App::new("install").visible_alias("setup"))
+        .try_get_matches_from(vec!["dym", "setpu"]);
+    assert!(m.is_err());
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidSubcommand);
+    assert!(err.message.contains("Did you mean 'setup'?"));
+}
+
+#[test]
+#[cfg(feature = "suggestions")]
+fn subcmd_did_you_mean_not_hidden() {
+    let m = App::new("dym")
+        .subcommand(App::new("install").setting(AppSettings::Hidden))
+        .subcommand(App::new("remove").alias("uninstall"))
+        .try_get_matches_from(vec!["dym", "instll"]);
+    assert!(m.is_err());
+    assert_ne!(m.unwrap_err().kind, ErrorKind::InvalidSubcommand);
+}
+
+#[test]
+#[cfg(feature = "suggestions")]
+fn subcmd_did_you_mean_disabled() {
+    let m = App::new("dym")
+        .unset_setting(AppSettings::SuggestSubcommands)
+        .subcommand(App::new("install"))
+        .try_get_matches_from(vec!["dym", "instll"]);
+    assert!(m.is_err());
+    assert_ne!(m.unwrap_err().kind, ErrorKind::InvalidSubcommand);
+}
+
+fn three_levels() -> App<'static> {
+    App::new("prog").subcommand(
+        App::new("remote").subcommand(
+            App::new("origin").subcommand(App::new("add").version("0.1").about("leaf")),
+        ),
+    )
+}
+
+#[test]
+fn nested_subcommand_help_shows_full_path() {
+    let m = three_levels().try_get_matches_from(vec!["prog", "remote", "origin", "add", "--help"]);
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.starts_with("prog-remote-origin-add 0.1"));
+    assert!(err.message.contains("USAGE:\n    prog remote origin add"));
+}
+
+#[test]
+fn nested_help_subcommand_shows_full_path() {
+    let m = three_levels().try_get_matches_from(vec!["prog", "help", "remote", "origin", "add"]);
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.starts_with("prog-remote-origin-add 0.1"));
+    assert!(err.message.contains("USAGE:\n    prog remote origin add"));
+}
+
+#[test]
+fn nested_subcommand_full_path_without_binary_name() {
+    let m = three_levels()
+        .setting(AppSettings::NoBinaryName)
+        .try_get_matches_from(vec!["remote", "origin", "add", "--help"]);
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.starts_with("prog-remote-origin-add 0.1"));
+    assert!(err.message.contains("USAGE:\n    prog remote origin add"));
+}
+
+#[test]
+fn nested_subcommand_version_shows_full_path() {
+    let mut app = three_levels();
+    app.try_get_matches_from_mut(vec!["prog", "remote", "origin", "add"])
+        .unwrap();
+    let add = &app.subcommands[0].subcommands[0].subcommands[0];
+    assert_eq!(add.get_bin_name(), Some("prog remote origin add"));
+
+    let mut ver = vec![];
+    add.write_version(&mut ver).unwrap();
+    assert_eq!(String::from_utf8(ver).unwrap(), "prog-remote-origin-add 0.1");
+}
+
+#[test]
+fn subcommands_iter_lists_declared() {
+    let app = App::new("git")
+        .subcommand(App::new("clone").about("Clone a repository"))
+        .subcommand(App::new("init"));
+    let scs: Vec<_> = app
+        .subcommands_iter()
+        .map(|sc| (sc.get_name(), sc.get_about()))
+        .collect();
+    assert_eq!(
+        scs,
+        vec![("clone", Some("Clone a repository")), ("init", None)]
+    );
+}
+
+fn sync_app<'b>() -> App<'b> {
+    App::new("prog")
+        .arg(Arg::with_name("verbose").short('v'))
+        .subcommand(
+            App::new("remote").subcommand(
+                App::new("update")
+                    .arg(Arg::with_name("all").long("all"))
+                    .arg(Arg::with_name("name").long("name").takes_value(true)),
+            ),
+        )
+}
+
+#[test]
+fn replace_token_with_subcommand_path() {
+    let m = sync_app()
+        .replace("--sync", &["remote", "update", "--all"])
+        .try_get_matches_from(vec!["prog", "-v", "--sync"])
+        .unwrap();
+    assert!(m.is_present("verbose"));
+    let update = m
+        .subcommand_matches("remote")
+        .and_then(|r| r.subcommand_matches("update"))
+        .unwrap();
+    assert!(update.is_present("all"));
+}
+
+#[test]
+fn replace_is_not_applied_to_its_output() {
+    let m = sync_app()
+        .replace("up", &["update"])
+        .replace("update", &["update", "--all"])
+        .try_get_matches_from(vec!["prog", "remote", "up"])
+        .unwrap();
+    let update = m
+        .subcommand_matches("remote")
+        .and_then(|r| r.subcommand_matches("update"))
+        .unwrap();
+    assert!(!update.is_present("all"));
+}
+
+#[test]
+fn replace_matches_whole_tokens_only() {
+    let m = sync_app()
+        .replace("update", &["update", "--all"])
+        .try_get_matches_from(vec!["prog", "remote", "update", "--name=update"])
+        .unwrap();
+    let update = m
+        .subcommand_matches("remote")
+        .and_then(|r| r.subcommand_matches("update"))
+        .unwrap();
+    assert!(update.is_present("all"));
+    assert_eq!(update.value_of("name"), Some("update"));
+}
+
+#[test]
+fn is_valid_arg_per_level() {
+    let m = App::new("prog")
+        .arg(Arg::from("-v, --verbose 'be verbose'"))
+        .arg(Arg::from("--major 'bump the major version'"))
+        .group(ArgGroup::with_name("vers").arg("major"))
+        .subcommand(App::new("sub").arg(Arg::from("--all 'everything'")))
+        .get_matches_from(vec!["prog", "sub"]);
+
+    assert!(m.is_valid_arg("verbose"));
+    assert!(!m.is_present("verbose"));
+    assert!(m.is_valid_arg("vers"));
+    assert!(m.is_valid_arg("help"));
+    assert!(!m.is_valid_arg("verbsoe"));
+    assert!(!m.is_valid_arg("all"));
+
+    let sub = m.subcommand_matches("sub").unwrap();
+    assert!(sub.is_valid_arg("all"));
+    assert!(!sub.is_valid_arg("major"));
+}
+
+#[test]
+fn remove_subcommand_takes_matches() {
+    let mut m = App::new("prog")
+        .setting(AppSettings::AllowMultipleSubcommands)
+        .subcommand(App::new("push").arg(Arg::with_name("remote")))
+        .get_matches_from(vec!["prog", "push", "origin", "push", "upstream"]);
+
+    let (name, sub_m) = m.remove_subcommand().unwrap();
+    assert_eq!(name, "push");
+    assert_eq!(sub_m.value_of("remote"), Some("origin"));
+
+    let (_, sub_m) = m.remove_subcommand().unwrap();
+    assert_eq!(sub_m.value_of("remote"), Some("upstream"));
+
+    assert!(m.remove_subcommand().is_none());
+    assert_eq!(m.subcommand_name(), None);
+    assert!(!m.is_present("push"));
+    assert!(m.subcommand_matches_all().is_empty());
+}
+
+#[test]
+fn subcommands_in_usage() {
+    let mut app = App::new("myprog")
+        .setting(AppSettings::ShowSubcommandsInUsage)
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(App::new("test").display_order(1))
+        .subcommand(App::new("build"))
+        .subcommand(App::new("secret").setting(AppSettings::Hidden));
+    assert_eq!(
+        app.generate_usage(),
+        "USAGE:\n    myprog <build|help|test>"
+    );
+}
+
+#[test]
+fn subcommands_in_usage_capped() {
+    let mut app = App::new("myprog")
+        .setting(AppSettings::ShowSubcommandsInUsage)
+        .setting(AppSettings::DisableHelpSubcommand)
+        .subcommands(
+            ["a", "b", "c", "d", "e", "f"]
+                .iter()
+                .map(|&name| App::new(name)),
+        );
+    assert_eq!(
+        app.generate_usage(),
+        "USAGE:\n    myprog [a|b|c|d|e|...]"
+    );
+}
+
+#[test]
+fn subcommand_placeholder_by_default() {
+    let mut app = App::new("myprog").subcommand(App::new("test"));
+    assert_eq!(app.generate_usage(), "USAGE:\n    myprog [SUBCOMMAND]");
+}
+
+#[test]
+fn overlay_subcommands_by_name() {
+    let app = App::new("git")
+        .arg(Arg::from("--dir [path] 'run in path'"))
+        .subcommand(
+            App::new("push")
+                .arg(Arg::from("--force 'force it'"))
+                .arg(Arg::from("[remote] 'the remote'")),
+        )
+        .subcommand(App::new("pull"));
+
+    let config = app
+        .clone()
+        .get_matches_from(vec!["git", "--dir", "/repo", "push", "--force", "origin"]);
+    let mut m = app.clone().get_matches_from(vec!["git", "push", "upstream"]);
+    m.overlay(config);
+    assert_eq!(m.value_of("dir"), Some("/repo"));
+    let push = m.subcommand_matches("push").unwrap();
+    assert_eq!(push.value_of("remote"), Some("upstream"));
+    assert!(push.is_present("force"));
+
+    // A different subcommand isn't overlaid
+    let config = app
+        .clone()
+        .get_matches_from(vec!["git", "push", "--force"]);
+    let mut m = app.clone().get_matches_from(vec!["git", "pull"]);
+    m.overlay(config);
+    assert_eq!(m.subcommand_name(), Some("pull"));
+    assert!(m.subcommand_matches("push").is_none());
+
+    // Without a subcommand the lower one is taken
+    let config = app.clone().get_matches_from(vec!["git", "pull"]);
+    let mut m = app.get_matches_from(vec!["git"]);
+    m.overlay(config);
+    assert_eq!(m.subcommand_name(), Some("pull"));
+}
@@ -1,7 +1,7 @@
 extern crate clap;
 extern crate regex;
 
-use clap::App;
+use clap::{App, AppSettings, ErrorKind};
 
 include!("../clap-test.rs");
 
@@ -49,6 +49,11 @@ SUBCOMMANDS:
     help    Prints this message or the help of the given subcommand(s)
     test    does testing things";
 
+static TAB_WIDTH_HELP: &str = "MyApp  1.0
+  -d             Turn debugging information on
+  -h, --help     Prints help information
+  -V, --version  Prints version information";
+
 #[test]
 fn with_template() {
     let app = app_example1().help_template(EXAMPLE1_TMPL_S);
@@ -126,6 +131,210 @@ fn template_author_version() {
     ));
 }
 
+#[test]
+fn template_tab() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .help_template("{bin}{tab}{version}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "MyApp    1.0",
+        false
+    ));
+}
+
+#[test]
+fn template_tab_width() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .tab_width(2)
+        .arg("-d 'Turn debugging information on'")
+        .help_template("{bin}{tab}{version}\n{flags}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        TAB_WIDTH_HELP,
+        false
+    ));
+}
+
+#[test]
+fn template_tab_width_zero() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .tab_width(0)
+        .help_template("{bin}{tab}{version}");
+    assert!(test::compare_output(app, "MyApp --help", "MyApp1.0", false));
+}
+
+#[test]
+fn template_columns() {
+    let app = App::new("My App Name")
+        .version("1.0")
+        .min_term_width(0)
+        .help_template("{columns:6}{bin}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "My App\nName",
+        false
+    ));
+}
+
+#[test]
+fn template_columns_raised_to_minimum() {
+    let app = App::new("My App Name")
+        .version("1.0")
+        .help_template("{columns:6}{bin}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "My App Name",
+        false
+    ));
+}
+
+#[test]
+fn template_columns_zero() {
+    let app = App::new("My App Name")
+        .version("1.0")
+        .set_term_width(6)
+        .help_template("{columns:0}{bin}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "My App Name",
+        false
+    ));
+}
+
+#[test]
+fn template_columns_invalid() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .help_template("{columns:abc}{bin}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "{columns:abc}MyApp",
+        false
+    ));
+}
+
+#[test]
+fn template_subcommands_all() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .subcommand(App::new("a").about("visible"))
+        .subcommand(App::new("b").about("hidden").setting(AppSettings::Hidden))
+        .help_template("{subcommands-all}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "a       visible
+    b       hidden
+    help    Prints this message or the help of the given subcommand(s)",
+        false
+    ));
+}
+
+#[test]
+fn template_subcommands_skips_hidden() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .subcommand(App::new("a").about("visible"))
+        .subcommand(App::new("b").about("hidden").setting(AppSettings::Hidden))
+        .help_template("{subcommands}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "a       visible
+    help    Prints this message or the help of the given subcommand(s)",
+        false
+    ));
+}
+
+#[test]
+fn template_partial() {
+    let app = App::new("MyApp")
+        .version("1.0")
+        .about("Does awesome things")
+        .help_partial("header", "{bin} {version}\n{about}")
+        .help_template("{@header}\n{@missing}");
+    assert!(test::compare_output(
+        app,
+        "MyApp --help",
+        "MyApp 1.0\nDoes awesome things\n{@missing}",
+        false
+    ));
+}
+
+#[test]
+fn template_partial_inherited() {
+    let app = App::new("MyApp")
+        .help_partial("header", "[{about}]")
+        .subcommand(
+            App::new("sub")
+                .about("Sub things")
+                .help_template("{@header}"),
+        );
+    assert!(test::compare_output(
+        app,
+        "MyApp sub --help",
+        "[Sub things]",
+        false
+    ));
+}
+
+#[test]
+fn template_partial_recursive() {
+    let mut app = App::new("MyApp")
+        .help_partial("loop", "{@loop}")
+        .help_template("{@loop}");
+    let err = app.write_help_to_string().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Format);
+}
+
+#[test]
+fn template_usage_wraps_with_hanging_indent() {
+    let app = App::new("prog")
+        .set_term_width(30)
+        .help_template("USAGE:\n    {usage}")
+        .arg("-v 'verbose'")
+        .arg("<alpha> 'a'")
+        .arg("<bravo> 'b'")
+        .arg("<charlie> 'c'")
+        .arg("<delta> 'd'")
+        .arg("<echo> 'e'");
+    assert!(test::compare_output(
+        app,
+        "prog --help",
+        "USAGE:\n    prog [FLAGS] <alpha>\n         <bravo> <charlie>\n         <delta> <echo>",
+        false
+    ));
+}
+
+#[test]
+fn template_usage_hangs_after_written_tags() {
+    // The column is the width of "prog ", not of "{bin} "
+    let app = App::new("prog")
+        .set_term_width(30)
+        .help_template("{bin} {usage}")
+        .arg("-v 'verbose'")
+        .arg("<alpha> 'a'")
+        .arg("<bravo> 'b'")
+        .arg("<charlie> 'c'")
+        .arg("<delta> 'd'")
+        .arg("<echo> 'e'");
+    assert!(test::compare_output(
+        app,
+        "prog --help",
+        "prog prog [FLAGS] <alpha>\n          <bravo> <charlie>\n          <delta> <echo>",
+        false
+    ));
+}
+
 // ----------
 
 fn app_example1<'b, 'c>() -> App<'c> {
@@ -3,8 +3,8 @@
 extern crate clap;
 
 use clap::{App, AppSettings, Arg, ErrorKind};
-use std::ffi::OsString;
-use std::os::unix::ffi::OsStringExt;
+use std::ffi::{OsStr, OsString};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
 
 #[test]
 fn invalid_utf8_strict_positional() {
@@ -266,3 +266,45 @@ fn invalid_utf8_option_long_equals() {
         &*OsString::from_vec(vec![0xe9])
     );
 }
+
+#[test]
+fn possible_values_os_exact_match() {
+    let m = App::new("bad_utf8")
+        .arg(
+            Arg::with_name("arg")
+                .possible_values_os(&[OsStr::from_bytes(b"caf\xe9"), OsStr::new("tea")]),
+        )
+        .try_get_matches_from(vec![OsString::from(""), OsString::from_vec(b"caf\xe9".to_vec())]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err());
+    assert_eq!(
+        m.unwrap().value_of_os("arg").unwrap().as_bytes(),
+        b"caf\xe9"
+    );
+}
+
+#[test]
+fn possible_values_os_no_lossy_match() {
+    // Both values are displayed as "caf\u{FFFD}", but they aren't the same bytes
+    let m = App::new("bad_utf8")
+        .arg(Arg::with_name("arg").possible_values_os(&[OsStr::from_bytes(b"caf\xe9")]))
+        .try_get_matches_from(vec![OsString::from(""), OsString::from_vec(b"caf\xff".to_vec())]);
+    assert!(m.is_err());
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert!(err.message.contains("[possible values: caf\u{FFFD}]"));
+}
+
+#[test]
+fn possible_values_os_with_str_values() {
+    let app = App::new("bad_utf8").arg(
+        Arg::with_name("arg")
+            .possible_values(&["tea"])
+            .possible_values_os(&[OsStr::from_bytes(b"caf\xe9")]),
+    );
+    assert!(app.clone().try_get_matches_from(vec!["", "tea"]).is_ok());
+    assert!(app
+        .clone()
+        .try_get_matches_from(vec![OsString::from(""), OsString::from_vec(b"caf\xe9".to_vec())])
+        .is_ok());
+    assert!(app.try_get_matches_from(vec!["", "coffee"]).is_err());
+}
@@ -59,3 +59,96 @@ fn override_ver() {
     assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
     assert!(m.unwrap().is_present("version"));
 }
+
+#[test]
+fn version_template() {
+    let mut a = App::new("myapp")
+        .version("1.2.3")
+        .long_version("1.2.3-long")
+        .build_info("abc123 2024-01-01")
+        .version_template("{bin} {version} ({build}) {unknown}");
+    let _ = a.try_get_matches_from_mut(vec![""]);
+
+    let mut ver = vec![];
+    a.write_version(&mut ver).unwrap();
+    assert_eq!(
+        str::from_utf8(&ver).unwrap(),
+        "myapp 1.2.3 (abc123 2024-01-01) {unknown}"
+    );
+
+    let mut ver = vec![];
+    a.write_long_version(&mut ver).unwrap();
+    assert_eq!(
+        str::from_utf8(&ver).unwrap(),
+        "myapp 1.2.3-long (abc123 2024-01-01) {unknown}"
+    );
+}
+
+#[test]
+fn version_template_output() {
+    let mut a = App::new("myapp")
+        .version("1.2.3")
+        .build_info("abc123")
+        .version_template("{bin} v{version} [{build}]");
+    let m = a.try_get_matches_from_mut(vec!["myapp", "--version"]);
+    assert_eq!(m.unwrap_err().kind, ErrorKind::VersionDisplayed);
+
+    let mut ver = vec![];
+    a.write_long_version(&mut ver).unwrap();
+    assert_eq!(str::from_utf8(&ver).unwrap(), "myapp v1.2.3 [abc123]");
+}
+
+fn version_after_parsing(app: App<'static>, argv0: &str) -> String {
+    let mut app = app.version("1.3");
+    app.try_get_matches_from_mut(vec![argv0]).unwrap();
+    let mut ver = vec![];
+    app.write_version(&mut ver).unwrap();
+    String::from_utf8(ver).unwrap()
+}
+
+#[test]
+fn version_uses_invoked_bin_name() {
+    assert_eq!(
+        version_after_parsing(App::new("clap-test"), "/usr/local/bin/ctest"),
+        "ctest 1.3"
+    );
+}
+
+#[test]
+fn version_keeps_dotted_bin_name() {
+    assert_eq!(
+        version_after_parsing(App::new("clap-test"), "/usr/bin/python3.8"),
+        "python3.8 1.3"
+    );
+}
+
+#[test]
+#[cfg(windows)]
+fn version_strips_exe() {
+    assert_eq!(
+        version_after_parsing(App::new("clap-test"), r"C:\bin\ctest.exe"),
+        "ctest 1.3"
+    );
+}
+
+#[test]
+#[cfg(not(windows))]
+fn version_keeps_exe_off_windows() {
+    assert_eq!(
+        version_after_parsing(App::new("clap-test"), "/usr/bin/ctest.exe"),
+        "ctest.exe 1.3"
+    );
+}
+
+#[test]
+fn version_keeps_explicit_bin_name() {
+    assert_eq!(
+        version_after_parsing(App::new("clap-test").bin_name("clap"), "ctest"),
+        "clap 1.3"
+    );
+}
+
+#[test]
+fn version_empty_argv0_uses_name() {
+    assert_eq!(version_after_parsing(App::new("clap-test"), ""), "clap-test 1.3");
+}
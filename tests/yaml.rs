@@ -45,3 +45,47 @@ fn author() {
     let help_string = String::from_utf8(help_buffer).unwrap();
     assert!(help_string.contains("Kevin K. <kbknapp@gmail.com>"));
 }
+
+#[test]
+fn last_positional() {
+    let yml = load_yaml!("app.yml");
+    let m = App::from(yml)
+        .try_get_matches_from(vec!["prog", "subcmd", "pos", "--", "one", "two"])
+        .unwrap();
+    let sc_m = m.subcommand_matches("subcmd").unwrap();
+    assert_eq!(sc_m.value_of("scpositional"), Some("pos"));
+    assert_eq!(
+        sc_m.values_of("sclast").unwrap().collect::<Vec<_>>(),
+        &["one", "two"]
+    );
+
+    let m = App::from(yml).try_get_matches_from(vec!["prog", "subcmd", "pos", "one"]);
+    assert!(m.is_err());
+}
+
+#[test]
+fn multiple_occurrences_and_values() {
+    let yml = load_yaml!("app.yml");
+    let m = App::from(yml)
+        .try_get_matches_from(vec![
+            "prog", "--define", "a=1", "--define", "b=2", "--files", "x", "y",
+        ])
+        .unwrap();
+    assert_eq!(m.occurrences_of("define"), 2);
+    assert_eq!(
+        m.values_of("define").unwrap().collect::<Vec<_>>(),
+        &["a=1", "b=2"]
+    );
+    assert_eq!(m.occurrences_of("files"), 1);
+    assert_eq!(
+        m.values_of("files").unwrap().collect::<Vec<_>>(),
+        &["x", "y"]
+    );
+
+    // Only one value per occurrence, so the next value is a positional
+    let m = App::from(yml)
+        .try_get_matches_from(vec!["prog", "--define", "a=1", "pos"])
+        .unwrap();
+    assert_eq!(m.values_of("define").unwrap().count(), 1);
+    assert_eq!(m.value_of("positional"), Some("pos"));
+}